@@ -2,9 +2,10 @@ use crate::abilities::{Ability, AbilityCooldown, DestroyAbility};
 use crate::buildings::building_pathfinding::PathfindStrengthExt;
 use crate::buildings::{get_neighbors_tilepos, Activate, Simulate};
 use crate::color_system::{ColorConflictGuarantees, ConflictType, TileColor};
+use crate::game::game_rng::GameRng;
 use crate::objects::ObjectCachedMap;
 use bevy::prelude::{
-    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, With, Without,
+    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, ResMut, With, Without,
 };
 use bevy_ecs_tilemap::prelude::*;
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
@@ -52,6 +53,7 @@ pub fn simulate_expand_from_cache(
         (Without<MapId>, With<Activate>, With<Simulate>),
     >,
     mut event_writer: EventWriter<ColorConflictGuarantees>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     let Some((_, _, tile_storage, tilemap_size)) = tile_storage_query
@@ -65,7 +67,6 @@ pub fn simulate_expand_from_cache(
         expands.iter()
     {
         commands.entity(entity).remove::<Activate>();
-        let mut rng = rand::thread_rng();
 
         let rndm = rng.gen_range(
             expand.ability_type.min_tile_strengthen..=expand.ability_type.max_tile_strengthen,
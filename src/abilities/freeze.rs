@@ -0,0 +1,67 @@
+use crate::abilities::{Ability, AbilityCooldown, DestroyAbility};
+use crate::buildings::building_pathfinding::PathfindStrengthExt;
+use crate::buildings::{Activate, Simulate};
+use crate::objects::{ObjectIndex, TileToObjectIndex};
+use bevy::prelude::{Commands, Component, Entity, FromReflect, Query, Reflect, With, Without};
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::mapping::MapId;
+
+/// Locks every tile in its range for as long as it lasts. Both regular and guaranteed conflicts on
+/// frozen tiles are ignored, so not even a nuke changes them
+#[derive(Default, Clone, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect)]
+pub struct Freeze {
+    pub strength: u32,
+}
+
+impl Freeze {
+    pub const STRENGTH: u32 = 3;
+    /// How many times the freeze activates before it wears off
+    pub const DURATION_TICKS: u32 = 10;
+}
+
+impl PathfindStrengthExt for Freeze {
+    fn pathfinding_strength(&self) -> u32 {
+        self.strength
+    }
+}
+
+/// Returns whether any freeze covers the tile
+pub fn tile_is_frozen(
+    tile_pos: &TilePos,
+    tile_to_object_index: &TileToObjectIndex,
+    object_index: &ObjectIndex,
+    freezes: &Query<Entity, (With<Ability<Freeze>>, Without<Tile>)>,
+) -> bool {
+    let Some(object_vec) = tile_to_object_index.map.get(tile_pos) else {
+        return false;
+    };
+
+    object_vec.iter().any(|object_id| {
+        object_index
+            .hashmap
+            .get(object_id)
+            .map_or(false, |entity| freezes.get(*entity).is_ok())
+    })
+}
+
+/// Freezes don't do anything when they activate, they only count down until they run out
+pub fn simulate_freeze(
+    freezes: Query<
+        (Entity, &AbilityCooldown),
+        (
+            With<Ability<Freeze>>,
+            Without<MapId>,
+            With<Activate>,
+            With<Simulate>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (entity, ability_cooldown) in freezes.iter() {
+        commands.entity(entity).remove::<Activate>();
+        if ability_cooldown.timer_ticks == 0 {
+            commands.entity(entity).insert(DestroyAbility);
+        }
+    }
+}
@@ -1,14 +1,20 @@
 pub mod expand;
 pub mod fortify;
+pub mod freeze;
 pub mod nuke;
-
-use std::time::Duration;
+pub mod shield;
+pub mod swap;
 
 use crate::abilities::expand::Expand;
 use crate::abilities::fortify::Fortify;
+use crate::abilities::freeze::Freeze;
 use crate::abilities::nuke::Nuke;
+use crate::abilities::shield::Shield;
+use crate::abilities::swap::Swap;
+use crate::buildings::{check_is_colorable, tile_distance};
 use crate::buildings::{Activate, Simulate, TimeSimulatingStopped};
-use crate::game::GameData;
+use crate::game::puzzle::PuzzleState;
+use crate::game::{GameBuildSettings, GameData};
 use crate::player::PlayerPoints;
 use bevy::ecs::system::SystemState;
 use bevy::prelude::{
@@ -27,6 +33,12 @@ use bevy_ggf::object::{Object, ObjectGridPosition, ObjectId, ObjectInfo};
 use bevy_ggf::player::{Player, PlayerMarker};
 use serde::Deserialize;
 
+/// Ability points it costs to place any ability
+pub const ABILITY_COST: u32 = 50;
+
+/// Seconds between two activations of an ability that lasts for a while
+pub const LASTING_ABILITY_TICK_SECONDS: f32 = 0.5;
+
 pub trait SpawnAbilityExt {
     fn spawn_ability(
         &mut self,
@@ -34,6 +46,13 @@ pub trait SpawnAbilityExt {
         player_id: usize,
         target_tile: TilePos,
     ) -> SpawnAbility;
+
+    fn spawn_swap_ability(
+        &mut self,
+        player_id: usize,
+        target_tile: TilePos,
+        swap_tile: TilePos,
+    ) -> SpawnAbility;
 }
 
 impl SpawnAbilityExt for GameCommands {
@@ -47,11 +66,33 @@ impl SpawnAbilityExt for GameCommands {
             ability_type: ability.clone(),
             player_id: player_id.clone(),
             target_tile_pos: target_tile.clone(),
+            swap_tile_pos: None,
         });
         SpawnAbility {
             ability_type: ability.clone(),
             player_id: player_id.clone(),
             target_tile_pos: target_tile.clone(),
+            swap_tile_pos: None,
+        }
+    }
+
+    fn spawn_swap_ability(
+        &mut self,
+        player_id: usize,
+        target_tile: TilePos,
+        swap_tile: TilePos,
+    ) -> SpawnAbility {
+        self.queue.push(SpawnAbility {
+            ability_type: Abilities::Swap,
+            player_id,
+            target_tile_pos: target_tile,
+            swap_tile_pos: Some(swap_tile),
+        });
+        SpawnAbility {
+            ability_type: Abilities::Swap,
+            player_id,
+            target_tile_pos: target_tile,
+            swap_tile_pos: Some(swap_tile),
         }
     }
 }
@@ -61,10 +102,36 @@ pub struct SpawnAbility {
     pub ability_type: Abilities,
     pub player_id: usize,
     pub target_tile_pos: TilePos,
+    /// The tile the area around the target tile is swapped with. Only used by [`Abilities::Swap`]
+    pub swap_tile_pos: Option<TilePos>,
 }
 
 impl GameCommand for SpawnAbility {
     fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let game_settings = world.resource::<GameBuildSettings>();
+        if self.player_id == 0 && !game_settings.restrictions.allows_ability(self.ability_type) {
+            return Err(format!(
+                "{:?} is restricted in this mission",
+                self.ability_type
+            ));
+        }
+
+        // Puzzles place from the inventory instead of spending points
+        let from_inventory = self.player_id == 0 && world.contains_resource::<PuzzleState>();
+        if from_inventory
+            && world
+                .resource::<PuzzleState>()
+                .inventory
+                .ability_count(self.ability_type)
+                == 0
+        {
+            return Err(format!("No {:?} left to place", self.ability_type));
+        }
+        let cost = match from_inventory {
+            true => 0,
+            false => ABILITY_COST,
+        };
+
         let game_data = world.remove_resource::<GameData>().unwrap();
 
         let mut system_state: SystemState<(
@@ -85,10 +152,11 @@ impl GameCommand for SpawnAbility {
 
         let result = match self.ability_type {
             Abilities::Nuke => {
-                if player_points.ability_points >= 50 {
+                if player_points.ability_points >= cost {
                     //actions.placed_ability = true;
 
-                    player_points.ability_points = player_points.ability_points.saturating_sub(50);
+                    player_points.ability_points =
+                        player_points.ability_points.saturating_sub(cost);
 
                     world.entity_mut(entity).insert(Changed::default());
 
@@ -157,10 +225,11 @@ impl GameCommand for SpawnAbility {
                     return Err("Tile is not a Colorable Tile".parse().unwrap());
                 }
 
-                if player_points.ability_points >= 50 && player_marker.id() == self.player_id {
+                if player_points.ability_points >= cost && player_marker.id() == self.player_id {
                     //actions.placed_ability = true;
 
-                    player_points.ability_points = player_points.ability_points.saturating_sub(50);
+                    player_points.ability_points =
+                        player_points.ability_points.saturating_sub(cost);
                     world.entity_mut(entity).insert(Changed::default());
 
                     let mut spawn = game_commands.spawn_object(
@@ -224,10 +293,11 @@ impl GameCommand for SpawnAbility {
                     return Err("Tile is not a Colorable Tile".parse().unwrap());
                 }
 
-                if player_points.ability_points >= 50 {
+                if player_points.ability_points >= cost {
                     //actions.placed_ability = true;
 
-                    player_points.ability_points = player_points.ability_points.saturating_sub(50);
+                    player_points.ability_points =
+                        player_points.ability_points.saturating_sub(cost);
                     world.entity_mut(entity).insert(Changed::default());
 
                     let mut spawn = game_commands.spawn_object(
@@ -273,14 +343,148 @@ impl GameCommand for SpawnAbility {
                     Err(String::from("Not enough points to place"))
                 }
             }
+            Abilities::Freeze => self.spawn_lasting_ability(
+                world,
+                &game_data,
+                cost,
+                Freeze {
+                    strength: Freeze::STRENGTH,
+                },
+                Freeze::DURATION_TICKS,
+                false,
+            ),
+            Abilities::Swap => match self.swap_tile_pos {
+                None => Err(String::from("Swap needs a tile to swap with")),
+                // The two areas can't overlap or tiles would be swapped twice
+                Some(swap_tile_pos)
+                    if tile_distance(self.target_tile_pos, swap_tile_pos) < Swap::MIN_DISTANCE =>
+                {
+                    Err(String::from("Swapped areas overlap"))
+                }
+                Some(swap_tile_pos) => self.spawn_lasting_ability(
+                    world,
+                    &game_data,
+                    cost,
+                    Swap {
+                        strength: Swap::STRENGTH,
+                        swap_tile_pos,
+                    },
+                    Swap::DURATION_TICKS,
+                    true,
+                ),
+            },
+            Abilities::Shield => self.spawn_lasting_ability(
+                world,
+                &game_data,
+                cost,
+                Shield {
+                    strength: Shield::STRENGTH,
+                },
+                Shield::DURATION_TICKS,
+                true,
+            ),
         };
 
         world.insert_resource(game_data);
 
+        if from_inventory && result.is_ok() {
+            world
+                .resource_mut::<PuzzleState>()
+                .inventory
+                .take_ability(self.ability_type);
+        }
+
         return result;
     }
 }
 
+impl SpawnAbility {
+    /// Places an ability that activates every [`LASTING_ABILITY_TICK_SECONDS`] for
+    /// `duration_ticks` activations. Shared by [`Freeze`], [`Swap`] and [`Shield`]
+    fn spawn_lasting_ability<T>(
+        &self,
+        world: &mut World,
+        game_data: &GameData,
+        cost: u32,
+        ability_type: T,
+        duration_ticks: u32,
+        requires_player_territory: bool,
+    ) -> Result<(), String>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let mut system_state: SystemState<(
+            Query<(Entity, &Player, &mut PlayerPoints)>,
+            Query<(Option<&PlayerMarker>, &TilePos, &TileTerrainInfo), With<Tile>>,
+        )> = SystemState::new(world);
+        let (mut players, tiles) = system_state.get_mut(world);
+
+        let Some((player_entity, _, mut player_points)) = players
+            .iter_mut()
+            .find(|(_, id, _)| id.id() == self.player_id)
+        else {
+            return Err(String::from("Failed to Find Player ID"));
+        };
+
+        let Some((player_marker, _, tile_terrain_info)) =
+            tiles.iter().find(|(_, id, _)| id == &&self.target_tile_pos)
+        else {
+            return Err(format!("Failed to Find {:?} Tile Pos", self.ability_type));
+        };
+
+        if !check_is_colorable(tile_terrain_info) {
+            return Err(String::from("Tile is not a Colorable Tile"));
+        }
+
+        if requires_player_territory
+            && player_marker.map_or(true, |player_marker| player_marker.id() != self.player_id)
+        {
+            return Err(String::from("Tile not owned by placing player"));
+        }
+
+        if player_points.ability_points < cost {
+            return Err(String::from("Not enough points to place"));
+        }
+        player_points.ability_points -= cost;
+        world.entity_mut(player_entity).insert(Changed::default());
+
+        let object_type_name = format!("{:?}", self.ability_type);
+        GameCommands::new()
+            .spawn_object(
+                (
+                    ObjectGridPosition {
+                        tile_position: self.target_tile_pos.into(),
+                    },
+                    ObjectStackingClass {
+                        stack_class: game_data.stacking_classes.get("Ability").unwrap().clone(),
+                    },
+                    Object,
+                    ObjectInfo {
+                        object_type: game_data
+                            .object_types
+                            .get(object_type_name.as_str())
+                            .unwrap()
+                            .clone(),
+                    },
+                    Ability { ability_type },
+                    AbilityCooldown {
+                        timer: Timer::from_seconds(0.0, TimerMode::Once),
+                        timer_reset: LASTING_ABILITY_TICK_SECONDS,
+                        timer_ticks: duration_ticks,
+                    },
+                    AbilityMarker {
+                        requires_player_territory,
+                    },
+                    Simulate,
+                ),
+                self.target_tile_pos,
+                MapId { id: 1 },
+                self.player_id,
+            )
+            .execute(world)
+    }
+}
+
 pub fn destroy_abilities(
     abilities: Query<
         (
@@ -327,26 +531,16 @@ pub fn update_ability_timers(
 ) {
     for (entity, mut timer, opt_time_simulating_stopped) in timers.iter_mut() {
         if let Some(time_simulating_stopped) = opt_time_simulating_stopped {
-            if time_simulating_stopped
-                .time_stopped
-                .elapsed()
-                .unwrap_or(Duration::default())
-                > timer.timer.remaining()
-            {
-                commands
-                    .entity(entity)
-                    .insert(Activate)
-                    .remove::<TimeSimulatingStopped>();
-                timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
-                timer.timer_ticks = timer.timer_ticks.saturating_sub(1);
-            }
+            timer.timer.tick(time_simulating_stopped.time_asleep(&time));
+            commands.entity(entity).remove::<TimeSimulatingStopped>();
         } else {
             timer.timer.tick(time.delta());
-            if timer.timer.finished() {
-                commands.entity(entity).insert(Activate);
-                timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
-                timer.timer_ticks = timer.timer_ticks.saturating_sub(1);
-            }
+        }
+
+        if timer.timer.finished() {
+            commands.entity(entity).insert(Activate);
+            timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
+            timer.timer_ticks = timer.timer_ticks.saturating_sub(1);
         }
     }
 }
@@ -372,6 +566,9 @@ pub enum Abilities {
     Nuke,
     Fortify,
     Expand,
+    Freeze,
+    Swap,
+    Shield,
 }
 
 #[derive(Default, Clone, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect)]
@@ -390,3 +587,48 @@ pub struct AbilityCooldown {
     pub timer_ticks: u32,
     pub timer_reset: f32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{put_to_sleep, step, tick, timer_world};
+
+    fn cooldown(seconds: f32) -> AbilityCooldown {
+        AbilityCooldown {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            timer_ticks: 3,
+            timer_reset: seconds,
+        }
+    }
+
+    #[test]
+    fn sleeping_ability_catches_up_like_an_awake_one() {
+        let (mut world, mut schedule) = timer_world(update_ability_timers);
+        let awake = world.spawn((cooldown(10.0), Simulate)).id();
+        let asleep = world.spawn((cooldown(10.0), Simulate)).id();
+        let about_to_fire = world.spawn((cooldown(0.5), Simulate)).id();
+        step(&mut world, &mut schedule, 5);
+
+        put_to_sleep(&mut world, asleep);
+        put_to_sleep(&mut world, about_to_fire);
+
+        step(&mut world, &mut schedule, 20);
+        world.entity_mut(asleep).insert(Simulate);
+        world.entity_mut(about_to_fire).insert(Simulate);
+        step(&mut world, &mut schedule, 1);
+
+        let elapsed = |entity: Entity| {
+            world
+                .get::<AbilityCooldown>(entity)
+                .unwrap()
+                .timer
+                .elapsed()
+        };
+        assert_eq!(elapsed(awake), tick() * 26);
+        assert_eq!(elapsed(asleep), elapsed(awake));
+
+        let about_to_fire_cooldown = world.get::<AbilityCooldown>(about_to_fire).unwrap();
+        assert!(world.get::<Activate>(about_to_fire).is_some());
+        assert_eq!(about_to_fire_cooldown.timer_ticks, 2);
+    }
+}
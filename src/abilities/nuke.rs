@@ -2,9 +2,10 @@ use crate::abilities::{Ability, AbilityCooldown, DestroyAbility};
 use crate::buildings::building_pathfinding::PathfindStrengthExt;
 use crate::buildings::{Activate, Simulate};
 use crate::color_system::{ColorConflictGuarantees, ConflictType};
+use crate::game::game_rng::GameRng;
 use crate::objects::ObjectCachedMap;
 use bevy::prelude::{
-    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, With, Without,
+    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, ResMut, With, Without,
 };
 use bevy_ecs_tilemap::prelude::TilePos;
 use bevy_ggf::mapping::MapId;
@@ -40,12 +41,12 @@ pub fn simulate_nuke_from_cache(
         (Without<MapId>, With<Activate>, With<Simulate>),
     >,
     mut event_writer: EventWriter<ColorConflictGuarantees>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     for (entity, _, player_marker, nuke, ability_cooldown, cache) in pulsers.iter() {
         commands.entity(entity).remove::<Activate>();
 
-        let mut rng = rand::thread_rng();
         for tile in cache.cache.iter() {
             let rndm = rng
                 .gen_range(nuke.ability_type.min_tile_damage..=nuke.ability_type.max_tile_damage);
@@ -0,0 +1,72 @@
+use crate::abilities::{Ability, AbilityCooldown, DestroyAbility};
+use crate::buildings::building_pathfinding::PathfindStrengthExt;
+use crate::buildings::{Activate, Simulate};
+use crate::objects::{ObjectIndex, TileToObjectIndex};
+use bevy::prelude::{Commands, Component, Entity, FromReflect, Query, Reflect, With, Without};
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::player::PlayerMarker;
+
+/// Makes the tiles of its player in its range immune to [`ConflictType::Damage`] for as long as it
+/// lasts
+///
+/// [`ConflictType::Damage`]: crate::color_system::ConflictType::Damage
+#[derive(Default, Clone, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect)]
+pub struct Shield {
+    pub strength: u32,
+}
+
+impl Shield {
+    pub const STRENGTH: u32 = 4;
+    /// How many times the shield activates before it wears off
+    pub const DURATION_TICKS: u32 = 16;
+}
+
+impl PathfindStrengthExt for Shield {
+    fn pathfinding_strength(&self) -> u32 {
+        self.strength
+    }
+}
+
+/// Returns whether a shield owned by `player_id` covers the tile
+pub fn tile_is_shielded(
+    tile_pos: &TilePos,
+    player_id: usize,
+    tile_to_object_index: &TileToObjectIndex,
+    object_index: &ObjectIndex,
+    shields: &Query<&PlayerMarker, (With<Ability<Shield>>, Without<Tile>)>,
+) -> bool {
+    let Some(object_vec) = tile_to_object_index.map.get(tile_pos) else {
+        return false;
+    };
+
+    object_vec.iter().any(|object_id| {
+        object_index
+            .hashmap
+            .get(object_id)
+            .and_then(|entity| shields.get(*entity).ok())
+            .map_or(false, |player_marker| player_marker.id() == player_id)
+    })
+}
+
+/// Shields don't do anything when they activate, they only count down until they run out
+pub fn simulate_shield(
+    shields: Query<
+        (Entity, &AbilityCooldown),
+        (
+            With<Ability<Shield>>,
+            Without<MapId>,
+            With<Activate>,
+            With<Simulate>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for (entity, ability_cooldown) in shields.iter() {
+        commands.entity(entity).remove::<Activate>();
+        if ability_cooldown.timer_ticks == 0 {
+            commands.entity(entity).insert(DestroyAbility);
+        }
+    }
+}
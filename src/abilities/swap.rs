@@ -0,0 +1,142 @@
+use crate::abilities::freeze::{tile_is_frozen, Freeze};
+use crate::abilities::{Ability, AbilityCooldown, DestroyAbility};
+use crate::buildings::building_pathfinding::PathfindStrengthExt;
+use crate::buildings::{check_is_colorable, Activate, Simulate};
+use crate::color_system::TileColor;
+use crate::objects::{wake_objects_on_tile, ObjectCachedMap, ObjectIndex, TileToObjectIndex};
+use bevy::prelude::{Commands, Component, Entity, FromReflect, Query, Reflect, Res, With, Without};
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage, TilemapSize};
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{ObjectGridPosition, ObjectId};
+use bevy_ggf::player::PlayerMarker;
+
+/// Exchanges the ownership of the area around it with the area of the same shape around
+/// `swap_tile_pos`. Pairs of tiles where either one is frozen are left alone
+#[derive(Default, Clone, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect)]
+pub struct Swap {
+    pub strength: u32,
+    pub swap_tile_pos: TilePos,
+}
+
+impl Swap {
+    pub const STRENGTH: u32 = 2;
+    /// Swaps happen all at once on the first activation
+    pub const DURATION_TICKS: u32 = 1;
+    /// How far apart the two swapped areas have to be so they don't overlap
+    pub const MIN_DISTANCE: u32 = Swap::STRENGTH * 2 + 1;
+}
+
+impl PathfindStrengthExt for Swap {
+    fn pathfinding_strength(&self) -> u32 {
+        self.strength
+    }
+}
+
+pub fn simulate_swap_from_cache(
+    mut tile_storage_query: Query<(&MapId, &TileStorage, &TilemapSize)>,
+    tiles: Query<
+        (&TileTerrainInfo, Option<(&PlayerMarker, &TileColor)>),
+        (With<Tile>, Without<Ability<Swap>>, Without<MapId>),
+    >,
+    swaps: Query<
+        (
+            Entity,
+            &ObjectGridPosition,
+            &Ability<Swap>,
+            &AbilityCooldown,
+            &ObjectCachedMap,
+        ),
+        (Without<MapId>, With<Activate>, With<Simulate>),
+    >,
+    tile_to_object_index: Res<TileToObjectIndex>,
+    object_index: Res<ObjectIndex>,
+    object_query: Query<(Entity, Option<&Simulate>), With<ObjectId>>,
+    freezes: Query<Entity, (With<Ability<Freeze>>, Without<Tile>)>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage, tilemap_size)) = tile_storage_query
+        .iter_mut()
+        .find(|(id, _, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    for (entity, object_grid_position, swap, ability_cooldown, cache) in swaps.iter() {
+        commands.entity(entity).remove::<Activate>();
+
+        let origin: TilePos = object_grid_position.tile_position.into();
+        let swap_tile_pos = swap.ability_type.swap_tile_pos;
+
+        for tile in cache.cache.iter() {
+            let tile_pos: TilePos = (*tile).into();
+            let Some(other_tile_pos) = TilePos::from_i32_pair(
+                swap_tile_pos.x as i32 + (tile_pos.x as i32 - origin.x as i32),
+                swap_tile_pos.y as i32 + (tile_pos.y as i32 - origin.y as i32),
+                tilemap_size,
+            ) else {
+                continue;
+            };
+
+            let (Some(tile_entity), Some(other_tile_entity)) = (
+                tile_storage.get(&tile_pos),
+                tile_storage.get(&other_tile_pos),
+            ) else {
+                continue;
+            };
+            let (Ok((tile_terrain_info, options)), Ok((other_tile_terrain_info, other_options))) =
+                (tiles.get(tile_entity), tiles.get(other_tile_entity))
+            else {
+                continue;
+            };
+            if !check_is_colorable(tile_terrain_info)
+                || !check_is_colorable(other_tile_terrain_info)
+            {
+                continue;
+            }
+            if [tile_pos, other_tile_pos].iter().any(|tile_pos| {
+                tile_is_frozen(tile_pos, &tile_to_object_index, &object_index, &freezes)
+            }) {
+                continue;
+            }
+
+            give_tile_ownership(tile_entity, other_options, &mut commands);
+            give_tile_ownership(other_tile_entity, options, &mut commands);
+
+            for changed_tile_pos in [tile_pos, other_tile_pos] {
+                wake_objects_on_tile(
+                    &changed_tile_pos,
+                    &tile_to_object_index,
+                    &object_index,
+                    &object_query,
+                    &mut commands,
+                );
+            }
+        }
+
+        if ability_cooldown.timer_ticks == 0 {
+            commands.entity(entity).insert(DestroyAbility);
+        }
+    }
+}
+
+fn give_tile_ownership(
+    tile_entity: Entity,
+    ownership: Option<(&PlayerMarker, &TileColor)>,
+    commands: &mut Commands,
+) {
+    match ownership {
+        Some((player_marker, tile_color)) => {
+            commands
+                .entity(tile_entity)
+                .insert((PlayerMarker::new(player_marker.id()), tile_color.clone()));
+        }
+        None => {
+            commands
+                .entity(tile_entity)
+                .remove::<PlayerMarker>()
+                .remove::<TileColor>();
+        }
+    }
+}
@@ -1,12 +1,16 @@
-use crate::abilities::SpawnAbilityExt;
+use crate::abilities::{Abilities, SpawnAbilityExt};
 use crate::actions::Actions;
 
-use crate::buildings::SpawnBuildingExt;
+use crate::buildings::artillery::RetargetArtilleryExt;
+use crate::buildings::upgrades::UpgradeBuildingExt;
+use crate::buildings::workshop::SpawnPainterExt;
+use crate::buildings::{DespawnBuildingExt, SpawnBuildingExt};
 use crate::draw::world_pos_to_tile_pos;
 use crate::game::GameData;
-use bevy::prelude::{Query, Res, ResMut};
+use bevy::prelude::{Query, Res, ResMut, With};
 use bevy_ecs_tilemap::prelude::{TilePos, TilemapSize};
 use bevy_ggf::game_core::command::GameCommands;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::player::{Player, PlayerMarker};
 use ns_defaults::camera::CursorWorldPos;
 
@@ -45,7 +49,12 @@ pub fn place_building(
                 continue;
             }
 
-            game_commands.spawn_building(actions.selected_building, player_id, target_tile_pos);
+            game_commands.spawn_building(
+                actions.selected_building,
+                actions.line_direction,
+                player_id,
+                target_tile_pos,
+            );
         }
     }
 }
@@ -53,6 +62,7 @@ pub fn place_building(
 pub fn place_ability(
     cursor_world_pos: Res<CursorWorldPos>,
     mut actions: Query<(Option<&PlayerMarker>, Option<&Player>, &mut Actions)>,
+    tiles: Query<(&TilePos, Option<&PlayerMarker>), With<TileTerrainInfo>>,
     mut game_commands: ResMut<GameCommands>,
     game_data: Res<GameData>,
 ) {
@@ -83,7 +93,186 @@ pub fn place_ability(
                 continue;
             }
 
+            // Swap takes two tiles, the first one picks the players own area and the second one
+            // the area it is swapped with. A first pick on a tile the player doesn't own is
+            // dropped right away instead of failing once the second tile is picked
+            if actions.selected_ability == Abilities::Swap {
+                match actions.swap_source_tile_pos {
+                    None => {
+                        let owns_tile = tiles.iter().any(|(tile_pos, opt_player_marker)| {
+                            *tile_pos == target_tile_pos
+                                && opt_player_marker
+                                    .map_or(false, |player_marker| player_marker.id() == player_id)
+                        });
+                        if owns_tile {
+                            actions.swap_source_tile_pos = Some(target_tile_pos.into());
+                        }
+                    }
+                    Some(swap_source_tile_pos) => {
+                        game_commands.spawn_swap_ability(
+                            player_id,
+                            swap_source_tile_pos.into(),
+                            target_tile_pos,
+                        );
+                        actions.swap_source_tile_pos = None;
+                    }
+                }
+                continue;
+            }
+
             game_commands.spawn_ability(actions.selected_ability, player_id, target_tile_pos);
         }
     }
 }
+
+pub fn retarget_artillery(
+    cursor_world_pos: Res<CursorWorldPos>,
+    mut actions: Query<(Option<&PlayerMarker>, Option<&Player>, &mut Actions)>,
+    mut game_commands: ResMut<GameCommands>,
+    game_data: Res<GameData>,
+) {
+    for (player_marker, player, mut actions) in actions.iter_mut() {
+        let player_id;
+        if player_marker.is_some() {
+            player_id = player_marker.unwrap().id();
+        } else {
+            player_id = player.unwrap().id();
+        }
+        if actions.try_retarget_artillery {
+            let mut target_tile_pos = TilePos::default();
+            if actions.target_world_pos {
+                if let Some(tile_pos) = world_pos_to_tile_pos(
+                    &cursor_world_pos.cursor_world_pos,
+                    &TilemapSize {
+                        x: game_data.map_size_x,
+                        y: game_data.map_size_y,
+                    },
+                ) {
+                    target_tile_pos = tile_pos;
+                } else {
+                    continue;
+                }
+            } else if actions.artillery_target_tile_pos.is_some() {
+                target_tile_pos = actions.artillery_target_tile_pos.unwrap().into();
+            } else {
+                continue;
+            }
+
+            game_commands.retarget_artillery(player_id, target_tile_pos);
+        }
+    }
+}
+
+pub fn upgrade_building(
+    cursor_world_pos: Res<CursorWorldPos>,
+    mut actions: Query<(Option<&PlayerMarker>, Option<&Player>, &mut Actions)>,
+    mut game_commands: ResMut<GameCommands>,
+    game_data: Res<GameData>,
+) {
+    for (player_marker, player, mut actions) in actions.iter_mut() {
+        let player_id;
+        if player_marker.is_some() {
+            player_id = player_marker.unwrap().id();
+        } else {
+            player_id = player.unwrap().id();
+        }
+        if actions.try_upgrade_building {
+            let mut target_tile_pos = TilePos::default();
+            if actions.target_world_pos {
+                if let Some(tile_pos) = world_pos_to_tile_pos(
+                    &cursor_world_pos.cursor_world_pos,
+                    &TilemapSize {
+                        x: game_data.map_size_x,
+                        y: game_data.map_size_y,
+                    },
+                ) {
+                    target_tile_pos = tile_pos;
+                } else {
+                    continue;
+                }
+            } else if actions.upgrade_tile_pos.is_some() {
+                target_tile_pos = actions.upgrade_tile_pos.unwrap().into();
+            } else {
+                continue;
+            }
+
+            game_commands.upgrade_building(player_id, target_tile_pos);
+        }
+    }
+}
+
+pub fn spawn_painter(
+    cursor_world_pos: Res<CursorWorldPos>,
+    mut actions: Query<(Option<&PlayerMarker>, Option<&Player>, &mut Actions)>,
+    mut game_commands: ResMut<GameCommands>,
+    game_data: Res<GameData>,
+) {
+    for (player_marker, player, mut actions) in actions.iter_mut() {
+        let player_id;
+        if player_marker.is_some() {
+            player_id = player_marker.unwrap().id();
+        } else {
+            player_id = player.unwrap().id();
+        }
+        if actions.try_spawn_painter {
+            let mut target_tile_pos = TilePos::default();
+            if actions.target_world_pos {
+                if let Some(tile_pos) = world_pos_to_tile_pos(
+                    &cursor_world_pos.cursor_world_pos,
+                    &TilemapSize {
+                        x: game_data.map_size_x,
+                        y: game_data.map_size_y,
+                    },
+                ) {
+                    target_tile_pos = tile_pos;
+                } else {
+                    continue;
+                }
+            } else if actions.painter_tile_pos.is_some() {
+                target_tile_pos = actions.painter_tile_pos.unwrap().into();
+            } else {
+                continue;
+            }
+
+            game_commands.spawn_painter(player_id, target_tile_pos);
+        }
+    }
+}
+
+pub fn despawn_building(
+    cursor_world_pos: Res<CursorWorldPos>,
+    mut actions: Query<(Option<&PlayerMarker>, Option<&Player>, &mut Actions)>,
+    mut game_commands: ResMut<GameCommands>,
+    game_data: Res<GameData>,
+) {
+    for (player_marker, player, mut actions) in actions.iter_mut() {
+        let player_id;
+        if player_marker.is_some() {
+            player_id = player_marker.unwrap().id();
+        } else {
+            player_id = player.unwrap().id();
+        }
+        if actions.try_despawn_building {
+            let mut target_tile_pos = TilePos::default();
+            if actions.target_world_pos {
+                if let Some(tile_pos) = world_pos_to_tile_pos(
+                    &cursor_world_pos.cursor_world_pos,
+                    &TilemapSize {
+                        x: game_data.map_size_x,
+                        y: game_data.map_size_y,
+                    },
+                ) {
+                    target_tile_pos = tile_pos;
+                } else {
+                    continue;
+                }
+            } else if actions.despawn_tile_pos.is_some() {
+                target_tile_pos = actions.despawn_tile_pos.unwrap().into();
+            } else {
+                continue;
+            }
+
+            game_commands.despawn_building(player_id, target_tile_pos);
+        }
+    }
+}
@@ -1,13 +1,18 @@
 use crate::abilities::Abilities;
-use crate::actions::game_control::{place_ability, place_building};
+use crate::actions::game_control::{
+    despawn_building, place_ability, place_building, retarget_artillery, spawn_painter,
+    upgrade_building,
+};
+use bevy::input::mouse::MouseWheel;
 use bevy::prelude::*;
 use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
 use bevy_ggf::mapping::tiles::TilePosition;
 use bevy_ggf::player::PlayerMarker;
 use serde::{Deserialize, Serialize};
 
+use crate::buildings::line::LineDirection;
 use crate::buildings::BuildingTypes;
-use crate::game::simulate_game;
+use crate::game::{simulate_game, GameSpeed, StepGame};
 use crate::{GamePausedState, GameState};
 
 mod game_control;
@@ -33,6 +38,30 @@ impl Plugin for ActionsPlugin {
                 .in_schedule(CoreSchedule::Main)
                 .run_if(in_state(GameState::Playing)),
         );
+        app.add_system(
+            retarget_artillery
+                .after(place_ability)
+                .in_schedule(CoreSchedule::Main)
+                .run_if(in_state(GameState::Playing)),
+        );
+        app.add_system(
+            upgrade_building
+                .after(retarget_artillery)
+                .in_schedule(CoreSchedule::Main)
+                .run_if(in_state(GameState::Playing)),
+        );
+        app.add_system(
+            spawn_painter
+                .after(upgrade_building)
+                .in_schedule(CoreSchedule::Main)
+                .run_if(in_state(GameState::Playing)),
+        );
+        app.add_system(
+            despawn_building
+                .after(spawn_painter)
+                .in_schedule(CoreSchedule::Main)
+                .run_if(in_state(GameState::Playing)),
+        );
     }
 }
 
@@ -41,13 +70,49 @@ impl Plugin for ActionsPlugin {
 pub struct Actions {
     pub try_place_building: bool,
     pub try_place_ability: bool,
+    pub try_retarget_artillery: bool,
+    pub try_upgrade_building: bool,
+    pub try_spawn_painter: bool,
+    pub try_despawn_building: bool,
+    /// What left clicking on the board does
+    pub click_mode: ClickMode,
     pub placed_building: bool,
     pub placed_ability: bool,
     pub selected_building: BuildingTypes,
     pub selected_ability: Abilities,
+    /// Which sides the next placed line radiates out to
+    pub line_direction: LineDirection,
     pub target_world_pos: bool,
     pub building_tile_pos: Option<TilePosition>,
     pub ability_tile_pos: Option<TilePosition>,
+    /// The players own tile picked as the first of the two tiles a swap needs
+    pub swap_source_tile_pos: Option<TilePosition>,
+    pub artillery_target_tile_pos: Option<TilePosition>,
+    pub upgrade_tile_pos: Option<TilePosition>,
+    /// The workshop the next painter is spawned from
+    pub painter_tile_pos: Option<TilePosition>,
+    pub despawn_tile_pos: Option<TilePosition>,
+}
+
+/// What left clicking on the board does for the player
+#[derive(
+    Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum ClickMode {
+    #[default]
+    Build,
+    Demolish,
+    Upgrade,
+}
+
+impl ClickMode {
+    /// Switches to the given mode, or back to building if it is already on
+    pub fn toggle(&mut self, mode: ClickMode) {
+        *self = match *self == mode {
+            true => ClickMode::Build,
+            false => mode,
+        };
+    }
 }
 
 impl SaveId for Actions {
@@ -99,10 +164,12 @@ fn handle_pause(
 
 pub fn update_actions(
     mouse: Res<Input<MouseButton>>,
+    mut mouse_wheel: EventReader<MouseWheel>,
     mut actions: Query<(&PlayerMarker, &mut Actions)>,
     keyboard_input: Res<Input<KeyCode>>,
     mut commands: Commands,
     current_state: Res<State<GamePausedState>>,
+    mut game_speed: ResMut<GameSpeed>,
 ) {
     if keyboard_input.just_pressed(KeyCode::Escape) {
         match current_state.0 {
@@ -111,23 +178,67 @@ pub fn update_actions(
         }
     }
 
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        game_speed.toggle_held();
+    }
+    if keyboard_input.just_pressed(KeyCode::Period) && game_speed.held {
+        commands.insert_resource(StepGame);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Equals, KeyCode::NumpadAdd])
+        && game_speed.can_speed_up()
+    {
+        game_speed.speed_up();
+    }
+    if keyboard_input.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract])
+        && game_speed.can_slow_down()
+    {
+        game_speed.slow_down();
+    }
+
+    let scrolled: f32 = mouse_wheel.iter().map(|event| event.y).sum();
+
     for (player, mut actions) in actions.iter_mut() {
         actions.building_tile_pos = None;
         actions.target_world_pos = false;
         actions.try_place_ability = false;
         actions.try_place_building = false;
+        actions.try_retarget_artillery = false;
+        actions.try_upgrade_building = false;
+        actions.try_spawn_painter = false;
+        actions.try_despawn_building = false;
         actions.placed_building = false;
         actions.placed_ability = false;
 
         if player.id() == 0 {
             if mouse.just_pressed(MouseButton::Left) {
-                actions.try_place_building = true;
+                match actions.click_mode {
+                    ClickMode::Build => actions.try_place_building = true,
+                    ClickMode::Demolish => actions.try_despawn_building = true,
+                    ClickMode::Upgrade => actions.try_upgrade_building = true,
+                }
                 actions.target_world_pos = true;
             }
+            // With artillery selected right clicking retargets it and with a workshop selected it
+            // spawns a painter from the clicked workshop instead of placing an ability
             if mouse.just_pressed(MouseButton::Right) {
-                actions.try_place_ability = true;
+                match actions.selected_building {
+                    BuildingTypes::Artillery => actions.try_retarget_artillery = true,
+                    BuildingTypes::Workshop => actions.try_spawn_painter = true,
+                    _ => actions.try_place_ability = true,
+                }
                 actions.target_world_pos = true;
             }
+            if mouse.just_pressed(MouseButton::Middle) {
+                actions.try_upgrade_building = true;
+                actions.target_world_pos = true;
+            }
+
+            if keyboard_input.just_pressed(KeyCode::X) {
+                actions.click_mode.toggle(ClickMode::Demolish);
+            }
+            if keyboard_input.just_pressed(KeyCode::C) {
+                actions.click_mode.toggle(ClickMode::Upgrade);
+            }
 
             if keyboard_input.just_pressed(KeyCode::Q) {
                 actions.selected_building = BuildingTypes::Pulser
@@ -138,6 +249,33 @@ pub fn update_actions(
             if keyboard_input.just_pressed(KeyCode::E) {
                 actions.selected_building = BuildingTypes::Line
             }
+            if keyboard_input.just_pressed(KeyCode::R) {
+                actions.selected_building = BuildingTypes::Wall
+            }
+            if keyboard_input.just_pressed(KeyCode::T) {
+                actions.selected_building = BuildingTypes::Harvester
+            }
+            if keyboard_input.just_pressed(KeyCode::Y) {
+                actions.selected_building = BuildingTypes::Relay
+            }
+            if keyboard_input.just_pressed(KeyCode::U) {
+                actions.selected_building = BuildingTypes::Artillery
+            }
+            if keyboard_input.just_pressed(KeyCode::I) {
+                actions.selected_building = BuildingTypes::Workshop
+            }
+
+            // Rotates the line that will be placed next
+            if keyboard_input.just_pressed(KeyCode::F) {
+                actions.line_direction = actions.line_direction.next();
+            }
+            if actions.selected_building == BuildingTypes::Line {
+                if scrolled > 0.0 {
+                    actions.line_direction = actions.line_direction.next();
+                } else if scrolled < 0.0 {
+                    actions.line_direction = actions.line_direction.previous();
+                }
+            }
 
             if keyboard_input.just_pressed(KeyCode::A) {
                 actions.selected_ability = Abilities::Nuke;
@@ -148,6 +286,18 @@ pub fn update_actions(
             if keyboard_input.just_pressed(KeyCode::D) {
                 actions.selected_ability = Abilities::Expand;
             }
+            if keyboard_input.just_pressed(KeyCode::G) {
+                actions.selected_ability = Abilities::Freeze;
+            }
+            if keyboard_input.just_pressed(KeyCode::H) {
+                actions.selected_ability = Abilities::Swap;
+            }
+            if keyboard_input.just_pressed(KeyCode::J) {
+                actions.selected_ability = Abilities::Shield;
+            }
+            if actions.selected_ability != Abilities::Swap {
+                actions.swap_source_tile_pos = None;
+            }
         }
     }
 }
@@ -1,17 +1,33 @@
+use crate::abilities::swap::Swap;
 use crate::abilities::Abilities;
 use crate::actions::Actions;
-use crate::buildings::BuildingTypes;
-use crate::color_system::{ColorConflicts, TileColor, TileColorStrength};
+use crate::buildings::artillery::Artillery;
+use crate::buildings::harvester::Harvester;
+use crate::buildings::line::{Line, LineDirection};
+use crate::buildings::upgrades::{building_tier_cost, BuildingTier};
+use crate::buildings::workshop::Workshop;
+use crate::buildings::{
+    tile_distance, Activate, Building, BuildingMarker, BuildingTypes, Simulate,
+    TimeSimulatingStopped,
+};
+use crate::color_system::{ColorConflicts, TileColor};
+use crate::game::game_rng::GameRng;
+use crate::game::survival::SurvivalWave;
 use crate::game::{GameBuildSettings, GameData};
 use crate::player::PlayerPoints;
-use bevy::prelude::{Commands, Entity, Query, Res, ResMut, With};
+use bevy::prelude::{Commands, Entity, Query, Res, ResMut, Time, With, Without};
 use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
 use bevy_ggf::game_core::state::Changed;
 use bevy_ggf::mapping::terrain::{TerrainClass, TileTerrainInfo};
 use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks, TilePosition};
 use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{ObjectGridPosition, ObjectInfo};
 use bevy_ggf::player::{Player, PlayerMarker};
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use std::time::Duration;
+
+/// How long a building has to be asleep before the ai considers it permanently idle
+const AI_IDLE_BUILDING_TIME: Duration = Duration::from_secs(30);
 
 pub fn run_ai_building(
     color_conflicts: Res<ColorConflicts>,
@@ -26,9 +42,17 @@ pub fn run_ai_building(
         With<Tile>,
     >,
     mut tile_storage_query: Query<(&MapId, &TileStorage)>,
-    mut player_query: Query<(Entity, &mut PlayerPoints, &Player, &mut Actions)>,
+    mut player_query: Query<(
+        Entity,
+        &mut PlayerPoints,
+        &Player,
+        &mut Actions,
+        Option<&SurvivalWave>,
+    )>,
+    harvesters: Query<&PlayerMarker, (With<Building<Harvester>>, Without<Tile>)>,
     game_data: Res<GameData>,
     game_settings: Res<GameBuildSettings>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     let Some((_, tile_storage)) = tile_storage_query
@@ -38,15 +62,26 @@ pub fn run_ai_building(
         return;
     };
 
-    for (entity, mut player_points, player, mut actions) in player_query.iter_mut() {
+    for (entity, mut player_points, player, mut actions, opt_survival_wave) in
+        player_query.iter_mut()
+    {
         if player.id() == 0 {
             continue;
         }
         actions.building_tile_pos = None;
         actions.ability_tile_pos = None;
+        actions.swap_source_tile_pos = None;
         actions.target_world_pos = false;
         actions.try_place_ability = false;
         actions.try_place_building = false;
+        actions.try_retarget_artillery = false;
+        actions.artillery_target_tile_pos = None;
+        actions.try_upgrade_building = false;
+        actions.upgrade_tile_pos = None;
+        actions.try_spawn_painter = false;
+        actions.painter_tile_pos = None;
+        actions.try_despawn_building = false;
+        actions.despawn_tile_pos = None;
         actions.placed_building = false;
         actions.placed_ability = false;
 
@@ -54,16 +89,20 @@ pub fn run_ai_building(
             continue;
         }
 
-        let mut rng = thread_rng();
-
-        if !rng.gen_bool(game_settings.game_difficulty.ai_building_speed()) {
+        // Survival waves build faster the later they spawn
+        let building_speed = match opt_survival_wave {
+            Some(survival_wave) => survival_wave.building_speed,
+            None => game_settings.game_difficulty.ai_building_speed(),
+        };
+        if !rng.gen_bool(building_speed) {
             continue;
         }
 
         let mut low_health_tile_pos: Option<(TilePos, usize)> = None;
 
         let mut sorted_highest_conflicts: Vec<(TilePos, usize)> = vec![];
-        for (tile_pos, player_id_vec) in color_conflicts.conflicts.iter().filter(|value| {
+        let conflicts = color_conflicts.sorted_conflicts();
+        for (tile_pos, player_id_vec) in conflicts.into_iter().filter(|value| {
             let tile_entity = tile_storage.get(&value.0).unwrap();
             let Ok((entity, tile_pos, tile_object_stacks, tile_terrain_info, options)) =
                 tiles.get_mut(tile_entity)
@@ -109,6 +148,19 @@ pub fn run_ai_building(
             sorted_highest_conflicts.sort_by(|a, b| a.1.cmp(&b.1));
         }
 
+        // When the borders are quiet the ai invests in harvesters placed safely inside its territory
+        if sorted_highest_conflicts.len() < 3 && rng.gen_bool(0.3) {
+            if let Some(tile_pos) =
+                find_harvester_tile(player.id(), &harvesters, &game_data, &tile_storage, &tiles)
+            {
+                actions.selected_building = BuildingTypes::Harvester;
+                actions.try_place_building = true;
+                actions.building_tile_pos = Some(tile_pos.into());
+                commands.entity(entity).insert(Changed::default());
+                continue;
+            }
+        }
+
         let info: Option<(TilePos, usize)> = match sorted_highest_conflicts.get(0) {
             None => match low_health_tile_pos {
                 None => {
@@ -154,15 +206,16 @@ pub fn run_ai_building(
             },
             Some(info) => Some(*info),
         };
-        let mut rng = thread_rng();
-
         if info.is_some() {
             match info.unwrap().1 {
                 0..=0 => {
-                    let chance = rng.gen_bool(0.5);
+                    // Quiet tiles are a safe place to put artillery or workshops from time to time
+                    let chance = rng.gen_range(0..10);
                     actions.selected_building = match chance {
-                        true => BuildingTypes::Line,
-                        false => BuildingTypes::Pulser,
+                        0..=3 => BuildingTypes::Line,
+                        4..=7 => BuildingTypes::Pulser,
+                        8 => BuildingTypes::Workshop,
+                        _ => BuildingTypes::Artillery,
                     }
                 }
                 1..=1 => {
@@ -174,13 +227,19 @@ pub fn run_ai_building(
                     }
                 }
                 _ => {
-                    let chance = rng.gen_bool(0.6);
+                    // Heavily contested tiles are worth shielding with a wall sometimes
+                    let chance = rng.gen_range(0..10);
                     actions.selected_building = match chance {
-                        true => BuildingTypes::Scatter,
-                        false => BuildingTypes::Pulser,
+                        0..=4 => BuildingTypes::Scatter,
+                        5..=7 => BuildingTypes::Pulser,
+                        _ => BuildingTypes::Wall,
                     }
                 }
             }
+            if actions.selected_building == BuildingTypes::Line {
+                actions.line_direction =
+                    best_line_direction(info.unwrap().0, player.id(), &tile_storage, &tiles);
+            }
             actions.try_place_building = true;
             actions.building_tile_pos = Some(info.unwrap().0.into());
             commands.entity(entity).insert(Changed::default());
@@ -203,6 +262,7 @@ pub fn run_ai_ability(
     mut player_query: Query<(Entity, &mut PlayerPoints, &Player, &mut Actions)>,
     game_data: Res<GameData>,
     game_settings: Res<GameBuildSettings>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     let Some((_, tile_storage)) = tile_storage_query
@@ -220,18 +280,17 @@ pub fn run_ai_ability(
             continue;
         }
 
-        let mut rng = thread_rng();
-
         if rng.gen_bool(game_settings.game_difficulty.ai_action_speed()) {
             continue;
         }
 
-        let ability_pick = rng.gen_range(0..=2);
+        let ability_pick = rng.gen_range(0..=5);
         match ability_pick {
-            //fortify
-            0 => {
+            // fortify, shield and freeze all protect the most contested tile of the ai
+            0 | 2 | 3 => {
                 let mut sorted_highest_conflicts: Vec<(TilePos, usize)> = vec![];
-                for (tile_pos, player_id_vec) in color_conflicts.conflicts.iter().filter(|value| {
+                let conflicts = color_conflicts.sorted_conflicts();
+                for (tile_pos, player_id_vec) in conflicts.into_iter().filter(|value| {
                     let tile_entity = tile_storage.get(&value.0).unwrap();
                     let Ok((entity, _, tile_object_stacks, options)) = tiles.get_mut(tile_entity)
                     else {
@@ -263,7 +322,11 @@ pub fn run_ai_ability(
                     sorted_highest_conflicts.sort_by(|a, b| a.1.cmp(&b.1));
                 }
                 if let Some(info) = sorted_highest_conflicts.get(0) {
-                    actions.selected_ability = Abilities::Fortify;
+                    actions.selected_ability = match ability_pick {
+                        0 => Abilities::Fortify,
+                        2 => Abilities::Shield,
+                        _ => Abilities::Freeze,
+                    };
                     actions.try_place_ability = true;
                     actions.ability_tile_pos = Some(info.0.into());
                 }
@@ -278,6 +341,41 @@ pub fn run_ai_ability(
                     }
                 }
             }
+            // swap the weakest tile of the ai with the strongest enemy tile far enough away
+            4 => {
+                let mut weakest: Option<(TilePos, u32)> = None;
+                let mut strongest: Vec<(TilePos, u32)> = vec![];
+                for (_, tile_pos, tile_object_stacks, options) in tiles.iter() {
+                    let Some((player_marker, tile_color)) = options else {
+                        continue;
+                    };
+                    let strength = tile_color.get_number_representation();
+                    if player_marker.id() != player.id() {
+                        strongest.push((*tile_pos, strength));
+                        continue;
+                    }
+                    if !tile_object_stacks.has_space(&ObjectStackingClass {
+                        stack_class: game_data.stacking_classes.get("Ability").unwrap().clone(),
+                    }) {
+                        continue;
+                    }
+                    if weakest.map_or(true, |(_, weakest_strength)| strength < weakest_strength) {
+                        weakest = Some((*tile_pos, strength));
+                    }
+                }
+                let Some((source_tile_pos, _)) = weakest else {
+                    continue;
+                };
+                strongest.sort_by(|a, b| b.1.cmp(&a.1));
+                if let Some((target_tile_pos, _)) = strongest.iter().find(|(tile_pos, _)| {
+                    tile_distance(source_tile_pos, *tile_pos) >= Swap::MIN_DISTANCE
+                }) {
+                    actions.selected_ability = Abilities::Swap;
+                    actions.try_place_ability = true;
+                    actions.swap_source_tile_pos = Some(source_tile_pos.into());
+                    actions.ability_tile_pos = Some((*target_tile_pos).into());
+                }
+            }
             // nuke
             _ => {
                 for (entity, tile_pos, tile_object_stacks, options) in tiles.iter() {
@@ -297,6 +395,295 @@ pub fn run_ai_ability(
     }
 }
 
+/// Points ai artillery at the most contested enemy tile in its range. Artillery without a target
+/// is always given one, otherwise the ai only occasionally picks a new target
+pub fn run_ai_artillery(
+    color_conflicts: Res<ColorConflicts>,
+    artillery_query: Query<
+        (&PlayerMarker, &ObjectGridPosition, &Building<Artillery>),
+        Without<Tile>,
+    >,
+    tiles: Query<Option<&PlayerMarker>, With<Tile>>,
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    mut player_query: Query<(Entity, &Player, &mut Actions)>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    for (entity, player, mut actions) in player_query.iter_mut() {
+        if player.id() == 0 {
+            continue;
+        }
+
+        let player_artillery: Vec<(&ObjectGridPosition, &Building<Artillery>)> = artillery_query
+            .iter()
+            .filter(|(player_marker, _, _)| player_marker.id() == player.id())
+            .map(|(_, object_grid_position, artillery)| (object_grid_position, artillery))
+            .collect();
+
+        let Some((object_grid_position, artillery)) = player_artillery
+            .iter()
+            .find(|(_, artillery)| artillery.building_type.target.is_none())
+            .or_else(|| match rng.gen_bool(0.05) {
+                true => player_artillery.get(rng.gen_range(0..player_artillery.len().max(1))),
+                false => None,
+            })
+        else {
+            continue;
+        };
+
+        let mut target: Option<(TilePos, usize)> = None;
+        for (tile_pos, conflicts) in color_conflicts.sorted_conflicts() {
+            if tile_distance(object_grid_position.tile_position.into(), *tile_pos)
+                > artillery.building_type.target_range
+            {
+                continue;
+            }
+            let Some(tile_entity) = tile_storage.get(tile_pos) else {
+                continue;
+            };
+            let Ok(Some(tile_player_marker)) = tiles.get(tile_entity) else {
+                continue;
+            };
+            if tile_player_marker.id() == player.id() {
+                continue;
+            }
+            if target.map_or(true, |(_, count)| conflicts.len() > count) {
+                target = Some((*tile_pos, conflicts.len()));
+            }
+        }
+
+        if let Some((tile_pos, _)) = target {
+            actions.try_retarget_artillery = true;
+            actions.artillery_target_tile_pos = Some(tile_pos.into());
+            commands.entity(entity).insert(Changed::default());
+        }
+    }
+}
+
+/// Occasionally spends spare building points on upgrading a random building of the ai
+pub fn run_ai_upgrade(
+    buildings: Query<
+        (
+            &PlayerMarker,
+            &ObjectGridPosition,
+            &ObjectInfo,
+            Option<&BuildingTier>,
+        ),
+        (With<BuildingMarker>, Without<Tile>),
+    >,
+    mut player_query: Query<(Entity, &PlayerPoints, &Player, &mut Actions)>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    for (entity, player_points, player, mut actions) in player_query.iter_mut() {
+        if player.id() == 0 || actions.try_place_building {
+            continue;
+        }
+        if player_points.building_points < 150 || !rng.gen_bool(0.05) {
+            continue;
+        }
+
+        let upgradeable: Vec<&ObjectGridPosition> = buildings
+            .iter()
+            .filter(|(player_marker, _, object_info, opt_tier)| {
+                let next_tier = opt_tier.map_or(1, |building_tier| building_tier.tier) + 1;
+                player_marker.id() == player.id()
+                    && building_tier_cost(object_info.object_type.name.as_str(), next_tier)
+                        .is_some()
+            })
+            .map(|(_, object_grid_position, _, _)| object_grid_position)
+            .collect();
+        if upgradeable.is_empty() {
+            continue;
+        }
+
+        let object_grid_position = upgradeable[rng.gen_range(0..upgradeable.len())];
+        actions.try_upgrade_building = true;
+        actions.upgrade_tile_pos = Some(object_grid_position.tile_position);
+        commands.entity(entity).insert(Changed::default());
+    }
+}
+
+/// Spawns a painter from a random ai workshop that is done with its cooldown
+pub fn run_ai_painters(
+    workshops: Query<
+        (&PlayerMarker, &ObjectGridPosition),
+        (With<Building<Workshop>>, With<Activate>, Without<Tile>),
+    >,
+    mut player_query: Query<(Entity, &Player, &mut Actions)>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    for (entity, player, mut actions) in player_query.iter_mut() {
+        if player.id() == 0 {
+            continue;
+        }
+
+        let ready_workshops: Vec<&ObjectGridPosition> = workshops
+            .iter()
+            .filter(|(player_marker, _)| player_marker.id() == player.id())
+            .map(|(_, object_grid_position)| object_grid_position)
+            .collect();
+        if ready_workshops.is_empty() {
+            continue;
+        }
+
+        let object_grid_position = ready_workshops[rng.gen_range(0..ready_workshops.len())];
+        actions.try_spawn_painter = true;
+        actions.painter_tile_pos = Some(object_grid_position.tile_position);
+        commands.entity(entity).insert(Changed::default());
+    }
+}
+
+/// Demolishes ai buildings that have been asleep for a long time so the refund can be spent on a
+/// new building somewhere it is actually needed
+pub fn run_ai_relocate(
+    buildings: Query<
+        (&PlayerMarker, &ObjectGridPosition, &TimeSimulatingStopped),
+        (With<BuildingMarker>, Without<Simulate>, Without<Tile>),
+    >,
+    mut player_query: Query<(Entity, &Player, &mut Actions)>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    for (entity, player, mut actions) in player_query.iter_mut() {
+        if player.id() == 0 || actions.try_place_building || actions.try_upgrade_building {
+            continue;
+        }
+        if !rng.gen_bool(0.1) {
+            continue;
+        }
+
+        let Some((_, object_grid_position, _)) =
+            buildings
+                .iter()
+                .find(|(player_marker, _, time_simulating_stopped)| {
+                    player_marker.id() == player.id()
+                        && time_simulating_stopped.time_asleep(&time) >= AI_IDLE_BUILDING_TIME
+                })
+        else {
+            continue;
+        };
+
+        actions.try_despawn_building = true;
+        actions.despawn_tile_pos = Some(object_grid_position.tile_position);
+        commands.entity(entity).insert(Changed::default());
+    }
+}
+
+/// Picks the direction in which a line placed on the tile would reach the most tiles the player
+/// doesn't own yet
+fn best_line_direction(
+    tile_pos: TilePos,
+    player_id: usize,
+    tile_storage: &TileStorage,
+    tiles: &Query<
+        (
+            Entity,
+            &TilePos,
+            &TileObjectStacks,
+            &TileTerrainInfo,
+            Option<(&mut PlayerMarker, &mut TileColor)>,
+        ),
+        With<Tile>,
+    >,
+) -> LineDirection {
+    let mut best: (LineDirection, u32) = (LineDirection::default(), 0);
+    for direction in LineDirection::all() {
+        let reach = Line::new(direction).reach() as i32;
+        let mut contested_tiles = 0;
+        for (step_x, step_y) in direction.steps() {
+            for distance in 1..=reach {
+                let Some(tile_entity) = TilePos::from_i32_pair(
+                    tile_pos.x as i32 + step_x * distance,
+                    tile_pos.y as i32 + step_y * distance,
+                    &tile_storage.size,
+                )
+                .and_then(|tile_pos| tile_storage.get(&tile_pos)) else {
+                    break;
+                };
+                let Ok((_, _, _, tile_terrain_info, options)) = tiles.get(tile_entity) else {
+                    break;
+                };
+                if tile_terrain_info.terrain_type.terrain_class.name.as_str() == "NonColorable" {
+                    break;
+                }
+                match options {
+                    Some((player_marker, _)) if player_marker.id() == player_id => {}
+                    _ => contested_tiles += 1,
+                }
+            }
+        }
+        if contested_tiles > best.1 {
+            best = (direction, contested_tiles);
+        }
+    }
+    best.0
+}
+
+/// Finds a fully strengthened interior tile for a new harvester. Returns None once the player
+/// already has a harvester for every 25 fully strengthened tiles it owns
+fn find_harvester_tile(
+    player_id: usize,
+    harvesters: &Query<&PlayerMarker, (With<Building<Harvester>>, Without<Tile>)>,
+    game_data: &Res<GameData>,
+    tile_storage: &TileStorage,
+    tiles: &Query<
+        (
+            Entity,
+            &TilePos,
+            &TileObjectStacks,
+            &TileTerrainInfo,
+            Option<(&mut PlayerMarker, &mut TileColor)>,
+        ),
+        With<Tile>,
+    >,
+) -> Option<TilePos> {
+    let mut max_strength_tiles: Vec<TilePos> = vec![];
+    for (_, tile_pos, _, _, options) in tiles.iter() {
+        let Some((player_marker, tile_color)) = options else {
+            continue;
+        };
+        if player_marker.id() == player_id && tile_color.max_strength() {
+            max_strength_tiles.push(*tile_pos);
+        }
+    }
+
+    let harvester_count = harvesters
+        .iter()
+        .filter(|player_marker| player_marker.id() == player_id)
+        .count();
+    if harvester_count >= max_strength_tiles.len() / 25 {
+        return None;
+    }
+
+    for tile_pos in max_strength_tiles {
+        let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+            continue;
+        };
+        let Ok((_, _, tile_object_stacks, _, _)) = tiles.get(tile_entity) else {
+            continue;
+        };
+        if !tile_object_stacks.has_space(&ObjectStackingClass {
+            stack_class: game_data.stacking_classes.get("Building").unwrap().clone(),
+        }) {
+            continue;
+        }
+        if !check_if_neighbor_not_same(tile_pos, player_id, game_data, tile_storage, tiles) {
+            return Some(tile_pos);
+        }
+    }
+    None
+}
+
 pub fn check_if_neighbor_not_same(
     target: TilePos,
     checking_player_id: usize,
@@ -28,6 +28,9 @@ impl Plugin for InternalAudioPlugin {
                     handle_spawned_object_sounds,
                     control_nuke_sound,
                     control_fortify_expand_sound,
+                    control_freeze_sound,
+                    control_swap_sound,
+                    control_shield_sound,
                     control_gain_tile_sound,
                     control_lost_tile_sound,
                     control_menu_sound,
@@ -138,6 +141,9 @@ pub enum GameSoundEvents {
     GainTile,
     LostTile,
     Nuke,
+    Freeze,
+    Swap,
+    Shield,
     PlaceBuilding,
 }
 
@@ -186,10 +192,17 @@ fn handle_spawned_object_sounds(
 
     for (entity, object_info) in query.iter() {
         match object_info.object_type.name.as_str() {
-            "Pulser" | "Line" | "Scatter" => events.send(GameSoundEvents::PlaceBuilding),
+            "Pulser" | "Line" | "Scatter" | "Wall" | "Harvester" | "Relay" | "Artillery"
+            | "Workshop" => events.send(GameSoundEvents::PlaceBuilding),
+            // Painters are spawned by workshops, objectives come with the level and pickups spawn on
+            // their own, none of them are placed by a player
+            "Painter" | "Objective" | "BonusPoints" | "AbilityCharge" | "Overclock" => {}
             "Fortify" => events.send(GameSoundEvents::Fortify),
             "Expand" => events.send(GameSoundEvents::Expand),
             "Nuke" => events.send(GameSoundEvents::Nuke),
+            "Freeze" => events.send(GameSoundEvents::Freeze),
+            "Swap" => events.send(GameSoundEvents::Swap),
+            "Shield" => events.send(GameSoundEvents::Shield),
             _ => todo!(),
         }
         commands.entity(entity).remove::<ObjectSpawnedSound>();
@@ -285,6 +298,54 @@ fn control_nuke_sound(
     }
 }
 
+fn control_freeze_sound(
+    mut events: EventReader<GameSoundEvents>,
+    sound_settings: Res<GameSoundSettings>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<AudioChannel<EffectSounds>>,
+) {
+    for sound_event in events.iter() {
+        if *sound_event != GameSoundEvents::Freeze {
+            continue;
+        }
+        audio
+            .play(audio_assets.freeze.clone())
+            .with_volume(0.3 * sound_settings.effects_sound_level.1);
+    }
+}
+
+fn control_swap_sound(
+    mut events: EventReader<GameSoundEvents>,
+    sound_settings: Res<GameSoundSettings>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<AudioChannel<EffectSounds>>,
+) {
+    for sound_event in events.iter() {
+        if *sound_event != GameSoundEvents::Swap {
+            continue;
+        }
+        audio
+            .play(audio_assets.swap.clone())
+            .with_volume(0.3 * sound_settings.effects_sound_level.1);
+    }
+}
+
+fn control_shield_sound(
+    mut events: EventReader<GameSoundEvents>,
+    sound_settings: Res<GameSoundSettings>,
+    audio_assets: Res<AudioAssets>,
+    audio: Res<AudioChannel<EffectSounds>>,
+) {
+    for sound_event in events.iter() {
+        if *sound_event != GameSoundEvents::Shield {
+            continue;
+        }
+        audio
+            .play(audio_assets.shield.clone())
+            .with_volume(0.3 * sound_settings.effects_sound_level.1);
+    }
+}
+
 fn control_place_build_sound(
     mut events: EventReader<GameSoundEvents>,
     sound_settings: Res<GameSoundSettings>,
@@ -0,0 +1,181 @@
+use crate::buildings::{tile_distance, Activate, Building, Simulate};
+use crate::color_system::{
+    register_guaranteed_color_conflict, ColorConflictGuarantees, ConflictType, TileColor,
+};
+use crate::game::GameData;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{
+    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, With, Without, World,
+};
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage, TilemapSize};
+use bevy_ggf::game_core::command::{GameCommand, GameCommands};
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{Tile, TilePosition};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::ObjectGridPosition;
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+/// A long range building that doesn't color around itself. Every time it activates it damages
+/// every tile within `blast_radius` of its target
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Artillery {
+    /// How far away from the artillery the target can be
+    pub target_range: u32,
+    pub blast_radius: u32,
+    pub target: Option<TilePosition>,
+}
+
+pub trait RetargetArtilleryExt {
+    fn retarget_artillery(&mut self, player_id: usize, target_tile: TilePos) -> RetargetArtillery;
+}
+
+impl RetargetArtilleryExt for GameCommands {
+    fn retarget_artillery(&mut self, player_id: usize, target_tile: TilePos) -> RetargetArtillery {
+        self.queue.push(RetargetArtillery {
+            player_id,
+            target_tile_pos: target_tile,
+        });
+        RetargetArtillery {
+            player_id,
+            target_tile_pos: target_tile,
+        }
+    }
+}
+
+/// Points the players artillery closest to the target tile, out of the ones that can reach it, at
+/// the target tile
+#[derive(Reflect, FromReflect, Clone)]
+pub struct RetargetArtillery {
+    pub player_id: usize,
+    pub target_tile_pos: TilePos,
+}
+
+impl GameCommand for RetargetArtillery {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let game_data = world.resource::<GameData>();
+        let tilemap_size = TilemapSize {
+            x: game_data.map_size_x,
+            y: game_data.map_size_y,
+        };
+        if self.target_tile_pos.x >= tilemap_size.x || self.target_tile_pos.y >= tilemap_size.y {
+            return Err(String::from("Target is outside of the map"));
+        }
+
+        let mut system_state: SystemState<
+            Query<(
+                Entity,
+                &PlayerMarker,
+                &ObjectGridPosition,
+                &mut Building<Artillery>,
+            )>,
+        > = SystemState::new(world);
+        let mut artillery_query = system_state.get_mut(world);
+
+        let Some((entity, _, _, mut artillery)) = artillery_query
+            .iter_mut()
+            .filter(|(_, player_marker, object_grid_position, artillery)| {
+                player_marker.id() == self.player_id
+                    && tile_distance(
+                        object_grid_position.tile_position.into(),
+                        self.target_tile_pos,
+                    ) <= artillery.building_type.target_range
+            })
+            .min_by_key(|(_, _, object_grid_position, _)| {
+                tile_distance(
+                    object_grid_position.tile_position.into(),
+                    self.target_tile_pos,
+                )
+            })
+        else {
+            return Err(String::from("No artillery in range of the target"));
+        };
+
+        artillery.building_type.target = Some(self.target_tile_pos.into());
+        world.entity_mut(entity).insert(Changed::default());
+
+        Ok(())
+    }
+}
+
+pub fn simulate_artillery(
+    tile_storage_query: Query<(&MapId, &TileStorage, &TilemapSize)>,
+    artillery_query: Query<
+        (Entity, &PlayerMarker, &Building<Artillery>),
+        (
+            Without<MapId>,
+            Without<Tile>,
+            With<Activate>,
+            With<Simulate>,
+        ),
+    >,
+    mut tiles: Query<
+        (
+            &TileTerrainInfo,
+            Option<(&mut PlayerMarker, &mut TileColor)>,
+        ),
+        (With<Tile>, Without<MapId>),
+    >,
+    mut event_writer: EventWriter<ColorConflictGuarantees>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage, tilemap_size)) = tile_storage_query
+        .iter()
+        .find(|(id, _, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    for (entity, player_marker, artillery) in artillery_query.iter() {
+        commands.entity(entity).remove::<Activate>();
+
+        let Some(target) = artillery.building_type.target else {
+            continue;
+        };
+        let target: TilePos = target.into();
+        let blast_radius = artillery.building_type.blast_radius;
+
+        for x in target.x.saturating_sub(blast_radius)..=target.x + blast_radius {
+            for y in target.y.saturating_sub(blast_radius)..=target.y + blast_radius {
+                let tile_pos = TilePos { x, y };
+                if x >= tilemap_size.x
+                    || y >= tilemap_size.y
+                    || tile_distance(target, tile_pos) > blast_radius
+                {
+                    continue;
+                }
+                let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+                    continue;
+                };
+                let Ok((tile_terrain_info, options)) = tiles.get_mut(tile_entity) else {
+                    continue;
+                };
+
+                register_guaranteed_color_conflict(
+                    &player_marker.id(),
+                    false,
+                    true,
+                    true,
+                    ConflictType::Damage,
+                    tile_pos,
+                    tile_terrain_info,
+                    &options,
+                    &mut event_writer,
+                );
+            }
+        }
+    }
+}
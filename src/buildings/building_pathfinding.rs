@@ -4,11 +4,11 @@ use std::{collections::BTreeMap, marker::PhantomData};
 
 use bevy::{ecs::{component::Component, entity::Entity, system::{Query, Resource, SystemState}, world::{Mut, World}}, utils::HashMap};
 use bevy_ecs_tilemap::{map::TilemapSize, tiles::TilePos};
-use bevy_ggf::{mapping::MapId, movement::TileMoveChecks, object::{ObjectGridPosition, ObjectId}, pathfinding::{dijkstra::Node, MapNode, PathfindAlgorithm, PathfindCallback, PathfindMap}, player::PlayerMarker};
+use bevy_ggf::{mapping::{terrain::TileTerrainInfo, MapId}, movement::TileMoveChecks, object::{ObjectGridPosition, ObjectId}, pathfinding::{dijkstra::Node, MapNode, PathfindAlgorithm, PathfindCallback, PathfindMap}, player::PlayerMarker};
 
-use crate::{abilities::Ability, mapping::map::MapTileStorage, objects::TileToObjectIndex, pathfinding::{AddObjectToTileToObjectIndex, RemoveObjectFromTileToObjectIndex}};
+use crate::{abilities::Ability, mapping::{map::MapTileStorage, terrain_step_cost}, objects::TileToObjectIndex, pathfinding::{AddObjectToTileToObjectIndex, RemoveObjectFromTileToObjectIndex}};
 
-use super::Building;
+use super::{modifiers::BuildingModifiers, Building};
 
 
 
@@ -163,18 +163,33 @@ pub struct SimplePathfindMap<BuildingType: PathfindStrengthExt> {
 
 pub trait PathfindStrengthExt{
     fn pathfinding_strength(&self) -> u32;
+
+    /// How much of the pathfinding strength stepping onto the tile uses up. None if the tile can't
+    /// be stepped onto at all
+    fn tile_step_cost(&self, world: &World, tile_entity: Entity) -> Option<u32> {
+        // Conduit tiles don't use up any range
+        Some(world.get::<TileTerrainInfo>(tile_entity).map_or(1, terrain_step_cost))
+    }
 }
 
 impl<T> PathfindStrengthExt for Building<T> where T: PathfindStrengthExt{
     fn pathfinding_strength(&self) -> u32 {
         self.building_type.pathfinding_strength()
     }
+
+    fn tile_step_cost(&self, world: &World, tile_entity: Entity) -> Option<u32> {
+        self.building_type.tile_step_cost(world, tile_entity)
+    }
 }
 
 impl<T> PathfindStrengthExt for Ability<T> where T: PathfindStrengthExt{
     fn pathfinding_strength(&self) -> u32 {
         self.ability_type.pathfinding_strength()
     }
+
+    fn tile_step_cost(&self, world: &World, tile_entity: Entity) -> Option<u32> {
+        self.ability_type.tile_step_cost(world, tile_entity)
+    }
 }
 
 impl<BuildingType: PathfindStrengthExt> RemoveObjectFromTileToObjectIndex for SimplePathfindMap<BuildingType>{
@@ -229,7 +244,7 @@ impl<BuildingType: Send + Sync + 'static + PathfindStrengthExt + Component> Path
     fn node_cost_calculation(
         &mut self,
         entity_moving: Entity,
-        _: Entity,
+        tile_entity: Entity,
         tile_pos: TilePos,
         move_from_tile_pos: TilePos,
         world: &World,
@@ -237,6 +252,9 @@ impl<BuildingType: Send + Sync + 'static + PathfindStrengthExt + Component> Path
         let Some(object_movement) = world.get::<BuildingType>(entity_moving) else {
             return false;
         };
+        let Some(step_cost) = object_movement.tile_step_cost(world, tile_entity) else {
+            return false;
+        };
 
         let Some([tile_node, move_from_tile_node]) =
             self.map.get_many_mut([&tile_pos, &move_from_tile_pos]) else{
@@ -244,18 +262,19 @@ impl<BuildingType: Send + Sync + 'static + PathfindStrengthExt + Component> Path
         };
 
         return if tile_node.calculated {
-            if (move_from_tile_node.move_cost + 1) < (tile_node.move_cost) {
+            if (move_from_tile_node.move_cost + step_cost) < (tile_node.move_cost) {
                 tile_node.move_cost =
-                    move_from_tile_node.move_cost + (move_from_tile_node.move_cost + 1);
+                    move_from_tile_node.move_cost + (move_from_tile_node.move_cost + step_cost);
                 tile_node.prior_node_pos = move_from_tile_node.node_pos;
                 true
             } else {
                 false
             }
-        } else if (move_from_tile_node.move_cost + 1)
+        } else if (move_from_tile_node.move_cost + step_cost)
             <= object_movement.pathfinding_strength()
+                + BuildingModifiers::pathfind_strength_bonus(world, entity_moving)
         {
-            tile_node.move_cost = move_from_tile_node.move_cost + 1;
+            tile_node.move_cost = move_from_tile_node.move_cost + step_cost;
             tile_node.prior_node_pos = move_from_tile_node.node_pos;
             true
         } else {
@@ -0,0 +1,110 @@
+use crate::buildings::{Activate, Building};
+use crate::color_system::{increase_ability_points, increase_building_points, TileColor};
+use crate::game::game_rng::GameRng;
+use crate::objects::ObjectCachedMap;
+use crate::player::PlayerPoints;
+use bevy::prelude::{
+    Commands, Component, Entity, FromReflect, Query, Reflect, ResMut, With, Without,
+};
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::player::{Player, PlayerMarker};
+use serde::{Deserialize, Serialize};
+
+use super::building_pathfinding::PathfindStrengthExt;
+use super::Simulate;
+
+/// An economy building that doesn't color anything. Every time it activates it turns the strength
+/// of the owned tiles around it into points for its player
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Harvester {
+    pub harvest_range: u32,
+    /// How much tile strength is needed for a single building point
+    pub strength_per_point: u32,
+    /// How many fully strengthened tiles are needed for a single ability point
+    pub max_tiles_per_ability_point: u32,
+}
+
+impl PathfindStrengthExt for Harvester {
+    fn pathfinding_strength(&self) -> u32 {
+        self.harvest_range
+    }
+}
+
+pub fn simulate_harvesters_from_cache(
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    harvesters: Query<
+        (
+            Entity,
+            &PlayerMarker,
+            &Building<Harvester>,
+            &ObjectCachedMap,
+        ),
+        (Without<MapId>, With<Activate>, With<Simulate>),
+    >,
+    tiles: Query<Option<(&PlayerMarker, &TileColor)>, (With<Tile>, Without<MapId>)>,
+    mut player_query: Query<(&mut PlayerPoints, &Player)>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    for (entity, player_marker, harvester, cache) in harvesters.iter() {
+        commands.entity(entity).remove::<Activate>();
+
+        let mut total_strength: u32 = 0;
+        let mut max_strength_tiles: u32 = 0;
+
+        for tile in cache.cache.iter() {
+            let Some(tile_entity) = tile_storage.get(&Into::<TilePos>::into(*tile)) else {
+                continue;
+            };
+
+            let Ok(Some((tile_player_marker, tile_color))) = tiles.get(tile_entity) else {
+                continue;
+            };
+
+            if tile_player_marker.id() != player_marker.id() {
+                continue;
+            }
+
+            total_strength += tile_color.get_number_representation();
+            if tile_color.max_strength() {
+                max_strength_tiles += 1;
+            }
+        }
+
+        let Some((mut player_points, _)) = player_query
+            .iter_mut()
+            .find(|(_, player)| player.id() == player_marker.id())
+        else {
+            continue;
+        };
+
+        for _ in 0..total_strength / harvester.building_type.strength_per_point.max(1) {
+            increase_building_points(&mut player_points, &mut rng);
+        }
+        for _ in 0..max_strength_tiles / harvester.building_type.max_tiles_per_ability_point.max(1)
+        {
+            increase_ability_points(&mut player_points, &mut rng);
+        }
+    }
+}
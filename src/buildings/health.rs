@@ -0,0 +1,79 @@
+use bevy::prelude::{Component, FromReflect, Reflect};
+use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How many hit points a building has. A building on a lost tile loses as many hit points every tick
+/// as the strength of the tile
+pub const BUILDING_MAX_HEALTH: u32 = 200;
+
+/// The hit points of a building. Buildings only get one once they are first damaged
+#[derive(
+    Clone, Copy, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub struct BuildingHealth {
+    pub health: u32,
+    pub max_health: u32,
+}
+
+impl Default for BuildingHealth {
+    fn default() -> Self {
+        BuildingHealth {
+            health: BUILDING_MAX_HEALTH,
+            max_health: BUILDING_MAX_HEALTH,
+        }
+    }
+}
+
+impl BuildingHealth {
+    pub fn is_full(&self) -> bool {
+        self.health >= self.max_health
+    }
+}
+
+impl SaveId for BuildingHealth {
+    fn save_id(&self) -> BinaryComponentId {
+        28
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        28
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+/// What happens to a building whose tile is taken over by another player
+#[derive(Default, Reflect, FromReflect, Clone, Copy, Eq, Debug, PartialEq)]
+pub enum BuildingLossRule {
+    /// The building loses hit points until it is destroyed
+    #[default]
+    Destroy,
+    /// The building loses hit points, but switches to the new owner of the tile if they bring it
+    /// to full strength before the building is destroyed
+    Capture,
+}
+
+impl Display for BuildingLossRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildingLossRule::Destroy => f.write_str("Destroy"),
+            BuildingLossRule::Capture => f.write_str("Capture"),
+        }
+    }
+}
+
+impl BuildingLossRule {
+    pub fn toggle(&self) -> BuildingLossRule {
+        match self {
+            BuildingLossRule::Destroy => BuildingLossRule::Capture,
+            BuildingLossRule::Capture => BuildingLossRule::Destroy,
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use std::collections::BTreeMap;
 
-use crate::buildings::{get_neighbors_tilepos, Activate, Building};
+use crate::buildings::{Activate, Building};
 use crate::color_system::{convert_tile, ColorConflictEvent, TileColor};
+use crate::mapping::terrain_step_cost;
 use crate::objects::{ObjectCachedMap, TileToObjectIndex};
 use crate::pathfinding::{AddObjectToTileToObjectIndex, RemoveObjectFromTileToObjectIndex};
 use bevy::ecs::world::World;
 use bevy::prelude::{
-    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, With, Without,
+    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, Res, Time, With, Without,
 };
 use bevy::utils::hashbrown::HashMap;
 use bevy_ecs_tilemap::map::TilemapSize;
@@ -20,7 +21,8 @@ use bevy_ggf::pathfinding::{MapNode, PathfindMap};
 use bevy_ggf::player::PlayerMarker;
 use serde::{Deserialize, Serialize};
 
-use super::Simulate;
+use super::modifiers::BuildingModifiers;
+use super::{Simulate, TimeSimulatingStopped};
 
 #[derive(
     Default,
@@ -39,6 +41,104 @@ pub struct Line {
     pub strength: u32,
     pub hits_per_tile: u32,
     pub max_changed_per_side: u32,
+    pub direction: LineDirection,
+}
+
+impl Line {
+    pub fn new(direction: LineDirection) -> Line {
+        Line {
+            strength: 10,
+            hits_per_tile: 2,
+            max_changed_per_side: 5,
+            direction,
+        }
+    }
+
+    /// Lines that cover fewer sides reach proportionally further down each of them
+    fn side_multiplier(&self) -> u32 {
+        4 / self.direction.steps().len() as u32
+    }
+
+    /// How many tiles the line reaches down each of its sides
+    pub fn reach(&self) -> u32 {
+        self.strength * self.side_multiplier()
+    }
+
+    pub fn max_changed_per_side(&self) -> u32 {
+        self.max_changed_per_side * self.side_multiplier()
+    }
+}
+
+/// Which sides a [`Line`] radiates out to
+#[derive(
+    Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum LineDirection {
+    /// Both the horizontal and the vertical axis
+    #[default]
+    Cross,
+    /// Both diagonal axes
+    Diagonals,
+    Horizontal,
+    /// The diagonal going from the bottom left to the top right
+    DiagonalRising,
+    Vertical,
+    /// The diagonal going from the top left to the bottom right
+    DiagonalFalling,
+}
+
+/// Every direction in the order they are rotated through
+const LINE_DIRECTIONS: [LineDirection; 6] = [
+    LineDirection::Cross,
+    LineDirection::Diagonals,
+    LineDirection::Horizontal,
+    LineDirection::DiagonalRising,
+    LineDirection::Vertical,
+    LineDirection::DiagonalFalling,
+];
+
+impl LineDirection {
+    /// The step taken from the line to the next tile on each of its sides
+    pub fn steps(&self) -> &'static [(i32, i32)] {
+        match self {
+            LineDirection::Cross => &[(-1, 0), (0, 1), (1, 0), (0, -1)],
+            LineDirection::Diagonals => &[(-1, -1), (-1, 1), (1, 1), (1, -1)],
+            LineDirection::Horizontal => &[(-1, 0), (1, 0)],
+            LineDirection::DiagonalRising => &[(-1, -1), (1, 1)],
+            LineDirection::Vertical => &[(0, 1), (0, -1)],
+            LineDirection::DiagonalFalling => &[(-1, 1), (1, -1)],
+        }
+    }
+
+    pub fn all() -> [LineDirection; 6] {
+        LINE_DIRECTIONS
+    }
+
+    pub fn next(&self) -> LineDirection {
+        let index = LINE_DIRECTIONS
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap_or(0);
+        LINE_DIRECTIONS[(index + 1) % LINE_DIRECTIONS.len()]
+    }
+
+    pub fn previous(&self) -> LineDirection {
+        let index = LINE_DIRECTIONS
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap_or(0);
+        LINE_DIRECTIONS[(index + LINE_DIRECTIONS.len() - 1) % LINE_DIRECTIONS.len()]
+    }
+
+    /// Which side of a line on the starting pos the target pos is on. None if the target isn't on
+    /// any of the sides
+    pub fn side_index(&self, starting_pos: TilePos, target_pos: TilePos) -> Option<usize> {
+        let step = (
+            (target_pos.x as i32 - starting_pos.x as i32).signum(),
+            (target_pos.y as i32 - starting_pos.y as i32).signum(),
+        );
+        self.steps().iter().position(|side_step| side_step == &step)
+    }
 }
 
 pub fn simulate_lines_from_cache(
@@ -63,6 +163,7 @@ pub fn simulate_lines_from_cache(
         (With<Tile>, Without<Building<Line>>, Without<MapId>),
     >,
     mut event_writer: EventWriter<ColorConflictEvent>,
+    time: Res<Time>,
     mut commands: Commands,
 ) {
     let Some((_, _, tile_storage, _)) = tile_storage_query
@@ -84,8 +185,14 @@ pub fn simulate_lines_from_cache(
                 continue;
             };
 
-            let index = get_side_index(ogp, Into::<TilePos>::into(*tile));
-            if sides_changed[index] >= line.building_type.max_changed_per_side {
+            let Some(index) = line
+                .building_type
+                .direction
+                .side_index(ogp, Into::<TilePos>::into(*tile))
+            else {
+                continue 'main_loop;
+            };
+            if sides_changed[index] >= line.building_type.max_changed_per_side() {
                 continue 'main_loop;
             }
             if let Ok((_, tile_terrain_info, options)) = tiles.get_mut(tile_entity) {
@@ -102,7 +209,7 @@ pub fn simulate_lines_from_cache(
                         tiles_changed += 1;
                     }
 
-                    if sides_changed[index] >= line.building_type.max_changed_per_side {
+                    if sides_changed[index] >= line.building_type.max_changed_per_side() {
                         continue 'main_loop;
                     }
                 }
@@ -110,24 +217,14 @@ pub fn simulate_lines_from_cache(
         }
 
         if tiles_changed == 0 {
-            commands.entity(entity).remove::<Simulate>();
+            commands
+                .entity(entity)
+                .remove::<Simulate>()
+                .insert(TimeSimulatingStopped::new(&time));
         }
     }
 }
 
-fn get_side_index(starting_pos: TilePos, target_pos: TilePos) -> usize {
-    if starting_pos.x > target_pos.x {
-        return 0;
-    }
-    if starting_pos.y < target_pos.y {
-        return 1;
-    }
-    if starting_pos.x < target_pos.x {
-        return 2;
-    }
-    3
-}
-
 #[derive(Default)]
 pub struct LinePathfindMap {
     pub map: HashMap<TilePos, Node>,
@@ -193,7 +290,7 @@ impl PathfindMap<TilePos, Node, (), Building<Line>> for LinePathfindMap {
     fn node_cost_calculation(
         &mut self,
         entity_moving: Entity,
-        _: Entity,
+        tile_entity: Entity,
         tile_pos: TilePos,
         move_from_tile_pos: TilePos,
         world: &World,
@@ -201,6 +298,9 @@ impl PathfindMap<TilePos, Node, (), Building<Line>> for LinePathfindMap {
         let Some(object_movement) = world.get::<Building<Line>>(entity_moving) else {
             return false;
         };
+        let step_cost = world
+            .get::<TileTerrainInfo>(tile_entity)
+            .map_or(1, terrain_step_cost);
 
         let Some([tile_node, move_from_tile_node]) =
             self.map.get_many_mut([&tile_pos, &move_from_tile_pos])
@@ -208,19 +308,31 @@ impl PathfindMap<TilePos, Node, (), Building<Line>> for LinePathfindMap {
             return false;
         };
 
+        // Only the sides the line was placed with leave the starting tile
+        if move_from_tile_node.prior_node_pos == move_from_tile_node.node_pos
+            && object_movement
+                .building_type
+                .direction
+                .side_index(move_from_tile_pos, tile_pos)
+                .is_none()
+        {
+            return false;
+        }
+
         return if tile_node.calculated {
-            if (move_from_tile_node.move_cost + 1) < (tile_node.move_cost) {
+            if (move_from_tile_node.move_cost + step_cost) < (tile_node.move_cost) {
                 tile_node.move_cost =
-                    move_from_tile_node.move_cost + (move_from_tile_node.move_cost + 1);
+                    move_from_tile_node.move_cost + (move_from_tile_node.move_cost + step_cost);
                 tile_node.prior_node_pos = move_from_tile_node.node_pos;
                 true
             } else {
                 false
             }
-        } else if (move_from_tile_node.move_cost + 1)
-            <= object_movement.building_type.strength as u32
+        } else if (move_from_tile_node.move_cost + step_cost)
+            <= object_movement.building_type.reach()
+                + BuildingModifiers::pathfind_strength_bonus(world, entity_moving)
         {
-            tile_node.move_cost = move_from_tile_node.move_cost + 1;
+            tile_node.move_cost = move_from_tile_node.move_cost + step_cost;
             tile_node.prior_node_pos = move_from_tile_node.node_pos;
             true
         } else {
@@ -229,51 +341,41 @@ impl PathfindMap<TilePos, Node, (), Building<Line>> for LinePathfindMap {
     }
 
     fn get_neighbors(&self, node_pos: TilePos, tilemap_size: &TilemapSize) -> Vec<TilePos> {
-        let mut neighbor_tiles: Vec<TilePos> = vec![];
-        let origin_tile = node_pos;
         let Some(node_to_get_neighbors) = self.get_node(node_pos) else {
             return vec![];
         };
-        if node_to_get_neighbors.prior_node_pos == node_to_get_neighbors.node_pos {
-            return get_neighbors_tilepos(node_to_get_neighbors.node_pos, tilemap_size);
-        }
-
-        if node_to_get_neighbors.node_pos.x < node_to_get_neighbors.prior_node_pos.x {
-            if let Some(west) =
-                TilePos::from_i32_pair(origin_tile.x as i32 - 1, origin_tile.y as i32, tilemap_size)
-            {
-                neighbor_tiles.push(west);
-                return neighbor_tiles;
-            }
-        }
-
-        if node_to_get_neighbors.node_pos.x > node_to_get_neighbors.prior_node_pos.x {
-            if let Some(east) =
-                TilePos::from_i32_pair(origin_tile.x as i32 + 1, origin_tile.y as i32, tilemap_size)
-            {
-                neighbor_tiles.push(east);
-                return neighbor_tiles;
-            }
-        }
 
-        if node_to_get_neighbors.node_pos.y < node_to_get_neighbors.prior_node_pos.y {
-            if let Some(south) =
-                TilePos::from_i32_pair(origin_tile.x as i32, origin_tile.y as i32 - 1, tilemap_size)
-            {
-                neighbor_tiles.push(south);
-                return neighbor_tiles;
-            }
+        // The starting tile branches out to every side, the line itself rejects the sides it doesn't
+        // use when calculating the cost
+        if node_to_get_neighbors.prior_node_pos == node_to_get_neighbors.node_pos {
+            return LineDirection::Cross
+                .steps()
+                .iter()
+                .chain(LineDirection::Diagonals.steps())
+                .filter_map(|(x, y)| {
+                    TilePos::from_i32_pair(
+                        node_pos.x as i32 + x,
+                        node_pos.y as i32 + y,
+                        tilemap_size,
+                    )
+                })
+                .collect();
         }
 
-        if node_to_get_neighbors.node_pos.y > node_to_get_neighbors.prior_node_pos.y {
-            if let Some(north) =
-                TilePos::from_i32_pair(origin_tile.x as i32, origin_tile.y as i32 + 1, tilemap_size)
-            {
-                neighbor_tiles.push(north);
-                return neighbor_tiles;
-            }
-        }
-        neighbor_tiles
+        // Every other tile only continues the side it is on
+        let step_x = (node_to_get_neighbors.node_pos.x as i32
+            - node_to_get_neighbors.prior_node_pos.x as i32)
+            .signum();
+        let step_y = (node_to_get_neighbors.node_pos.y as i32
+            - node_to_get_neighbors.prior_node_pos.y as i32)
+            .signum();
+        TilePos::from_i32_pair(
+            node_pos.x as i32 + step_x,
+            node_pos.y as i32 + step_y,
+            tilemap_size,
+        )
+        .into_iter()
+        .collect()
     }
 
     fn get_node_mut(&mut self, node_pos: TilePos) -> Option<&mut Node> {
@@ -1,18 +1,40 @@
+pub mod artillery;
 pub mod building_pathfinding;
+pub mod harvester;
+pub mod health;
 pub mod line;
+pub mod modifiers;
+pub mod objective;
 pub mod pulser;
+pub mod relay;
 pub mod scatter;
+pub mod upgrades;
+pub mod wall;
+pub mod workshop;
 
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
 
-use crate::buildings::line::Line;
+use crate::buildings::artillery::Artillery;
+use crate::buildings::harvester::Harvester;
+use crate::buildings::health::{BuildingHealth, BuildingLossRule};
+use crate::buildings::line::{Line, LineDirection};
+use crate::buildings::modifiers::{BuildingModifier, BuildingModifierSource};
+use crate::buildings::objective::Objective;
 use crate::buildings::pulser::Pulser;
+use crate::buildings::relay::Relay;
 use crate::buildings::scatter::Scatter;
-use crate::game::GameData;
+use crate::buildings::wall::Wall;
+use crate::buildings::workshop::Workshop;
+use crate::color_system::TileColor;
+use crate::game::puzzle::PuzzleState;
+use crate::game::{GameBuildSettings, GameData};
+use crate::objects::{invalidate_object_cache, ObjectCachedMap, TileToObjectIndex};
 use crate::player::PlayerPoints;
+use crate::power_ups::Overclock;
 use bevy::ecs::system::SystemState;
 use bevy::prelude::{
-    Commands, Component, Entity, FromReflect, Query, Reflect, Res, Timer, With, Without, World,
+    Commands, Component, Entity, FromReflect, Query, Reflect, Res, ResMut, Timer, With, Without,
+    World,
 };
 use bevy::time::{Time, TimerMode};
 use bevy::utils::hashbrown::HashMap;
@@ -33,6 +55,7 @@ pub trait SpawnBuildingExt {
     fn spawn_building(
         &mut self,
         ability: BuildingTypes,
+        line_direction: LineDirection,
         player_id: usize,
         target_tile: TilePos,
     ) -> SpawnBuilding;
@@ -42,16 +65,19 @@ impl SpawnBuildingExt for GameCommands {
     fn spawn_building(
         &mut self,
         ability: BuildingTypes,
+        line_direction: LineDirection,
         player_id: usize,
         target_tile: TilePos,
     ) -> SpawnBuilding {
         self.queue.push(SpawnBuilding {
             building_type: ability.clone(),
+            line_direction,
             player_id: player_id.clone(),
             target_tile_pos: target_tile.clone(),
         });
         SpawnBuilding {
             building_type: ability.clone(),
+            line_direction,
             player_id: player_id.clone(),
             target_tile_pos: target_tile.clone(),
         }
@@ -61,12 +87,42 @@ impl SpawnBuildingExt for GameCommands {
 #[derive(Reflect, FromReflect, Clone)]
 pub struct SpawnBuilding {
     pub building_type: BuildingTypes,
+    /// Which sides the building radiates out to if it is a [`Line`]
+    pub line_direction: LineDirection,
     pub player_id: usize,
     pub target_tile_pos: TilePos,
 }
 
 impl GameCommand for SpawnBuilding {
     fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let game_settings = world.resource::<GameBuildSettings>();
+        if self.player_id == 0
+            && !game_settings
+                .restrictions
+                .allows_building(self.building_type)
+        {
+            return Err(format!(
+                "{:?} is restricted in this mission",
+                self.building_type
+            ));
+        }
+
+        // Puzzles place from the inventory instead of spending points
+        let from_inventory = self.player_id == 0 && world.contains_resource::<PuzzleState>();
+        if from_inventory
+            && world
+                .resource::<PuzzleState>()
+                .inventory
+                .building_count(self.building_type)
+                == 0
+        {
+            return Err(format!("No {:?} left to place", self.building_type));
+        }
+        let cost = match from_inventory {
+            true => 0,
+            false => 50,
+        };
+
         let game_data = world.remove_resource::<GameData>().unwrap();
 
         let mut system_state: SystemState<(
@@ -118,227 +174,510 @@ impl GameCommand for SpawnBuilding {
             return Err("Tile already occupied".parse().unwrap());
         }
 
-        let mut game_commands = GameCommands::new();
-
-        let result = match self.building_type {
-            BuildingTypes::Pulser => {
-                if player_points.building_points >= 50 {
-                    //actions.placed_building = true;
-
-                    player_points.building_points =
-                        player_points.building_points.saturating_sub(50);
-                    world.entity_mut(entity).insert(Changed::default());
-
-                    let mut spawn = game_commands.spawn_object(
-                        (
-                            ObjectGridPosition {
-                                tile_position: self.target_tile_pos.into(),
-                            },
-                            ObjectStackingClass {
-                                stack_class: game_data
-                                    .stacking_classes
-                                    .get("Building")
-                                    .unwrap()
-                                    .clone(),
-                            },
-                            Object,
-                            ObjectInfo {
-                                object_type: game_data.object_types.get("Pulser").unwrap().clone(),
-                            },
-                            Building {
-                                building_type: Pulser {
-                                    strength: 7,
-                                    max_pulse_tiles: 10,
-                                },
-                            },
-                            BuildingCooldown {
-                                timer: Timer::from_seconds(0.75, TimerMode::Once),
-                                timer_reset: 0.75,
-                            },
-                            BuildingMarker::default(),
-                            Simulate,
-                        ),
-                        self.target_tile_pos,
-                        MapId { id: 1 },
-                        self.player_id,
-                    );
-
-                    spawn.execute(world)
-                } else {
-                    Err(String::from("Not enough points to place"))
-                }
-            }
-            BuildingTypes::Scatter => {
-                if player_points.building_points >= 50 {
-                    //actions.placed_building = true;
-
-                    player_points.building_points =
-                        player_points.building_points.saturating_sub(50);
-                    world.entity_mut(entity).insert(Changed::default());
-
-                    let mut spawn = game_commands.spawn_object(
-                        (
-                            ObjectGridPosition {
-                                tile_position: self.target_tile_pos.into(),
-                            },
-                            ObjectStackingClass {
-                                stack_class: game_data
-                                    .stacking_classes
-                                    .get("Building")
-                                    .unwrap()
-                                    .clone(),
-                            },
-                            Object,
-                            ObjectInfo {
-                                object_type: game_data.object_types.get("Scatter").unwrap().clone(),
-                            },
-                            Building {
-                                building_type: Scatter {
-                                    scatter_range: 4,
-                                    scatter_amount: 20,
-                                },
-                            },
-                            BuildingCooldown {
-                                timer: Timer::from_seconds(0.35, TimerMode::Once),
-                                timer_reset: 0.35,
-                            },
-                            BuildingMarker::default(),
-                            Simulate,
-                        ),
-                        self.target_tile_pos,
-                        MapId { id: 1 },
-                        self.player_id,
-                    );
-                    spawn.execute(world)
-                } else {
-                    Err(String::from("Not enough points to place"))
-                }
-            }
-            BuildingTypes::Line => {
-                if player_points.building_points >= 50 {
-                    //actions.placed_building = true;
-
-                    player_points.building_points =
-                        player_points.building_points.saturating_sub(50);
-                    world.entity_mut(entity).insert(Changed::default());
-
-                    let mut spawn = game_commands.spawn_object(
-                        (
-                            ObjectGridPosition {
-                                tile_position: self.target_tile_pos.into(),
-                            },
-                            ObjectStackingClass {
-                                stack_class: game_data
-                                    .stacking_classes
-                                    .get("Building")
-                                    .unwrap()
-                                    .clone(),
-                            },
-                            Object,
-                            ObjectInfo {
-                                object_type: game_data.object_types.get("Line").unwrap().clone(),
-                            },
-                            Building {
-                                building_type: Line {
-                                    strength: 10,
-                                    hits_per_tile: 2,
-                                    max_changed_per_side: 5,
-                                },
-                            },
-                            BuildingCooldown {
-                                timer: Timer::from_seconds(0.5, TimerMode::Once),
-                                timer_reset: 0.5,
-                            },
-                            BuildingMarker::default(),
-                            Simulate,
-                        ),
-                        self.target_tile_pos,
-                        MapId { id: 1 },
-                        self.player_id,
-                    );
-
-                    spawn.execute(world)
-                } else {
-                    Err(String::from("Not enough points to place"))
-                }
-            }
+        let result = if player_points.building_points >= cost {
+            player_points.building_points = player_points.building_points.saturating_sub(cost);
+            world.entity_mut(entity).insert(Changed::default());
+
+            building_spawn_command(
+                &game_data,
+                self.building_type,
+                self.line_direction,
+                self.player_id,
+                self.target_tile_pos,
+                cost,
+            )
+            .execute(world)
+        } else {
+            Err(String::from("Not enough points to place"))
         };
 
         world.insert_resource(game_data);
 
+        if from_inventory && result.is_ok() {
+            world
+                .resource_mut::<PuzzleState>()
+                .inventory
+                .take_building(self.building_type);
+        }
+
         return result;
     }
 }
 
+/// The components every building spawns with, next to the ones specific to its type
+fn building_components(
+    game_data: &GameData,
+    object_type_name: &str,
+    target_tile_pos: TilePos,
+    points_spent: u32,
+) -> (
+    ObjectGridPosition,
+    ObjectStackingClass,
+    Object,
+    ObjectInfo,
+    BuildingMarker,
+    BuildingPointsSpent,
+    Simulate,
+) {
+    (
+        ObjectGridPosition {
+            tile_position: target_tile_pos.into(),
+        },
+        ObjectStackingClass {
+            stack_class: game_data.stacking_classes.get("Building").unwrap().clone(),
+        },
+        Object,
+        ObjectInfo {
+            object_type: game_data
+                .object_types
+                .get(object_type_name)
+                .unwrap()
+                .clone(),
+        },
+        BuildingMarker::default(),
+        BuildingPointsSpent {
+            points: points_spent,
+        },
+        Simulate,
+    )
+}
+
+/// Creates the command that spawns a new building of the given type through
+/// [`GameCommands::spawn_object`]. Doesn't check or charge any points, `points_spent` is only
+/// remembered on the building so demolishing it refunds what was actually paid
+pub fn building_spawn_command(
+    game_data: &GameData,
+    building_type: BuildingTypes,
+    line_direction: LineDirection,
+    player_id: usize,
+    target_tile_pos: TilePos,
+    points_spent: u32,
+) -> Box<dyn GameCommand> {
+    let mut game_commands = GameCommands::new();
+    match building_type {
+        BuildingTypes::Pulser => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Pulser", target_tile_pos, points_spent),
+                Building {
+                    building_type: Pulser {
+                        strength: 7,
+                        max_pulse_tiles: 10,
+                    },
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(0.75, TimerMode::Once),
+                    timer_reset: 0.75,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Scatter => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Scatter", target_tile_pos, points_spent),
+                Building {
+                    building_type: Scatter {
+                        scatter_range: 4,
+                        scatter_amount: 20,
+                    },
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(0.35, TimerMode::Once),
+                    timer_reset: 0.35,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Line => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Line", target_tile_pos, points_spent),
+                Building {
+                    building_type: Line::new(line_direction),
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(0.5, TimerMode::Once),
+                    timer_reset: 0.5,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Wall => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Wall", target_tile_pos, points_spent),
+                Building {
+                    building_type: Wall {
+                        shield_range: 3,
+                        shield_hits: 2,
+                    },
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Harvester => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Harvester", target_tile_pos, points_spent),
+                Building {
+                    building_type: Harvester {
+                        harvest_range: 2,
+                        strength_per_point: 12,
+                        max_tiles_per_ability_point: 5,
+                    },
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(2.0, TimerMode::Once),
+                    timer_reset: 2.0,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Relay => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Relay", target_tile_pos, points_spent),
+                Building {
+                    building_type: Relay,
+                },
+                BuildingModifierSource {
+                    range: 3,
+                    modifier: BuildingModifier::PathfindStrength,
+                    amount: 1,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Artillery => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Artillery", target_tile_pos, points_spent),
+                Building {
+                    building_type: Artillery {
+                        target_range: 20,
+                        blast_radius: 1,
+                        target: None,
+                    },
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(6.0, TimerMode::Once),
+                    timer_reset: 6.0,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+        BuildingTypes::Workshop => Box::new(game_commands.spawn_object(
+            (
+                building_components(game_data, "Workshop", target_tile_pos, points_spent),
+                Building {
+                    building_type: Workshop { max_painters: 3 },
+                },
+                BuildingCooldown {
+                    timer: Timer::from_seconds(10.0, TimerMode::Once),
+                    timer_reset: 10.0,
+                },
+            ),
+            target_tile_pos,
+            MapId { id: 1 },
+            player_id,
+        )),
+    }
+}
+
+/// Creates the command that spawns an [`Objective`] held by the given player
+pub fn objective_spawn_command(
+    game_data: &GameData,
+    player_id: usize,
+    target_tile_pos: TilePos,
+) -> Box<dyn GameCommand> {
+    let mut game_commands = GameCommands::new();
+    Box::new(game_commands.spawn_object(
+        (
+            ObjectGridPosition {
+                tile_position: target_tile_pos.into(),
+            },
+            ObjectStackingClass {
+                stack_class: game_data.stacking_classes.get("Building").unwrap().clone(),
+            },
+            Object,
+            ObjectInfo {
+                object_type: game_data.object_types.get("Objective").unwrap().clone(),
+            },
+            Building {
+                building_type: Objective {
+                    building_points: 5,
+                    ability_points: 1,
+                },
+            },
+            BuildingCooldown {
+                timer: Timer::from_seconds(5.0, TimerMode::Once),
+                timer_reset: 5.0,
+            },
+            BuildingMarker::default(),
+            Simulate,
+        ),
+        target_tile_pos,
+        MapId { id: 1 },
+        player_id,
+    ))
+}
+
+pub trait DespawnBuildingExt {
+    fn despawn_building(&mut self, player_id: usize, target_tile: TilePos) -> DespawnBuilding;
+}
+
+impl DespawnBuildingExt for GameCommands {
+    fn despawn_building(&mut self, player_id: usize, target_tile: TilePos) -> DespawnBuilding {
+        self.queue.push(DespawnBuilding {
+            player_id,
+            target_tile_pos: target_tile,
+        });
+        DespawnBuilding {
+            player_id,
+            target_tile_pos: target_tile,
+        }
+    }
+}
+
+/// Demolishes the players building on the target tile, refunding part of the points spent on it
+#[derive(Reflect, FromReflect, Clone)]
+pub struct DespawnBuilding {
+    pub player_id: usize,
+    pub target_tile_pos: TilePos,
+}
+
+impl GameCommand for DespawnBuilding {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let refund_percent = world
+            .resource::<GameBuildSettings>()
+            .demolish_refund_percent;
+
+        let mut system_state: SystemState<(
+            Query<(Entity, &Player, &mut PlayerPoints)>,
+            Query<
+                (
+                    Entity,
+                    &ObjectId,
+                    &PlayerMarker,
+                    &ObjectGridPosition,
+                    &ObjectStackingClass,
+                    Option<&BuildingPointsSpent>,
+                    Option<&ObjectCachedMap>,
+                    Option<&Building<Objective>>,
+                ),
+                (With<BuildingMarker>, Without<DespawnObject>),
+            >,
+            Query<&mut TileObjectStacks, With<Tile>>,
+            Query<(&MapId, &TileStorage)>,
+            ResMut<TileToObjectIndex>,
+            Commands,
+        )> = SystemState::new(world);
+        let (
+            mut players,
+            buildings,
+            mut tiles,
+            tile_storage_query,
+            mut tile_to_object_index,
+            mut commands,
+        ) = system_state.get_mut(world);
+
+        let Some((
+            building_entity,
+            object_id,
+            player_marker,
+            _,
+            object_stacking_class,
+            opt_points_spent,
+            opt_cache,
+            opt_objective,
+        )) = buildings
+            .iter()
+            .find(|(_, _, _, object_grid_position, _, _, _, _)| {
+                TilePos::from(object_grid_position.tile_position) == self.target_tile_pos
+            })
+        else {
+            return Err(String::from("No building on target tile"));
+        };
+
+        if player_marker.id() != self.player_id {
+            return Err(String::from("Building not owned by demolishing player"));
+        }
+
+        if opt_objective.is_some() {
+            return Err(String::from("Objectives can't be demolished"));
+        }
+
+        let Some((player_entity, _, mut player_points)) = players
+            .iter_mut()
+            .find(|(_, id, _)| id.id() == self.player_id)
+        else {
+            return Err(String::from("Failed to Find Player ID"));
+        };
+
+        let Some((_, tile_storage)) = tile_storage_query
+            .iter()
+            .find(|(id, _)| id == &&MapId { id: 1 })
+        else {
+            return Err(String::from("Failed to find map"));
+        };
+        let Some(mut tile_object_stacks) = tile_storage
+            .get(&self.target_tile_pos)
+            .and_then(|tile_entity| tiles.get_mut(tile_entity).ok())
+        else {
+            return Err(String::from("Failed to Find target tile pos"));
+        };
+        tile_object_stacks.decrement_object_class_count(object_stacking_class);
+
+        // Buildings that were placed for free, like the ones a level starts with, refund nothing
+        let points_spent = opt_points_spent.map_or(0, |points_spent| points_spent.points);
+        player_points.add_building_points(points_spent * refund_percent / 100);
+        commands.entity(player_entity).insert(Changed::default());
+
+        if let Some(object_cached_map) = opt_cache {
+            invalidate_object_cache(
+                building_entity,
+                object_id,
+                object_cached_map,
+                &mut tile_to_object_index,
+                &mut commands,
+            );
+        }
+        commands.entity(building_entity).insert(DespawnObject);
+
+        system_state.apply(world);
+
+        Ok(())
+    }
+}
+
+/// Damages buildings standing on a tile that their player doesn't own and heals the ones that are
+/// back on their own tiles. Depending on the [`BuildingLossRule`] a building on a fully
+/// strengthened enemy tile is captured by that player instead
 pub fn destroy_buildings(
-    buildings: Query<
+    mut buildings: Query<
         (
             Entity,
-            &PlayerMarker,
+            &mut PlayerMarker,
             &ObjectId,
             &ObjectGridPosition,
-            &BuildingMarker,
             &ObjectStackingClass,
+            Option<&mut BuildingHealth>,
+            Option<&ObjectCachedMap>,
+            Option<&Building<Objective>>,
         ),
-        With<Object>,
+        (With<Object>, With<BuildingMarker>, Without<DespawnObject>),
     >,
     mut tiles: Query<
         (
-            Entity,
             &TileTerrainInfo,
-            Option<&PlayerMarker>,
+            Option<(&PlayerMarker, &TileColor)>,
             &mut TileObjectStacks,
         ),
         (Without<Object>, With<Tile>),
     >,
     mut tile_storage_query: Query<(&MapId, &TileStorage)>,
+    game_settings: Res<GameBuildSettings>,
+    mut tile_to_object_index: ResMut<TileToObjectIndex>,
     mut commands: Commands,
 ) {
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter_mut()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
     for (
         building_entity,
-        player_marker,
+        mut player_marker,
         object_id,
         object_grid_pos,
-        building,
         object_stacking_class,
-    ) in buildings.iter()
+        opt_health,
+        opt_cache,
+        opt_objective,
+    ) in buildings.iter_mut()
     {
-        let Some((_, tile_storage)) = tile_storage_query
-            .iter_mut()
-            .find(|(id, _)| id == &&MapId { id: 1 })
-        else {
+        let Some(tile_entity) = tile_storage.get(&object_grid_pos.tile_position.into()) else {
             continue;
         };
 
-        let tile_entity = tile_storage
-            .get(&object_grid_pos.tile_position.into())
-            .unwrap();
-
-        let Ok((entity, tile_terrain_info, tile_marker, mut tile_object_stacks)) =
+        let Ok((tile_terrain_info, tile_options, mut tile_object_stacks)) =
             tiles.get_mut(tile_entity)
         else {
             continue;
         };
-        let mut destroy_ability = false;
-        if let Some(tile_marker) = tile_marker {
-            // if there is a tile marker and its not the same as the buildings kill it
-            if player_marker != tile_marker {
-                destroy_ability = true;
-            }
-            // if there is no tile marker then the building dies
-        } else {
-            destroy_ability = true;
-        }
 
         // if the tile is noncolorable kill it
         if tile_terrain_info.terrain_type.terrain_class.name.as_str() == "NonColorable" {
-            destroy_ability = true;
+            commands.entity(building_entity).insert(DespawnObject);
+            tile_object_stacks.decrement_object_class_count(object_stacking_class);
+            continue;
         }
 
-        if destroy_ability {
-            //println!("killing buildings");
+        let damage = match tile_options {
+            Some((tile_marker, _)) if tile_marker == &*player_marker => {
+                // Buildings slowly heal while their player holds the tile
+                if let Some(mut health) = opt_health {
+                    if !health.is_full() {
+                        health.health += 1;
+                        commands.entity(building_entity).insert(Changed::default());
+                    }
+                }
+                continue;
+            }
+            Some((tile_marker, tile_color)) => {
+                // Objectives are always captured no matter the loss rule
+                if (game_settings.building_loss_rule == BuildingLossRule::Capture
+                    || opt_objective.is_some())
+                    && tile_color.max_strength()
+                {
+                    *player_marker = tile_marker.clone();
+                    if let Some(object_cached_map) = opt_cache {
+                        invalidate_object_cache(
+                            building_entity,
+                            object_id,
+                            object_cached_map,
+                            &mut tile_to_object_index,
+                            &mut commands,
+                        );
+                    }
+                    // The new owner didn't pay for the building so it won't get a refund for it
+                    commands.entity(building_entity).insert((
+                        BuildingHealth::default(),
+                        BuildingPointsSpent::default(),
+                        Changed::default(),
+                        Simulate,
+                    ));
+                    continue;
+                }
+                if opt_objective.is_some() {
+                    continue;
+                }
+                tile_color.get_number_representation()
+            }
+            None if opt_objective.is_some() => continue,
+            None => 1,
+        };
+
+        let mut health = opt_health.map_or(BuildingHealth::default(), |health| *health);
+        health.health = health.health.saturating_sub(damage);
+
+        if health.health == 0 {
             commands.entity(building_entity).insert(DespawnObject);
             tile_object_stacks.decrement_object_class_count(object_stacking_class);
+        } else {
+            commands
+                .entity(building_entity)
+                .insert((health, Changed::default()));
         }
     }
 }
@@ -349,32 +688,39 @@ pub fn update_building_timers(
             Entity,
             &mut BuildingCooldown,
             Option<&TimeSimulatingStopped>,
+            Option<(&PlayerMarker, &BuildingMarker)>,
         ),
         (Without<Activate>, With<Simulate>),
     >,
+    overclocked_players: Query<&Player, With<Overclock>>,
     mut commands: Commands,
     time: Res<Time>,
 ) {
-    for (entity, mut timer, opt_time_simulating_stopped) in timers.iter_mut() {
-        if let Some(time_simulating_stopped) = opt_time_simulating_stopped {
-            if time_simulating_stopped
-                .time_stopped
-                .elapsed()
-                .unwrap_or(Duration::default())
-                > timer.timer.remaining()
+    for (entity, mut timer, opt_time_simulating_stopped, opt_building) in timers.iter_mut() {
+        let multiplier = match opt_building {
+            Some((player_marker, _))
+                if overclocked_players
+                    .iter()
+                    .any(|player| player.id() == player_marker.id()) =>
             {
-                commands
-                    .entity(entity)
-                    .insert(Activate)
-                    .remove::<TimeSimulatingStopped>();
-                timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
+                Overclock::COOLDOWN_MULTIPLIER
             }
+            _ => 1,
+        };
+
+        // Sleeping objects catch up on all the game time they missed in one go when woken up
+        if let Some(time_simulating_stopped) = opt_time_simulating_stopped {
+            timer
+                .timer
+                .tick(time_simulating_stopped.time_asleep(&time) * multiplier);
+            commands.entity(entity).remove::<TimeSimulatingStopped>();
         } else {
-            timer.timer.tick(time.delta());
-            if timer.timer.finished() {
-                commands.entity(entity).insert(Activate);
-                timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
-            }
+            timer.timer.tick(time.delta() * multiplier);
+        }
+
+        if timer.timer.finished() {
+            commands.entity(entity).insert(Activate);
+            timer.timer = Timer::from_seconds(timer.timer_reset, TimerMode::Once);
         }
     }
 }
@@ -453,6 +799,114 @@ impl SaveId for Building<Line> {
     }
 }
 
+impl SaveId for Building<Wall> {
+    fn save_id(&self) -> BinaryComponentId {
+        15
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        15
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl SaveId for Building<Harvester> {
+    fn save_id(&self) -> BinaryComponentId {
+        20
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        20
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl SaveId for Building<Relay> {
+    fn save_id(&self) -> BinaryComponentId {
+        23
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        23
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl SaveId for Building<Artillery> {
+    fn save_id(&self) -> BinaryComponentId {
+        24
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        24
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl SaveId for Building<Workshop> {
+    fn save_id(&self) -> BinaryComponentId {
+        25
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        25
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl SaveId for Building<Objective> {
+    fn save_id(&self) -> BinaryComponentId {
+        29
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        29
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
 #[derive(
     Default,
     Clone,
@@ -485,9 +939,23 @@ pub struct Activate;
 pub struct Simulate;
 
 /// Component inserted onto a building or ability when it is no longer being simulated to save the state needed to resimulate it when it should be.
+/// Stores the elapsed game world [`Time`] so sleeping is unaffected by pausing or the game speed
 #[derive(Clone, Eq, Hash, Debug, PartialEq, Component, Serialize, Deserialize)]
 pub struct TimeSimulatingStopped {
-    pub time_stopped: SystemTime,
+    pub time_stopped: Duration,
+}
+
+impl TimeSimulatingStopped {
+    pub fn new(time: &Time) -> TimeSimulatingStopped {
+        TimeSimulatingStopped {
+            time_stopped: time.elapsed(),
+        }
+    }
+
+    /// How much game time has passed since the object stopped being simulated
+    pub fn time_asleep(&self, time: &Time) -> Duration {
+        time.elapsed().saturating_sub(self.time_stopped)
+    }
 }
 
 impl SaveId for Activate {
@@ -508,6 +976,44 @@ impl SaveId for Activate {
     }
 }
 
+/// The building points a player paid to place and upgrade a building, what demolishing it refunds
+/// part of
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct BuildingPointsSpent {
+    pub points: u32,
+}
+
+impl SaveId for BuildingPointsSpent {
+    fn save_id(&self) -> BinaryComponentId {
+        31
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        31
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
 #[derive(Default, Clone, Debug, Component, Reflect, FromReflect, Serialize, Deserialize)]
 pub struct BuildingCooldown {
     pub timer: Timer,
@@ -550,6 +1056,11 @@ pub enum BuildingTypes {
     Pulser,
     Scatter,
     Line,
+    Wall,
+    Harvester,
+    Relay,
+    Artillery,
+    Workshop,
 }
 
 #[derive(Default, Clone, Copy, Eq, Hash, Debug, PartialEq)]
@@ -559,8 +1070,14 @@ pub struct TileNode {
     pub(crate) tile_pos: TilePos,
 }
 
+/// The manhattan distance between two tiles
+pub fn tile_distance(from: TilePos, to: TilePos) -> u32 {
+    from.x.abs_diff(to.x) + from.y.abs_diff(to.y)
+}
+
+/// Every terrain type of the Colorable terrain class can be colored, including the special terrains
 pub fn check_is_colorable(tile_terrain_info: &TileTerrainInfo) -> bool {
-    tile_terrain_info.terrain_type.name == String::from("BasicColorable")
+    tile_terrain_info.terrain_type.terrain_class.name == String::from("Colorable")
 }
 
 pub fn get_neighbors_tilepos(
@@ -643,3 +1160,96 @@ pub fn get_two_node_mut(
         None
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{put_to_sleep, step, tick, timer_world};
+
+    fn cooldown(seconds: f32) -> BuildingCooldown {
+        BuildingCooldown {
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+            timer_reset: seconds,
+        }
+    }
+
+    fn cooldown_elapsed(world: &World, entity: Entity) -> Duration {
+        world
+            .get::<BuildingCooldown>(entity)
+            .unwrap()
+            .timer
+            .elapsed()
+    }
+
+    #[test]
+    fn time_asleep_only_counts_game_time() {
+        let mut time = Time::default();
+        let startup = time.startup();
+        time.update_with_instant(startup + Duration::from_secs(1));
+        let time_simulating_stopped = TimeSimulatingStopped::new(&time);
+
+        // Real time passing without the game world time being updated doesn't count
+        assert_eq!(time_simulating_stopped.time_asleep(&time), Duration::ZERO);
+
+        time.update_with_instant(startup + Duration::from_secs(3));
+        assert_eq!(
+            time_simulating_stopped.time_asleep(&time),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn sleeping_building_catches_up_like_an_awake_one() {
+        let (mut world, mut schedule) = timer_world(update_building_timers);
+        let awake = world.spawn((cooldown(10.0), Simulate)).id();
+        let asleep = world.spawn((cooldown(10.0), Simulate)).id();
+
+        step(&mut world, &mut schedule, 5);
+        put_to_sleep(&mut world, asleep);
+        step(&mut world, &mut schedule, 100);
+        assert_eq!(cooldown_elapsed(&world, asleep), tick() * 5);
+
+        world.entity_mut(asleep).insert(Simulate);
+        step(&mut world, &mut schedule, 1);
+        assert_eq!(cooldown_elapsed(&world, awake), tick() * 106);
+        assert_eq!(
+            cooldown_elapsed(&world, asleep),
+            cooldown_elapsed(&world, awake)
+        );
+        assert!(world.get::<TimeSimulatingStopped>(asleep).is_none());
+    }
+
+    #[test]
+    fn sleeping_building_activates_like_an_awake_one() {
+        let (mut world, mut schedule) = timer_world(update_building_timers);
+        let awake = world.spawn((cooldown(0.75), Simulate)).id();
+        let asleep = world.spawn((cooldown(0.75), Simulate)).id();
+
+        put_to_sleep(&mut world, asleep);
+        step(&mut world, &mut schedule, 40);
+        assert!(world.get::<Activate>(awake).is_some());
+        assert!(world.get::<Activate>(asleep).is_none());
+
+        world.entity_mut(asleep).insert(Simulate);
+        step(&mut world, &mut schedule, 1);
+        assert!(world.get::<Activate>(asleep).is_some());
+        assert_eq!(cooldown_elapsed(&world, asleep), Duration::ZERO);
+    }
+
+    #[test]
+    fn paused_game_time_changes_nothing() {
+        let (mut world, mut schedule) = timer_world(update_building_timers);
+        let awake = world.spawn((cooldown(10.0), Simulate)).id();
+        let asleep = world.spawn((cooldown(10.0), Simulate)).id();
+        put_to_sleep(&mut world, asleep);
+        step(&mut world, &mut schedule, 1);
+
+        // While the game is paused `simulate_game` doesn't run, so the game world time isn't
+        // updated no matter how much real time passes
+        world.entity_mut(asleep).insert(Simulate);
+        step(&mut world, &mut schedule, 1);
+
+        assert_eq!(cooldown_elapsed(&world, awake), tick() * 2);
+        assert_eq!(cooldown_elapsed(&world, asleep), tick() * 2);
+    }
+}
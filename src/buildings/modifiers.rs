@@ -0,0 +1,157 @@
+//! Modifiers that buildings apply to the allied buildings around them. Changing the modifiers of a
+//! building invalidates its [`ObjectCachedMap`] so its footprint is recomputed
+
+use crate::buildings::{tile_distance, BuildingMarker, Simulate};
+use crate::objects::{invalidate_object_cache, ObjectCachedMap, TileToObjectIndex};
+use bevy::prelude::{
+    Commands, Component, Entity, FromReflect, Query, Reflect, ResMut, With, Without, World,
+};
+use bevy_ecs_tilemap::tiles::TilePos;
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{ObjectGridPosition, ObjectId};
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum BuildingModifier {
+    /// Increases the pathfinding strength, and so the size of the footprint, of affected buildings
+    #[default]
+    PathfindStrength,
+}
+
+/// Applies a [`BuildingModifier`] to every allied building within `range` tiles of this building
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct BuildingModifierSource {
+    pub range: u32,
+    pub modifier: BuildingModifier,
+    pub amount: u32,
+}
+
+impl SaveId for BuildingModifierSource {
+    fn save_id(&self) -> BinaryComponentId {
+        22
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        22
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+/// The total of every modifier currently applied to a building
+#[derive(Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Component)]
+pub struct BuildingModifiers {
+    pub pathfind_strength: u32,
+}
+
+impl BuildingModifiers {
+    fn add(&mut self, source: &BuildingModifierSource) {
+        match source.modifier {
+            BuildingModifier::PathfindStrength => {
+                self.pathfind_strength = self.pathfind_strength.saturating_add(source.amount)
+            }
+        }
+    }
+
+    /// The extra pathfinding strength the given entity gets from its modifiers
+    pub fn pathfind_strength_bonus(world: &World, entity: Entity) -> u32 {
+        world
+            .get::<BuildingModifiers>(entity)
+            .map_or(0, |modifiers| modifiers.pathfind_strength)
+    }
+}
+
+/// Recomputes the modifiers of every building from the modifier sources in range. Buildings whose
+/// modifiers changed have their cache invalidated and are woken up so the new footprint is used
+pub fn update_building_modifiers(
+    sources: Query<
+        (
+            Entity,
+            &PlayerMarker,
+            &ObjectGridPosition,
+            &BuildingModifierSource,
+        ),
+        (With<BuildingMarker>, Without<DespawnObject>),
+    >,
+    mut buildings: Query<
+        (
+            Entity,
+            &ObjectId,
+            &PlayerMarker,
+            &ObjectGridPosition,
+            Option<&mut BuildingModifiers>,
+            Option<&ObjectCachedMap>,
+        ),
+        (With<BuildingMarker>, Without<DespawnObject>, Without<MapId>),
+    >,
+    mut tile_to_object_index: ResMut<TileToObjectIndex>,
+    mut commands: Commands,
+) {
+    for (entity, object_id, player_marker, object_grid_position, opt_modifiers, opt_cache) in
+        buildings.iter_mut()
+    {
+        let building_pos: TilePos = object_grid_position.tile_position.into();
+
+        let mut new_modifiers = BuildingModifiers::default();
+        for (source_entity, source_player_marker, source_grid_position, source) in sources.iter() {
+            if source_entity == entity || source_player_marker.id() != player_marker.id() {
+                continue;
+            }
+            if tile_distance(building_pos, source_grid_position.tile_position.into())
+                <= source.range
+            {
+                new_modifiers.add(source);
+            }
+        }
+
+        match opt_modifiers {
+            Some(mut modifiers) => {
+                if *modifiers == new_modifiers {
+                    continue;
+                }
+                *modifiers = new_modifiers;
+            }
+            None => {
+                if new_modifiers == BuildingModifiers::default() {
+                    continue;
+                }
+                commands.entity(entity).insert(new_modifiers);
+            }
+        }
+
+        // Buildings that don't have a cache yet will get one using the new modifiers
+        if let Some(object_cached_map) = opt_cache {
+            invalidate_object_cache(
+                entity,
+                object_id,
+                object_cached_map,
+                &mut tile_to_object_index,
+                &mut commands,
+            );
+            commands.entity(entity).insert(Simulate);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::buildings::{Activate, Building};
+use crate::color_system::{increase_ability_points, increase_building_points};
+use crate::game::game_rng::GameRng;
+use crate::player::PlayerPoints;
+use bevy::prelude::{Commands, Component, Entity, FromReflect, Query, Reflect, ResMut, With};
+use bevy_ggf::player::{Player, PlayerMarker};
+use serde::{Deserialize, Serialize};
+
+use super::Simulate;
+
+/// A capturable structure that is only ever placed by level files. It can't be damaged or
+/// demolished and instead changes hands whenever another player fully strengthens its tile. Every
+/// time it activates it gives its owner points
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Objective {
+    pub building_points: u32,
+    pub ability_points: u32,
+}
+
+pub fn simulate_objectives(
+    objectives: Query<
+        (Entity, &PlayerMarker, &Building<Objective>),
+        (With<Activate>, With<Simulate>),
+    >,
+    mut player_query: Query<(&mut PlayerPoints, &Player)>,
+    mut rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    for (entity, player_marker, objective) in objectives.iter() {
+        commands.entity(entity).remove::<Activate>();
+
+        // The neutral faction doesn't have any points so objectives it holds give nothing
+        let Some((mut player_points, _)) = player_query
+            .iter_mut()
+            .find(|(_, player)| player.id() == player_marker.id())
+        else {
+            continue;
+        };
+
+        for _ in 0..objective.building_type.building_points {
+            increase_building_points(&mut player_points, &mut rng);
+        }
+        for _ in 0..objective.building_type.ability_points {
+            increase_ability_points(&mut player_points, &mut rng);
+        }
+    }
+}
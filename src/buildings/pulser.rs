@@ -1,11 +1,14 @@
 ﻿use std::hash::Hash;
 
 use crate::buildings::{Activate, Building};
-use crate::color_system::{convert_tile, ColorConflictEvent, TileColor, TileColorStrength};
+use crate::color_system::{convert_tile, ColorConflictEvent, TileColor};
+use crate::game::game_rng::GameRng;
 use crate::objects::ObjectCachedMap;
 use bevy::ecs::event::EventWriter;
 use bevy::ecs::system::Commands;
-use bevy::prelude::{Component, Entity, FromReflect, Query, Reflect, With, Without};
+use bevy::prelude::{
+    Component, Entity, FromReflect, Query, Reflect, Res, ResMut, Time, With, Without,
+};
 use bevy_ecs_tilemap::tiles::TileStorage;
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::mapping::tiles::Tile;
@@ -18,7 +21,7 @@ use bevy_ggf::object::ObjectId;
 use bevy_ggf::player::PlayerMarker;
 
 use super::building_pathfinding::PathfindStrengthExt;
-use super::Simulate;
+use super::{Simulate, TimeSimulatingStopped};
 
 #[derive(
     Default,
@@ -67,6 +70,8 @@ pub fn simulate_pulsers_from_cache(
         (With<Tile>, Without<Building<Pulser>>, Without<MapId>),
     >,
     mut event_writer: EventWriter<ColorConflictEvent>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     let Some((_, _, tile_storage, _)) = tile_storage_query
@@ -75,7 +80,6 @@ pub fn simulate_pulsers_from_cache(
     else {
         return;
     };
-    let mut rng = rand::thread_rng();
 
     for (entity, id, player_marker, pulser, cache) in pulsers.iter() {
         commands.entity(entity).remove::<Activate>();
@@ -91,8 +95,7 @@ pub fn simulate_pulsers_from_cache(
             if let Ok((_, _, options)) = tiles.get_mut(tile_entity) {
                 if let Some((tile_player_marker, tile_color)) = options.as_ref() {
                     if player_marker.id() == tile_player_marker.id() {
-                        if let TileColorStrength::Five = tile_color.tile_color_strength {
-                        } else {
+                        if !tile_color.max_strength() {
                             target_tiles.push((index, tile));
                         }
                     } else {
@@ -133,7 +136,10 @@ pub fn simulate_pulsers_from_cache(
         }
 
         if tiles_changed == 0 {
-            commands.entity(entity).remove::<Simulate>();
+            commands
+                .entity(entity)
+                .remove::<Simulate>()
+                .insert(TimeSimulatingStopped::new(&time));
         }
     }
 }
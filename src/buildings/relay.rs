@@ -0,0 +1,20 @@
+use bevy::prelude::{Component, FromReflect, Reflect};
+use serde::{Deserialize, Serialize};
+
+/// A support building that doesn't color anything. It is spawned with a
+/// [`BuildingModifierSource`](super::modifiers::BuildingModifierSource) that extends the range of
+/// the allied buildings around it
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Relay;
@@ -1,8 +1,10 @@
 use crate::buildings::{Activate, Building};
 use crate::color_system::{convert_tile, ColorConflictEvent, TileColor};
+use crate::game::game_rng::GameRng;
 use crate::objects::ObjectCachedMap;
 use bevy::prelude::{
-    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, With, Without,
+    Commands, Component, Entity, EventWriter, FromReflect, Query, Reflect, Res, ResMut, Time, With,
+    Without,
 };
 use bevy_ecs_tilemap::prelude::{TileStorage, TilemapSize};
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
@@ -14,7 +16,7 @@ use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use super::building_pathfinding::PathfindStrengthExt;
-use super::Simulate;
+use super::{Simulate, TimeSimulatingStopped};
 
 #[derive(
     Default,
@@ -61,6 +63,8 @@ pub fn simulate_scatter_from_cache(
         (With<Tile>, Without<Building<Scatter>>, Without<MapId>),
     >,
     mut event_writer: EventWriter<ColorConflictEvent>,
+    time: Res<Time>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
     let Some((_, _, tile_storage, _)) = tile_storage_query
@@ -75,8 +79,6 @@ pub fn simulate_scatter_from_cache(
 
         let mut tiles_changed: u32 = 0;
 
-        let mut rng = rand::thread_rng();
-
         for _ in 0..=scatter.building_type.scatter_amount {
             let y: usize = rng.gen_range(0..cache.cache.len());
 
@@ -98,7 +100,10 @@ pub fn simulate_scatter_from_cache(
         }
 
         if tiles_changed == 0 {
-            commands.entity(entity).remove::<Simulate>();
+            commands
+                .entity(entity)
+                .remove::<Simulate>()
+                .insert(TimeSimulatingStopped::new(&time));
             continue;
         }
     }
@@ -0,0 +1,535 @@
+//! Upgrading buildings in place to a higher tier. What every tier changes for a building is
+//! described by the tiers of its [`TierUpgradeExt`] implementation
+
+use crate::buildings::artillery::Artillery;
+use crate::buildings::harvester::Harvester;
+use crate::buildings::line::Line;
+use crate::buildings::modifiers::BuildingModifierSource;
+use crate::buildings::pulser::Pulser;
+use crate::buildings::scatter::Scatter;
+use crate::buildings::wall::Wall;
+use crate::buildings::workshop::Workshop;
+use crate::buildings::{Building, BuildingCooldown, BuildingMarker, BuildingPointsSpent, Simulate};
+use crate::objects::{invalidate_object_cache, ObjectCachedMap, TileToObjectIndex};
+use crate::player::PlayerPoints;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{
+    Commands, Component, Entity, FromReflect, Query, Reflect, ResMut, With, Without, World,
+};
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::command::{GameCommand, GameCommands};
+use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::object::{ObjectGridPosition, ObjectId, ObjectInfo};
+use bevy_ggf::player::{Player, PlayerMarker};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_BUILDING_TIER: u32 = 3;
+
+/// The tier of a building. Buildings without one are tier 1
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct BuildingTier {
+    pub tier: u32,
+}
+
+impl SaveId for BuildingTier {
+    fn save_id(&self) -> BinaryComponentId {
+        27
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        27
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+/// What upgrading a building to a tier costs and what it adds to the building
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TierUpgrade<T> {
+    pub cost: u32,
+    /// Multiplied with the cooldown of the building
+    pub cooldown_multiplier: f32,
+    /// The stats the tier adds, specific to the type of building
+    pub stats: T,
+}
+
+impl<T> TierUpgrade<T> {
+    const fn new(cost: u32, cooldown_multiplier: f32, stats: T) -> TierUpgrade<T> {
+        TierUpgrade {
+            cost,
+            cooldown_multiplier,
+            stats,
+        }
+    }
+}
+
+/// What a tier adds to a [`Pulser`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PulserTier {
+    pub strength: u32,
+    pub max_pulse_tiles: u32,
+}
+
+/// What a tier adds to a [`Scatter`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScatterTier {
+    pub scatter_range: u32,
+    pub scatter_amount: u32,
+}
+
+/// What a tier adds to a [`Line`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineTier {
+    pub strength: u32,
+    pub max_changed_per_side: u32,
+}
+
+/// What a tier adds to a [`Wall`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WallTier {
+    pub shield_range: u32,
+    pub shield_hits: u32,
+}
+
+/// What a tier adds to a [`Harvester`]. Fewer strength per point means more points per harvest
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarvesterTier {
+    pub harvest_range: u32,
+    pub strength_per_point_reduction: u32,
+}
+
+/// What a tier adds to the [`BuildingModifierSource`] of a [`Relay`](super::relay::Relay)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RelayTier {
+    pub range: u32,
+    pub amount: u32,
+}
+
+/// What a tier adds to an [`Artillery`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ArtilleryTier {
+    pub target_range: u32,
+    pub blast_radius: u32,
+}
+
+/// What a tier adds to a [`Workshop`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorkshopTier {
+    pub max_painters: u32,
+}
+
+/// A component that is upgraded when its building goes up a tier
+pub trait TierUpgradeExt {
+    type Tier: Copy;
+
+    /// The upgrades to tier 2 and tier 3
+    const TIERS: [TierUpgrade<Self::Tier>; 2];
+
+    fn apply_tier_upgrade(&mut self, tier: &Self::Tier);
+
+    /// Returns the upgrade that takes the building to the given tier, if there is one
+    fn tier_upgrade(tier: u32) -> Option<TierUpgrade<Self::Tier>> {
+        match tier {
+            2..=MAX_BUILDING_TIER => Self::TIERS.get(tier as usize - 2).copied(),
+            _ => None,
+        }
+    }
+}
+
+impl TierUpgradeExt for Pulser {
+    type Tier = PulserTier;
+
+    const TIERS: [TierUpgrade<PulserTier>; 2] = [
+        TierUpgrade::new(
+            75,
+            0.9,
+            PulserTier {
+                strength: 2,
+                max_pulse_tiles: 5,
+            },
+        ),
+        TierUpgrade::new(
+            125,
+            0.8,
+            PulserTier {
+                strength: 2,
+                max_pulse_tiles: 5,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &PulserTier) {
+        self.strength += tier.strength;
+        self.max_pulse_tiles += tier.max_pulse_tiles;
+    }
+}
+
+impl TierUpgradeExt for Scatter {
+    type Tier = ScatterTier;
+
+    const TIERS: [TierUpgrade<ScatterTier>; 2] = [
+        TierUpgrade::new(
+            75,
+            1.0,
+            ScatterTier {
+                scatter_range: 1,
+                scatter_amount: 10,
+            },
+        ),
+        TierUpgrade::new(
+            125,
+            0.85,
+            ScatterTier {
+                scatter_range: 1,
+                scatter_amount: 10,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &ScatterTier) {
+        self.scatter_range += tier.scatter_range;
+        self.scatter_amount += tier.scatter_amount;
+    }
+}
+
+impl TierUpgradeExt for Line {
+    type Tier = LineTier;
+
+    const TIERS: [TierUpgrade<LineTier>; 2] = [
+        TierUpgrade::new(
+            75,
+            1.0,
+            LineTier {
+                strength: 3,
+                max_changed_per_side: 1,
+            },
+        ),
+        TierUpgrade::new(
+            125,
+            0.8,
+            LineTier {
+                strength: 3,
+                max_changed_per_side: 1,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &LineTier) {
+        self.strength += tier.strength;
+        self.max_changed_per_side += tier.max_changed_per_side;
+    }
+}
+
+impl TierUpgradeExt for Wall {
+    type Tier = WallTier;
+
+    const TIERS: [TierUpgrade<WallTier>; 2] = [
+        TierUpgrade::new(
+            75,
+            1.0,
+            WallTier {
+                shield_range: 1,
+                shield_hits: 1,
+            },
+        ),
+        TierUpgrade::new(
+            125,
+            1.0,
+            WallTier {
+                shield_range: 1,
+                shield_hits: 2,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &WallTier) {
+        self.shield_range += tier.shield_range;
+        self.shield_hits += tier.shield_hits;
+    }
+}
+
+impl TierUpgradeExt for Harvester {
+    type Tier = HarvesterTier;
+
+    const TIERS: [TierUpgrade<HarvesterTier>; 2] = [
+        TierUpgrade::new(
+            100,
+            0.9,
+            HarvesterTier {
+                harvest_range: 1,
+                strength_per_point_reduction: 2,
+            },
+        ),
+        TierUpgrade::new(
+            150,
+            0.8,
+            HarvesterTier {
+                harvest_range: 1,
+                strength_per_point_reduction: 2,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &HarvesterTier) {
+        self.harvest_range += tier.harvest_range;
+        self.strength_per_point = self
+            .strength_per_point
+            .saturating_sub(tier.strength_per_point_reduction)
+            .max(1);
+    }
+}
+
+/// Relays are upgraded through the [`BuildingModifierSource`] they spawn with
+impl TierUpgradeExt for BuildingModifierSource {
+    type Tier = RelayTier;
+
+    const TIERS: [TierUpgrade<RelayTier>; 2] = [
+        TierUpgrade::new(
+            75,
+            1.0,
+            RelayTier {
+                range: 1,
+                amount: 0,
+            },
+        ),
+        TierUpgrade::new(
+            125,
+            1.0,
+            RelayTier {
+                range: 1,
+                amount: 1,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &RelayTier) {
+        self.range += tier.range;
+        self.amount += tier.amount;
+    }
+}
+
+impl TierUpgradeExt for Artillery {
+    type Tier = ArtilleryTier;
+
+    const TIERS: [TierUpgrade<ArtilleryTier>; 2] = [
+        TierUpgrade::new(
+            100,
+            0.85,
+            ArtilleryTier {
+                target_range: 5,
+                blast_radius: 0,
+            },
+        ),
+        TierUpgrade::new(
+            150,
+            0.75,
+            ArtilleryTier {
+                target_range: 5,
+                blast_radius: 1,
+            },
+        ),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &ArtilleryTier) {
+        self.target_range += tier.target_range;
+        self.blast_radius += tier.blast_radius;
+    }
+}
+
+impl TierUpgradeExt for Workshop {
+    type Tier = WorkshopTier;
+
+    const TIERS: [TierUpgrade<WorkshopTier>; 2] = [
+        TierUpgrade::new(75, 0.8, WorkshopTier { max_painters: 1 }),
+        TierUpgrade::new(125, 0.7, WorkshopTier { max_painters: 1 }),
+    ];
+
+    fn apply_tier_upgrade(&mut self, tier: &WorkshopTier) {
+        self.max_painters += tier.max_painters;
+    }
+}
+
+/// Returns what upgrading a building of the given object type to the given tier costs, if it can be
+/// upgraded to that tier
+pub fn building_tier_cost(object_type_name: &str, tier: u32) -> Option<u32> {
+    match object_type_name {
+        "Pulser" => Pulser::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Scatter" => Scatter::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Line" => Line::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Wall" => Wall::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Harvester" => Harvester::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Relay" => BuildingModifierSource::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Artillery" => Artillery::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        "Workshop" => Workshop::tier_upgrade(tier).map(|upgrade| upgrade.cost),
+        _ => None,
+    }
+}
+
+/// Applies the upgrade to the given tier to the entity if it has the upgraded component, returning
+/// the cooldown multiplier of the upgrade
+fn upgrade_component<T>(world: &mut World, entity: Entity, tier: u32) -> Option<f32>
+where
+    T: TierUpgradeExt + Component,
+{
+    let upgrade = T::tier_upgrade(tier)?;
+    world
+        .get_mut::<T>(entity)?
+        .apply_tier_upgrade(&upgrade.stats);
+    Some(upgrade.cooldown_multiplier)
+}
+
+fn upgrade_building_type<T>(world: &mut World, entity: Entity, tier: u32) -> Option<f32>
+where
+    T: TierUpgradeExt + Send + Sync + 'static,
+{
+    let upgrade = T::tier_upgrade(tier)?;
+    world
+        .get_mut::<Building<T>>(entity)?
+        .building_type
+        .apply_tier_upgrade(&upgrade.stats);
+    Some(upgrade.cooldown_multiplier)
+}
+
+pub trait UpgradeBuildingExt {
+    fn upgrade_building(&mut self, player_id: usize, target_tile: TilePos) -> UpgradeBuilding;
+}
+
+impl UpgradeBuildingExt for GameCommands {
+    fn upgrade_building(&mut self, player_id: usize, target_tile: TilePos) -> UpgradeBuilding {
+        self.queue.push(UpgradeBuilding {
+            player_id,
+            target_tile_pos: target_tile,
+        });
+        UpgradeBuilding {
+            player_id,
+            target_tile_pos: target_tile,
+        }
+    }
+}
+
+/// Upgrades the players building on the target tile to its next tier
+#[derive(Reflect, FromReflect, Clone)]
+pub struct UpgradeBuilding {
+    pub player_id: usize,
+    pub target_tile_pos: TilePos,
+}
+
+impl GameCommand for UpgradeBuilding {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let mut system_state: SystemState<(
+            Query<(Entity, &Player, &mut PlayerPoints)>,
+            Query<
+                (
+                    Entity,
+                    &PlayerMarker,
+                    &ObjectGridPosition,
+                    &ObjectInfo,
+                    Option<&BuildingTier>,
+                ),
+                (With<BuildingMarker>, Without<DespawnObject>),
+            >,
+        )> = SystemState::new(world);
+        let (mut players, buildings) = system_state.get_mut(world);
+
+        let Some((building_entity, player_marker, _, object_info, opt_tier)) =
+            buildings.iter().find(|(_, _, object_grid_position, _, _)| {
+                TilePos::from(object_grid_position.tile_position) == self.target_tile_pos
+            })
+        else {
+            return Err(String::from("No building on target tile"));
+        };
+
+        if player_marker.id() != self.player_id {
+            return Err(String::from("Building not owned by upgrading player"));
+        }
+
+        let object_type_name = object_info.object_type.name.clone();
+        let next_tier = opt_tier.map_or(1, |building_tier| building_tier.tier) + 1;
+        let Some(cost) = building_tier_cost(object_type_name.as_str(), next_tier) else {
+            return Err(String::from("Building can't be upgraded any further"));
+        };
+
+        let Some((player_entity, _, mut player_points)) = players
+            .iter_mut()
+            .find(|(_, id, _)| id.id() == self.player_id)
+        else {
+            return Err(String::from("Failed to Find Player ID"));
+        };
+
+        if player_points.building_points < cost {
+            return Err(String::from("Not enough points to upgrade"));
+        }
+        player_points.building_points -= cost;
+        world.entity_mut(player_entity).insert(Changed::default());
+
+        let cooldown_multiplier = match object_type_name.as_str() {
+            "Pulser" => upgrade_building_type::<Pulser>(world, building_entity, next_tier),
+            "Scatter" => upgrade_building_type::<Scatter>(world, building_entity, next_tier),
+            "Line" => upgrade_building_type::<Line>(world, building_entity, next_tier),
+            "Wall" => upgrade_building_type::<Wall>(world, building_entity, next_tier),
+            "Harvester" => upgrade_building_type::<Harvester>(world, building_entity, next_tier),
+            "Relay" => {
+                upgrade_component::<BuildingModifierSource>(world, building_entity, next_tier)
+            }
+            "Artillery" => upgrade_building_type::<Artillery>(world, building_entity, next_tier),
+            "Workshop" => upgrade_building_type::<Workshop>(world, building_entity, next_tier),
+            _ => None,
+        };
+        if let (Some(cooldown_multiplier), Some(mut cooldown)) = (
+            cooldown_multiplier,
+            world.get_mut::<BuildingCooldown>(building_entity),
+        ) {
+            cooldown.timer_reset *= cooldown_multiplier;
+        }
+        if let Some(mut points_spent) = world.get_mut::<BuildingPointsSpent>(building_entity) {
+            points_spent.points += cost;
+        }
+
+        world.entity_mut(building_entity).insert((
+            BuildingTier { tier: next_tier },
+            Changed::default(),
+            Simulate,
+        ));
+
+        // The footprint depends on the buildings stats so it has to be recomputed
+        let mut system_state: SystemState<(
+            Query<(&ObjectId, &ObjectCachedMap)>,
+            ResMut<TileToObjectIndex>,
+            Commands,
+        )> = SystemState::new(world);
+        let (cached_maps, mut tile_to_object_index, mut commands) = system_state.get_mut(world);
+        if let Ok((object_id, object_cached_map)) = cached_maps.get(building_entity) {
+            invalidate_object_cache(
+                building_entity,
+                object_id,
+                object_cached_map,
+                &mut tile_to_object_index,
+                &mut commands,
+            );
+        }
+        system_state.apply(world);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use crate::buildings::Building;
+use crate::objects::{ObjectIndex, TileToObjectIndex};
+use bevy::prelude::{Component, FromReflect, Query, Reflect, Without};
+use bevy_ecs_tilemap::tiles::TilePos;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+use super::building_pathfinding::PathfindStrengthExt;
+
+/// A defensive building that doesn't color anything. Tiles owned by the same player within its
+/// range absorb extra hits before they are damaged, from guaranteed damage like nukes as well
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Wall {
+    pub shield_range: u32,
+    /// How many hits a shielded tile absorbs before it is damaged once
+    pub shield_hits: u32,
+}
+
+impl PathfindStrengthExt for Wall {
+    fn pathfinding_strength(&self) -> u32 {
+        self.shield_range
+    }
+}
+
+/// Inserted onto a shielded tile to count the hits it has absorbed since it was last damaged
+#[derive(Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Component)]
+pub struct ShieldHits {
+    pub hits: u32,
+}
+
+/// Returns how many hits the strongest wall owned by `player_id` that covers the tile makes it
+/// absorb. Returns 0 if the tile isn't shielded for that player
+pub fn tile_shield_strength(
+    tile_pos: &TilePos,
+    player_id: usize,
+    tile_to_object_index: &TileToObjectIndex,
+    object_index: &ObjectIndex,
+    walls: &Query<(&PlayerMarker, &Building<Wall>), Without<Tile>>,
+) -> u32 {
+    let Some(object_vec) = tile_to_object_index.map.get(tile_pos) else {
+        return 0;
+    };
+
+    let mut shield_hits = 0;
+    for object_id in object_vec.iter() {
+        let Some(entity) = object_index.hashmap.get(object_id) else {
+            continue;
+        };
+        if let Ok((player_marker, wall)) = walls.get(*entity) {
+            if player_marker.id() == player_id {
+                shield_hits = shield_hits.max(wall.building_type.shield_hits);
+            }
+        }
+    }
+    shield_hits
+}
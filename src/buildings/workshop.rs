@@ -0,0 +1,151 @@
+use crate::buildings::{Activate, Building, BuildingCooldown, Simulate};
+use crate::game::GameData;
+use crate::units::painter::Painter;
+use crate::units::{Unit, UnitMarker};
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{
+    Component, Entity, FromReflect, Query, Reflect, Timer, TimerMode, With, Without, World,
+};
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::command::{GameCommand, GameCommands};
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::mapping::tiles::ObjectStackingClass;
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{Object, ObjectGridPosition, ObjectInfo};
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+/// A building that doesn't color anything. Once its cooldown is over its player can spawn a
+/// [`Painter`] on its tile, as long as they have less than `max_painters` painters for every
+/// workshop they own
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Workshop {
+    pub max_painters: u32,
+}
+
+pub trait SpawnPainterExt {
+    fn spawn_painter(&mut self, player_id: usize, target_tile: TilePos) -> SpawnPainter;
+}
+
+impl SpawnPainterExt for GameCommands {
+    fn spawn_painter(&mut self, player_id: usize, target_tile: TilePos) -> SpawnPainter {
+        self.queue.push(SpawnPainter {
+            player_id,
+            target_tile_pos: target_tile,
+        });
+        SpawnPainter {
+            player_id,
+            target_tile_pos: target_tile,
+        }
+    }
+}
+
+/// Spawns a painter from the players workshop on the target tile. The workshop has to be done with
+/// its cooldown and starts it over once the painter is out
+#[derive(Reflect, FromReflect, Clone)]
+pub struct SpawnPainter {
+    pub player_id: usize,
+    pub target_tile_pos: TilePos,
+}
+
+impl GameCommand for SpawnPainter {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let mut system_state: SystemState<(
+            Query<
+                (
+                    Entity,
+                    &PlayerMarker,
+                    &ObjectGridPosition,
+                    &Building<Workshop>,
+                    Option<&Activate>,
+                ),
+                (Without<MapId>, With<Simulate>, Without<DespawnObject>),
+            >,
+            Query<&PlayerMarker, (With<Unit<Painter>>, Without<DespawnObject>)>,
+        )> = SystemState::new(world);
+        let (workshops, painters) = system_state.get(world);
+
+        let Some((workshop_entity, player_marker, _, _, opt_activate)) =
+            workshops.iter().find(|(_, _, object_grid_position, _, _)| {
+                TilePos::from(object_grid_position.tile_position) == self.target_tile_pos
+            })
+        else {
+            return Err(String::from("No workshop on target tile"));
+        };
+
+        if player_marker.id() != self.player_id {
+            return Err(String::from("Workshop not owned by spawning player"));
+        }
+        if opt_activate.is_none() {
+            return Err(String::from("Workshop is still on cooldown"));
+        }
+
+        let painter_limit: u32 = workshops
+            .iter()
+            .filter(|(_, player_marker, _, _, _)| player_marker.id() == self.player_id)
+            .map(|(_, _, _, workshop, _)| workshop.building_type.max_painters)
+            .sum();
+        let painter_count = painters
+            .iter()
+            .filter(|player_marker| player_marker.id() == self.player_id)
+            .count() as u32;
+        if painter_count >= painter_limit {
+            return Err(String::from("Too many painters for the players workshops"));
+        }
+
+        let game_data = world.resource::<GameData>();
+        let unit_stacking_class = game_data.stacking_classes.get("Unit").unwrap().clone();
+        let painter_object_type = game_data.object_types.get("Painter").unwrap().clone();
+
+        // Fails when there is already a unit on the workshop
+        let mut game_commands = GameCommands::new();
+        game_commands
+            .spawn_object(
+                (
+                    ObjectGridPosition {
+                        tile_position: self.target_tile_pos.into(),
+                    },
+                    ObjectStackingClass {
+                        stack_class: unit_stacking_class,
+                    },
+                    Object,
+                    ObjectInfo {
+                        object_type: painter_object_type,
+                    },
+                    Unit {
+                        unit_type: Painter { sight_range: 8 },
+                    },
+                    BuildingCooldown {
+                        timer: Timer::from_seconds(1.0, TimerMode::Once),
+                        timer_reset: 1.0,
+                    },
+                    UnitMarker,
+                    Simulate,
+                ),
+                self.target_tile_pos,
+                MapId { id: 1 },
+                self.player_id,
+            )
+            .execute(world)?;
+
+        world
+            .entity_mut(workshop_entity)
+            .remove::<Activate>()
+            .insert(Changed::default());
+
+        Ok(())
+    }
+}
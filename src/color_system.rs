@@ -1,12 +1,19 @@
-use crate::buildings::Simulate;
-use crate::objects::{ObjectIndex, TileToObjectIndex};
+use crate::abilities::freeze::{tile_is_frozen, Freeze};
+use crate::abilities::shield::{tile_is_shielded, Shield};
+use crate::abilities::Ability;
+use crate::buildings::wall::{tile_shield_strength, ShieldHits, Wall};
+use crate::buildings::{check_is_colorable, Building, Simulate};
+use crate::game::game_rng::GameRng;
+use crate::game::GameBuildSettings;
+use crate::mapping::HardenedTile;
+use crate::objects::{wake_objects_on_tile, ObjectIndex, TileToObjectIndex};
 use crate::player::PlayerPoints;
 use bevy::app::{App, Plugin};
 use bevy::ecs::system::SystemState;
 use bevy::math::Vec3;
 use bevy::prelude::{
-    Commands, Component, Entity, EventReader, EventWriter, FromReflect, Mut, Query, ResMut,
-    Resource, With, World,
+    Commands, Component, Entity, EventReader, EventWriter, FromReflect, Mut, Query, Res, ResMut,
+    Resource, With, Without, World,
 };
 use bevy::reflect::Reflect;
 use bevy::utils::HashMap;
@@ -19,8 +26,9 @@ use bevy_ggf::mapping::MapId;
 use bevy_ggf::object::ObjectId;
 use bevy_ggf::pathfinding::PathfindCallback;
 use bevy_ggf::player::{Player, PlayerMarker};
-use rand::{thread_rng, Rng};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 pub struct ColorSystemPlugin;
 
@@ -141,7 +149,7 @@ impl PathfindCallback<TilePos> for ColorConflictCallback {
             return;
         };
 
-        if tile_terrain_info.terrain_type.name != String::from("BasicColorable") {
+        if !check_is_colorable(tile_terrain_info) {
             world.insert_resource(system_state);
             return;
         }
@@ -253,6 +261,8 @@ pub fn handle_color_conflicts(
             Entity,
             &TilePos,
             Option<(&mut PlayerMarker, &mut TileColor)>,
+            Option<&mut ShieldHits>,
+            Option<&mut HardenedTile>,
         ),
         With<Tile>,
     >,
@@ -261,12 +271,21 @@ pub fn handle_color_conflicts(
     tile_to_object_index: ResMut<TileToObjectIndex>,
     object_index: ResMut<ObjectIndex>,
     object_query: Query<(Entity, Option<&Simulate>), With<ObjectId>>,
+    walls: Query<(&PlayerMarker, &Building<Wall>), Without<Tile>>,
+    game_settings: Res<GameBuildSettings>,
+    freezes: Query<Entity, (With<Ability<Freeze>>, Without<Tile>)>,
+    mut rng: ResMut<GameRng>,
 ) {
     player_tiles_changed_count.player_lost_tiles = 0;
     player_tiles_changed_count.player_gained_tiles = 0;
 
-    for (tile_pos, player_id_vec) in color_conflicts.conflicts.iter() {
-        let mut id_hashmap: HashMap<usize, u32> = HashMap::default();
+    for (tile_pos, player_id_vec) in color_conflicts.sorted_conflicts() {
+        if tile_is_frozen(tile_pos, &tile_to_object_index, &object_index, &freezes) {
+            continue;
+        }
+
+        // Ordered so ties between players always go to the same one
+        let mut id_hashmap: BTreeMap<usize, u32> = BTreeMap::new();
         for (player_id, _object_id) in player_id_vec.iter() {
             let count = id_hashmap.entry(*player_id).or_insert(0);
             let count = *count;
@@ -294,16 +313,16 @@ pub fn handle_color_conflicts(
 
         let tile_entity = tile_storage.get(&tile_pos).unwrap();
 
-        let Ok((entity, _, options)) = tiles.get_mut(tile_entity) else {
+        let Ok((entity, _, options, opt_shield_hits, opt_hardened_tile)) =
+            tiles.get_mut(tile_entity)
+        else {
             continue;
         };
 
         match options {
             None => {
                 commands.entity(entity).insert((
-                    TileColor {
-                        tile_color_strength: TileColorStrength::One,
-                    },
+                    game_settings.tile_strength_rules.new_tile_color(),
                     PlayerMarker::new(highest.0),
                 ));
                 for (entity, mut player_points, player_id) in player_query.iter_mut() {
@@ -313,20 +332,36 @@ pub fn handle_color_conflicts(
                             .saturating_add(1);
                     }
                     if player_id.id() == highest.0 {
-                        increase_ability_points(&mut player_points);
+                        increase_ability_points(&mut player_points, &mut rng);
                     }
                 }
             }
             Some((tile_player_marker, mut tile_color)) => {
                 if highest.0 == tile_player_marker.id() {
-                    if let TileColorStrength::Five = tile_color.tile_color_strength {
+                    if tile_color.max_strength() {
                         continue;
                     } else {
-                        tile_color.strengthen();
+                        tile_color.strengthen(game_settings.tile_strength_rules.heal);
                     }
                 } else {
-                    tile_color.damage();
-                    if let TileColorStrength::Neutral = tile_color.tile_color_strength {
+                    if wall_absorbs_hit(
+                        tile_pos,
+                        entity,
+                        tile_player_marker.id(),
+                        opt_shield_hits,
+                        &tile_to_object_index,
+                        &object_index,
+                        &walls,
+                        &mut commands,
+                    ) {
+                        continue;
+                    }
+                    if absorbs_hit(opt_hardened_tile) {
+                        continue;
+                    }
+
+                    tile_color.damage(game_settings.tile_strength_rules.damage);
+                    if tile_color.is_neutral() {
                         if tile_player_marker.id() == 0 {
                             player_tiles_changed_count.player_lost_tiles =
                                 player_tiles_changed_count
@@ -340,42 +375,85 @@ pub fn handle_color_conflicts(
             }
         }
 
-        if let Some(object_vec) = tile_to_object_index.map.get(&tile_pos) {
-            for object_id in object_vec.iter() {
-                if let Some(entity) = object_index.hashmap.get(&object_id) {
-                    if let Ok((entity, opt_simulate)) = object_query.get(*entity) {
-                        if opt_simulate.is_none() {
-                            commands.entity(entity).insert(Simulate);
-                        }
-                    }
-                }
+        wake_objects_on_tile(
+            tile_pos,
+            &tile_to_object_index,
+            &object_index,
+            &object_query,
+            &mut commands,
+        );
+    }
+    color_conflicts.conflicts.clear();
+}
+
+/// Tiles shielded by a wall of their owner absorb hits until the shield is used up. Returns whether
+/// the hit was absorbed, once the shield breaks the hit count is cleared and the tile takes damage
+fn wall_absorbs_hit(
+    tile_pos: &TilePos,
+    tile_entity: Entity,
+    tile_owner: usize,
+    opt_shield_hits: Option<Mut<ShieldHits>>,
+    tile_to_object_index: &TileToObjectIndex,
+    object_index: &ObjectIndex,
+    walls: &Query<(&PlayerMarker, &Building<Wall>), Without<Tile>>,
+    commands: &mut Commands,
+) -> bool {
+    let shield_strength = tile_shield_strength(
+        tile_pos,
+        tile_owner,
+        tile_to_object_index,
+        object_index,
+        walls,
+    );
+    let hits = opt_shield_hits
+        .as_ref()
+        .map_or(0, |shield_hits| shield_hits.hits)
+        + 1;
+    if shield_strength > 0 && hits <= shield_strength {
+        match opt_shield_hits {
+            Some(mut shield_hits) => shield_hits.hits = hits,
+            None => {
+                commands.entity(tile_entity).insert(ShieldHits { hits });
             }
         }
+        return true;
     }
-    color_conflicts.conflicts.clear();
+    if opt_shield_hits.is_some() {
+        commands.entity(tile_entity).remove::<ShieldHits>();
+    }
+    false
+}
+
+/// Hardened tiles only lose strength on every second hit
+fn absorbs_hit(opt_hardened_tile: Option<Mut<HardenedTile>>) -> bool {
+    opt_hardened_tile.map_or(false, |mut hardened_tile| hardened_tile.absorb_hit())
 }
 
-pub fn increase_building_points(mut player_points: &mut PlayerPoints) {
+/// Gives the player a building point. Past 50 points the point gets less likely the closer the
+/// player is to [`PlayerPoints::MAX_BUILDING_POINTS`]
+pub fn increase_building_points(mut player_points: &mut PlayerPoints, rng: &mut GameRng) {
     if player_points.building_points < 50 {
         player_points.building_points = player_points.building_points.saturating_add(1);
         return;
     }
-    let mut rng = thread_rng();
-    let amount_fifty_points: f64 = player_points.building_points as f64 / 50.0;
-    let chance = rng.gen_bool((amount_fifty_points - 0.0) / (4.0 - 0.0));
+    let chance = rng.gen_bool(
+        (player_points.building_points as f64 / PlayerPoints::MAX_BUILDING_POINTS as f64).min(1.0),
+    );
     if !chance {
         player_points.building_points = player_points.building_points.saturating_add(1);
     }
 }
 
-pub fn increase_ability_points(mut player_points: &mut PlayerPoints) {
+/// Gives the player an ability point. Past 50 points the point gets less likely the closer the
+/// player is to [`PlayerPoints::MAX_ABILITY_POINTS`]
+pub fn increase_ability_points(mut player_points: &mut PlayerPoints, rng: &mut GameRng) {
     if player_points.ability_points < 50 {
         player_points.ability_points = player_points.ability_points.saturating_add(1);
         return;
     }
-    let mut rng = thread_rng();
-    let amount_fifty_points: f64 = player_points.ability_points as f64 / 50.0;
-    let chance = rng.gen_bool((amount_fifty_points - 0.0) / (3.0 - 0.0));
+    let chance = rng.gen_bool(
+        (player_points.ability_points as f64 / PlayerPoints::MAX_ABILITY_POINTS as f64).min(1.0),
+    );
     if !chance {
         player_points.ability_points = player_points.ability_points.saturating_add(1);
     }
@@ -390,6 +468,8 @@ pub fn handle_color_conflict_guarantees(
             Entity,
             &TilePos,
             Option<(&mut PlayerMarker, &mut TileColor)>,
+            Option<&mut ShieldHits>,
+            Option<&mut HardenedTile>,
         ),
         With<Tile>,
     >,
@@ -397,11 +477,20 @@ pub fn handle_color_conflict_guarantees(
     tile_to_object_index: ResMut<TileToObjectIndex>,
     object_index: ResMut<ObjectIndex>,
     object_query: Query<(Entity, Option<&Simulate>), With<ObjectId>>,
+    game_settings: Res<GameBuildSettings>,
+    shields: Query<&PlayerMarker, (With<Ability<Shield>>, Without<Tile>)>,
+    walls: Query<(&PlayerMarker, &Building<Wall>), Without<Tile>>,
+    freezes: Query<Entity, (With<Ability<Freeze>>, Without<Tile>)>,
 ) {
     player_tiles_changed_count.player_lost_tiles = 0;
     player_tiles_changed_count.player_gained_tiles = 0;
 
     for (tile_pos, conflict_info) in color_conflicts.guaranteed_conflicts.iter() {
+        // Frozen tiles are locked against guaranteed effects like nukes as well
+        if tile_is_frozen(tile_pos, &tile_to_object_index, &object_index, &freezes) {
+            continue;
+        }
+
         for (
             casting_player,
             affect_casting_player,
@@ -419,7 +508,9 @@ pub fn handle_color_conflict_guarantees(
 
             let tile_entity = tile_storage.get(&tile_pos).unwrap();
 
-            let Ok((entity, _, options)) = tiles.get_mut(tile_entity) else {
+            let Ok((entity, _, options, opt_shield_hits, opt_hardened_tile)) =
+                tiles.get_mut(tile_entity)
+            else {
                 continue;
             };
 
@@ -433,19 +524,32 @@ pub fn handle_color_conflict_guarantees(
                                     .saturating_add(1);
                         }
                         commands.entity(entity).insert((
-                            TileColor {
-                                tile_color_strength: TileColorStrength::One,
-                            },
+                            game_settings.tile_strength_rules.new_tile_color(),
                             PlayerMarker::new(*casting_player),
                         ));
                     }
                 }
                 Some((tile_player_marker, mut tile_color)) => {
+                    if ConflictType::Damage == *conflict_type
+                        && tile_is_shielded(
+                            tile_pos,
+                            tile_player_marker.id(),
+                            &tile_to_object_index,
+                            &object_index,
+                            &shields,
+                        )
+                    {
+                        continue;
+                    }
+
                     if *casting_player == tile_player_marker.id() && *affect_casting_player {
                         match conflict_type {
                             ConflictType::Damage => {
-                                tile_color.damage();
-                                if let TileColorStrength::Neutral = tile_color.tile_color_strength {
+                                if absorbs_hit(opt_hardened_tile) {
+                                    continue;
+                                }
+                                tile_color.damage(game_settings.tile_strength_rules.damage);
+                                if tile_color.is_neutral() {
                                     player_tiles_changed_count.player_lost_tiles =
                                         player_tiles_changed_count
                                             .player_lost_tiles
@@ -456,30 +560,50 @@ pub fn handle_color_conflict_guarantees(
                                 }
                             }
                             _ => {
-                                if let TileColorStrength::Five = tile_color.tile_color_strength {
-                                } else {
-                                    tile_color.strengthen();
+                                if !tile_color.max_strength() {
+                                    tile_color.strengthen(game_settings.tile_strength_rules.heal);
                                 }
                             }
                         }
                     } else if *affect_other_players {
+                        // Walls shield their owners tiles from guaranteed damage the same way they
+                        // do from regular conflicts
+                        if ConflictType::Stengthen != *conflict_type
+                            && wall_absorbs_hit(
+                                tile_pos,
+                                entity,
+                                tile_player_marker.id(),
+                                opt_shield_hits,
+                                &tile_to_object_index,
+                                &object_index,
+                                &walls,
+                                &mut commands,
+                            )
+                        {
+                            continue;
+                        }
                         match conflict_type {
                             ConflictType::Damage => {
-                                tile_color.damage();
-                                if let TileColorStrength::Neutral = tile_color.tile_color_strength {
+                                if absorbs_hit(opt_hardened_tile) {
+                                    continue;
+                                }
+                                tile_color.damage(game_settings.tile_strength_rules.damage);
+                                if tile_color.is_neutral() {
                                     commands.entity(entity).remove::<PlayerMarker>();
                                     commands.entity(entity).remove::<TileColor>();
                                 }
                             }
                             ConflictType::Stengthen => {
-                                if let TileColorStrength::Five = tile_color.tile_color_strength {
-                                } else {
-                                    tile_color.strengthen();
+                                if !tile_color.max_strength() {
+                                    tile_color.strengthen(game_settings.tile_strength_rules.heal);
                                 }
                             }
                             ConflictType::Natural => {
-                                tile_color.damage();
-                                if let TileColorStrength::Neutral = tile_color.tile_color_strength {
+                                if absorbs_hit(opt_hardened_tile) {
+                                    continue;
+                                }
+                                tile_color.damage(game_settings.tile_strength_rules.damage);
+                                if tile_color.is_neutral() {
                                     commands.entity(entity).remove::<PlayerMarker>();
                                     commands.entity(entity).remove::<TileColor>();
                                 }
@@ -488,17 +612,13 @@ pub fn handle_color_conflict_guarantees(
                     }
                 }
             }
-            if let Some(object_vec) = tile_to_object_index.map.get(&tile_pos) {
-                for object_id in object_vec.iter() {
-                    if let Some(entity) = object_index.hashmap.get(&object_id) {
-                        if let Ok((entity, opt_simulate)) = object_query.get(*entity) {
-                            if opt_simulate.is_none() {
-                                commands.entity(entity).insert(Simulate);
-                            }
-                        }
-                    }
-                }
-            }
+            wake_objects_on_tile(
+                tile_pos,
+                &tile_to_object_index,
+                &object_index,
+                &object_query,
+                &mut commands,
+            );
         }
     }
     color_conflicts.guaranteed_conflicts.clear();
@@ -539,6 +659,14 @@ pub struct ColorConflicts {
 }
 
 impl ColorConflicts {
+    /// The conflicts ordered by tile. The map itself iterates in a different order every game, so
+    /// anything that rolls the [`GameRng`] per conflict has to go through this to replay the same
+    pub fn sorted_conflicts(&self) -> Vec<(&TilePos, &Vec<(usize, usize)>)> {
+        let mut conflicts: Vec<(&TilePos, &Vec<(usize, usize)>)> = self.conflicts.iter().collect();
+        conflicts.sort_unstable_by_key(|(tile_pos, _)| (tile_pos.x, tile_pos.y));
+        conflicts
+    }
+
     pub fn register_conflict(&mut self, tile_pos: TilePos, player: usize, from_object: usize) {
         if let Some(conflicts) = self.conflicts.get_mut(&tile_pos) {
             conflicts.push((player, from_object));
@@ -579,19 +707,137 @@ impl ColorConflicts {
     }
 }
 
-#[derive(
-    Default, Clone, Eq, Hash, Debug, PartialEq, Reflect, FromReflect, Serialize, Deserialize,
-)]
-pub enum TileColorStrength {
-    #[default]
-    Neutral,
-    One,
-    Two,
-    Three,
-    Four,
-    Five,
+/// How strong tiles can get and how much a single conflict changes their strength. Part of the
+/// [`GameBuildSettings`] so every match can use its own rules. The menu cycles through a few
+/// presets and can set every field on its own on top of them
+///
+/// [`GameBuildSettings`]: crate::game::GameBuildSettings
+#[derive(Clone, Copy, Eq, Hash, Debug, PartialEq, Reflect, FromReflect)]
+pub struct TileStrengthRules {
+    pub max_strength: u32,
+    /// How much strength a tile loses every time it is damaged
+    pub damage: u32,
+    /// How much strength a tile gains every time it is strengthened
+    pub heal: u32,
 }
 
+impl Default for TileStrengthRules {
+    fn default() -> Self {
+        TILE_STRENGTH_PRESETS[0].1
+    }
+}
+
+/// The rules that can be picked in the menu, in the order they are cycled through
+const TILE_STRENGTH_PRESETS: [(&str, TileStrengthRules); 4] = [
+    (
+        "Standard",
+        TileStrengthRules {
+            max_strength: 5,
+            damage: 1,
+            heal: 1,
+        },
+    ),
+    (
+        "Blitz",
+        TileStrengthRules {
+            max_strength: 3,
+            damage: 1,
+            heal: 1,
+        },
+    ),
+    (
+        "Siege",
+        TileStrengthRules {
+            max_strength: 10,
+            damage: 1,
+            heal: 1,
+        },
+    ),
+    (
+        "Brittle",
+        TileStrengthRules {
+            max_strength: 5,
+            damage: 2,
+            heal: 1,
+        },
+    ),
+];
+
+impl std::fmt::Display for TileStrengthRules {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match TILE_STRENGTH_PRESETS
+            .iter()
+            .find(|(_, rules)| rules == self)
+        {
+            Some((name, _)) => write!(f, "{} ({} levels)", name, self.max_strength),
+            None => write!(
+                f,
+                "{} levels, -{} / +{}",
+                self.max_strength, self.damage, self.heal
+            ),
+        }
+    }
+}
+
+impl TileStrengthRules {
+    /// The highest max strength the rules can be set to
+    pub const MAX_STRENGTH_LIMIT: u32 = 10;
+
+    pub fn increase_max_strength(&mut self) {
+        self.max_strength = (self.max_strength + 1).min(TileStrengthRules::MAX_STRENGTH_LIMIT);
+    }
+
+    /// Damage and heal are lowered along with the max strength so a single hit never does more
+    /// than take a full strength tile to neutral
+    pub fn decrease_max_strength(&mut self) {
+        self.max_strength = self.max_strength.saturating_sub(1).max(1);
+        self.damage = self.damage.min(self.max_strength);
+        self.heal = self.heal.min(self.max_strength);
+    }
+
+    pub fn increase_damage(&mut self) {
+        self.damage = (self.damage + 1).min(self.max_strength);
+    }
+
+    pub fn decrease_damage(&mut self) {
+        self.damage = self.damage.saturating_sub(1).max(1);
+    }
+
+    pub fn increase_heal(&mut self) {
+        self.heal = (self.heal + 1).min(self.max_strength);
+    }
+
+    pub fn decrease_heal(&mut self) {
+        self.heal = self.heal.saturating_sub(1).max(1);
+    }
+
+    fn preset_index(&self) -> Option<usize> {
+        TILE_STRENGTH_PRESETS
+            .iter()
+            .position(|(_, rules)| rules == self)
+    }
+
+    pub fn next_preset(&self) -> TileStrengthRules {
+        let index = self.preset_index().map_or(0, |index| index + 1);
+        TILE_STRENGTH_PRESETS[index % TILE_STRENGTH_PRESETS.len()].1
+    }
+
+    pub fn prev_preset(&self) -> TileStrengthRules {
+        let index = self.preset_index().unwrap_or(0) + TILE_STRENGTH_PRESETS.len() - 1;
+        TILE_STRENGTH_PRESETS[index % TILE_STRENGTH_PRESETS.len()].1
+    }
+
+    /// The color of a tile that was just taken over
+    pub fn new_tile_color(&self) -> TileColor {
+        TileColor {
+            strength: 1,
+            max_strength: self.max_strength,
+        }
+    }
+}
+
+/// The strength of an owned tile. A tile whose strength drops to 0 turns neutral and loses its
+/// color
 #[derive(
     Default,
     Clone,
@@ -606,7 +852,8 @@ pub enum TileColorStrength {
     Deserialize,
 )]
 pub struct TileColor {
-    pub tile_color_strength: TileColorStrength,
+    pub strength: u32,
+    pub max_strength: u32,
 }
 
 impl SaveId for TileColor {
@@ -628,108 +875,45 @@ impl SaveId for TileColor {
 }
 
 impl TileColor {
+    /// Neutral and full strength tiles fill the whole tile, everything in between grows from half
+    /// the tile
     pub fn get_scale(&self) -> Vec3 {
-        match self.tile_color_strength {
-            TileColorStrength::Neutral => Vec3 {
-                x: 1.0,
-                y: 1.0,
-                z: 1.0,
-            },
-            TileColorStrength::One => Vec3 {
-                x: 0.5,
-                y: 0.5,
-                z: 1.0,
-            },
-            TileColorStrength::Two => Vec3 {
-                x: 0.6,
-                y: 0.6,
-                z: 1.0,
-            },
-            TileColorStrength::Three => Vec3 {
-                x: 0.7,
-                y: 0.7,
-                z: 1.0,
-            },
-            TileColorStrength::Four => Vec3 {
-                x: 0.8,
-                y: 0.8,
-                z: 1.0,
-            },
-            TileColorStrength::Five => Vec3 {
-                x: 1.0,
-                y: 1.0,
-                z: 1.0,
-            },
+        let scale = if self.is_neutral() || self.max_strength() {
+            1.0
+        } else if self.max_strength <= 2 {
+            0.5
+        } else {
+            0.5 + 0.3 * (self.strength - 1) as f32 / (self.max_strength - 2) as f32
+        };
+        Vec3 {
+            x: scale,
+            y: scale,
+            z: 1.0,
         }
     }
     pub fn get_normalized_number_representation(&self) -> f32 {
-        match self.tile_color_strength {
-            TileColorStrength::Neutral => 0.0,
-            TileColorStrength::One => 0.2,
-            TileColorStrength::Two => 0.4,
-            TileColorStrength::Three => 0.6,
-            TileColorStrength::Four => 0.8,
-            TileColorStrength::Five => 1.0,
+        if self.max_strength == 0 {
+            return 0.0;
         }
+        self.strength as f32 / self.max_strength as f32
     }
     pub fn get_number_representation(&self) -> u32 {
-        match self.tile_color_strength {
-            TileColorStrength::Neutral => 0,
-            TileColorStrength::One => 1,
-            TileColorStrength::Two => 2,
-            TileColorStrength::Three => 3,
-            TileColorStrength::Four => 4,
-            TileColorStrength::Five => 5,
-        }
+        self.strength
     }
 
     pub fn max_strength(&self) -> bool {
-        return if self.tile_color_strength == TileColorStrength::Five {
-            true
-        } else {
-            false
-        };
+        self.strength >= self.max_strength
     }
 
-    pub fn damage(&mut self) {
-        match self.tile_color_strength {
-            TileColorStrength::Neutral => {}
-            TileColorStrength::One => {
-                self.tile_color_strength = TileColorStrength::Neutral;
-            }
-            TileColorStrength::Two => {
-                self.tile_color_strength = TileColorStrength::One;
-            }
-            TileColorStrength::Three => {
-                self.tile_color_strength = TileColorStrength::Two;
-            }
-            TileColorStrength::Four => {
-                self.tile_color_strength = TileColorStrength::Three;
-            }
-            TileColorStrength::Five => {
-                self.tile_color_strength = TileColorStrength::Four;
-            }
-        }
+    pub fn is_neutral(&self) -> bool {
+        self.strength == 0
     }
 
-    pub fn strengthen(&mut self) {
-        match self.tile_color_strength {
-            TileColorStrength::Neutral => {
-                self.tile_color_strength = TileColorStrength::One;
-            }
-            TileColorStrength::One => {
-                self.tile_color_strength = TileColorStrength::Two;
-            }
-            TileColorStrength::Two => {
-                self.tile_color_strength = TileColorStrength::Three;
-            }
-            TileColorStrength::Three => {
-                self.tile_color_strength = TileColorStrength::Four;
-            }
-            TileColorStrength::Four => {
-                self.tile_color_strength = TileColorStrength::Five;
-            }
-            TileColorStrength::Five => {}
-        }
+    pub fn damage(&mut self, amount: u32) {
+        self.strength = self.strength.saturating_sub(amount);
+    }
+
+    pub fn strengthen(&mut self, amount: u32) {
+        self.strength = self.strength.saturating_add(amount).min(self.max_strength);
     }
 }
@@ -1,11 +1,20 @@
+use crate::actions::{Actions, ClickMode};
+use crate::buildings::health::BuildingHealth;
+use crate::buildings::line::{Line, LineDirection};
+use crate::buildings::upgrades::BuildingTier;
+use crate::buildings::BuildingTypes;
 use crate::color_system::TileColor;
-use crate::draw::{DrawObject, DrawTile, MyColorLens};
+use crate::draw::{world_pos_to_tile_pos, DrawObject, DrawTile, MyColorLens};
+use crate::game::puzzle::PuzzleState;
 use crate::game::state::OldTileState;
+use crate::game::sudden_death::SuddenDeathState;
 use crate::game::GameData;
 use crate::loading::TextureAssets;
+use crate::ui::accessibility::{AccessibilitySettings, OwnershipPattern};
 use crate::ui::PlayerColors;
 use bevy::prelude::*;
-use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ecs_tilemap::prelude::{TilePos, TilemapSize};
+use bevy_ggf::game_core::Game;
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::object::{ObjectGridPosition, ObjectInfo};
 use bevy_ggf::player::PlayerMarker;
@@ -13,6 +22,8 @@ use bevy_tweening::lens::TransformScaleLens;
 use bevy_tweening::{Animator, EaseFunction, RepeatCount, Tween};
 use bevy_vector_shapes::prelude::{RectangleBundle, ShapeConfig, ThicknessType};
 use bevy_vector_shapes::render::ShapePipelineType;
+use bevy_vector_shapes::shapes::Rectangle;
+use ns_defaults::camera::CursorWorldPos;
 use std::time::Duration;
 
 use super::UpdateTile;
@@ -29,6 +40,34 @@ pub struct ChildGraphics;
 #[derive(Component)]
 pub struct ChildBackgroundGraphics;
 
+/// Root of the accessibility pattern drawn over an owned tile
+#[derive(Component)]
+pub struct ChildPatternGraphics;
+
+/// The shape drawn for an object, moved whenever the object changes tiles
+#[derive(Component)]
+pub struct ChildObjectGraphics;
+
+/// Outline over a tile that sudden death collapses next
+#[derive(Component)]
+pub struct SuddenDeathWarning {
+    tile_pos: TilePos,
+}
+
+/// Outline around the region a puzzle has to capture
+#[derive(Component)]
+pub struct PuzzleTargetOutline;
+
+/// One of the pips drawn on a building for every tier above the first
+#[derive(Component)]
+pub struct ChildTierGraphics;
+
+/// Ring around a damaged building showing how much health it has left
+#[derive(Component)]
+pub struct ChildHealthGraphics {
+    pub health: u32,
+}
+
 pub fn draw_tile_backgrounds(
     game_info: Res<GameData>,
     tile_query: Query<(Entity, &TileTerrainInfo, &TilePos), (Added<UpdateTile>, Without<Children>)>,
@@ -58,10 +97,7 @@ pub fn draw_tile_backgrounds(
                                 z: 1.0,
                             },
                         },
-                        color: match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
-                            "NonColorable" => player_colors.get_noncolorable(),
-                            _ => player_colors.get_colorable(),
-                        },
+                        color: player_colors.get_terrain(tile_terrain_info),
                         hollow: false,
                         cap: Default::default(),
                         thickness: TILE_OUTLINE,
@@ -122,23 +158,14 @@ pub fn draw_tiles(
             Duration::from_millis(100),
             MyColorLens {
                 start: match &old_tile_state.is_some() {
-                    false => match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
-                        "NonColorable" => player_colors.get_noncolorable(),
-                        _ => player_colors.get_colorable(),
-                    },
+                    false => player_colors.get_terrain(tile_terrain_info),
                     true => match old_tile_state.unwrap().player_id.is_some() {
                         true => player_colors.get_color(old_tile_state.unwrap().player_id.unwrap()),
-                        false => match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
-                            "NonColorable" => player_colors.get_noncolorable(),
-                            _ => player_colors.get_colorable(),
-                        },
+                        false => player_colors.get_terrain(tile_terrain_info),
                     },
                 },
                 end: match options {
-                    None => match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
-                        "NonColorable" => player_colors.get_noncolorable(),
-                        _ => player_colors.get_colorable(),
-                    },
+                    None => player_colors.get_terrain(tile_terrain_info),
                     Some((_, player_marker)) => player_colors.get_color(player_marker.id()),
                 },
             },
@@ -205,10 +232,7 @@ pub fn draw_tiles(
                     scale: tile_color_size,
                 },
                 color: match options {
-                    None => match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
-                        "NonColorable" => player_colors.get_noncolorable(),
-                        _ => player_colors.get_colorable(),
-                    },
+                    None => player_colors.get_terrain(tile_terrain_info),
                     Some((_, player_marker)) => player_colors.get_color(player_marker.id()),
                 },
                 hollow: false,
@@ -261,13 +285,133 @@ pub fn draw_tiles(
     }
 }
 
+/// Draws the per player [`OwnershipPattern`] over owned tiles when the accessibility option is on.
+/// Redraws every tile when the option or palette changes, otherwise only tiles that were updated
+pub fn draw_ownership_patterns(
+    game_info: Res<GameData>,
+    accessibility_settings: Res<AccessibilitySettings>,
+    player_colors: Res<PlayerColors>,
+    updated_tiles: Query<
+        (
+            Entity,
+            &TilePos,
+            Option<&Children>,
+            Option<(&TileColor, &PlayerMarker)>,
+        ),
+        Added<UpdateTile>,
+    >,
+    all_tiles: Query<
+        (
+            Entity,
+            &TilePos,
+            Option<&Children>,
+            Option<(&TileColor, &PlayerMarker)>,
+        ),
+        With<TileTerrainInfo>,
+    >,
+    pattern_query: Query<Entity, With<ChildPatternGraphics>>,
+    mut commands: Commands,
+) {
+    let redraw_all = accessibility_settings.is_changed() || player_colors.is_changed();
+
+    let tiles: Vec<_> = match redraw_all {
+        true => all_tiles.iter().collect(),
+        false => updated_tiles.iter().collect(),
+    };
+
+    for (entity, tile_pos, children, options) in tiles {
+        if let Some(children) = children {
+            for child in children.iter() {
+                if pattern_query.get(*child).is_ok() {
+                    commands.entity(*child).despawn_recursive();
+                }
+            }
+        }
+
+        if !accessibility_settings.ownership_patterns {
+            continue;
+        }
+
+        let Some((tile_color, player_marker)) = options else {
+            continue;
+        };
+
+        let card_x = (tile_pos.x as f32 * (TILE_SIZE + TILE_GAP))
+            - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+        let card_y = (tile_pos.y as f32 * (TILE_SIZE + TILE_GAP))
+            - ((game_info.map_size_y as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+
+        let pattern_color =
+            OwnershipPattern::contrast_color(player_colors.get_color(player_marker.id()));
+
+        let pattern = commands
+            .spawn((
+                SpatialBundle {
+                    transform: Transform {
+                        translation: Vec3 {
+                            x: card_x,
+                            y: card_y,
+                            z: 2.5,
+                        },
+                        rotation: Default::default(),
+                        scale: tile_color.get_scale(),
+                    },
+                    ..default()
+                },
+                ChildPatternGraphics,
+            ))
+            .with_children(|parent| {
+                for (offset, size, rotation) in
+                    OwnershipPattern::for_player(player_marker.id()).shapes(TILE_SIZE)
+                {
+                    parent.spawn(bevy_vector_shapes::shapes::ShapeBundle::rect(
+                        &ShapeConfig {
+                            transform: Transform {
+                                translation: offset.extend(0.0),
+                                rotation: Quat::from_rotation_z(rotation),
+                                scale: Vec3::ONE,
+                            },
+                            color: pattern_color,
+                            hollow: false,
+                            cap: Default::default(),
+                            thickness: TILE_OUTLINE,
+                            thickness_type: ThicknessType::World,
+                            corner_radii: Default::default(),
+                            render_layers: None,
+                            alpha_mode: AlphaMode::Blend,
+                            disable_laa: false,
+                            instance_id: 0,
+                            canvas: None,
+                            texture: None,
+                            alignment: Default::default(),
+                            roundness: 0.0,
+                            pipeline: ShapePipelineType::Shape2d,
+                        },
+                        size,
+                    ));
+                }
+            })
+            .id();
+        commands.entity(entity).push_children(&[pattern]);
+    }
+}
+
 pub fn draw_objects(
     game_info: Res<GameData>,
-    tile_query: Query<(Entity, &DrawObject, &ObjectInfo, &ObjectGridPosition)>,
+    tile_query: Query<(
+        Entity,
+        &DrawObject,
+        &ObjectInfo,
+        &ObjectGridPosition,
+        Option<&BuildingTier>,
+        Option<&BuildingHealth>,
+    )>,
     mut commands: Commands,
     texture_assets: Res<TextureAssets>,
 ) {
-    for (entity, _, object_info, tile_pos) in tile_query.iter() {
+    for (entity, _, object_info, tile_pos, opt_building_tier, opt_building_health) in
+        tile_query.iter()
+    {
         let card_x = (tile_pos.tile_position.x as f32 * (TILE_SIZE + TILE_GAP))
             - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
         let card_y = (tile_pos.tile_position.y as f32 * (TILE_SIZE + TILE_GAP))
@@ -308,9 +452,22 @@ pub fn draw_objects(
                         "Pulser" => Some(texture_assets.pulser.clone()),
                         "Scatter" => Some(texture_assets.scatter.clone()),
                         "Line" => Some(texture_assets.line.clone()),
+                        "Wall" => Some(texture_assets.wall.clone()),
+                        "Harvester" => Some(texture_assets.harvester.clone()),
+                        "Relay" => Some(texture_assets.relay.clone()),
+                        "Artillery" => Some(texture_assets.artillery.clone()),
+                        "Workshop" => Some(texture_assets.workshop.clone()),
+                        "Objective" => Some(texture_assets.objective.clone()),
+                        "BonusPoints" => Some(texture_assets.bonus_points.clone()),
+                        "AbilityCharge" => Some(texture_assets.ability_charge.clone()),
+                        "Overclock" => Some(texture_assets.overclock.clone()),
+                        "Painter" => Some(texture_assets.painter.clone()),
                         "Nuke" => Some(texture_assets.nuke.clone()),
                         "Fortify" => Some(texture_assets.fortify.clone()),
                         "Expand" => Some(texture_assets.expand.clone()),
+                        "Freeze" => Some(texture_assets.freeze.clone()),
+                        "Swap" => Some(texture_assets.swap.clone()),
+                        "Shield" => Some(texture_assets.shield.clone()),
                         &_ => Some(texture_assets.pulser.clone()),
                     },
                     pipeline: ShapePipelineType::Shape2d,
@@ -320,12 +477,492 @@ pub fn draw_objects(
                     y: OBJECT_SIZE,
                 },
             ))
+            .insert(ChildObjectGraphics)
             .id();
+        if let Some(building_tier) = opt_building_tier {
+            spawn_tier_pips(&mut commands, child, building_tier.tier);
+        }
+        if let Some(building_health) = opt_building_health {
+            spawn_health_ring(&mut commands, child, building_health);
+        }
         commands.entity(entity).push_children(&[child]);
         commands.entity(entity).remove::<DrawObject>();
     }
 }
 
+/// Redraws the tier pips of buildings that were upgraded after they were drawn
+pub fn update_building_tiers(
+    object_query: Query<(&BuildingTier, &Children), Changed<BuildingTier>>,
+    graphics_query: Query<Option<&Children>, With<ChildObjectGraphics>>,
+    pips_query: Query<Entity, With<ChildTierGraphics>>,
+    mut commands: Commands,
+) {
+    for (building_tier, children) in object_query.iter() {
+        for child in children.iter() {
+            let Ok(opt_graphics_children) = graphics_query.get(*child) else {
+                continue;
+            };
+            let pips: Vec<Entity> = opt_graphics_children
+                .map(|graphics_children| {
+                    graphics_children
+                        .iter()
+                        .filter(|entity| pips_query.get(**entity).is_ok())
+                        .copied()
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            // The tier is reinserted every time the building changes so only redraw when it differs
+            if pips.len() as u32 == building_tier.tier.saturating_sub(1) {
+                continue;
+            }
+            for pip in pips {
+                commands.entity(pip).despawn_recursive();
+            }
+            spawn_tier_pips(&mut commands, *child, building_tier.tier);
+        }
+    }
+}
+
+/// Redraws the health ring of buildings whose health changed after they were drawn
+pub fn update_building_health(
+    object_query: Query<(&BuildingHealth, &Children), Changed<BuildingHealth>>,
+    graphics_query: Query<Option<&Children>, With<ChildObjectGraphics>>,
+    rings_query: Query<&ChildHealthGraphics>,
+    mut commands: Commands,
+) {
+    for (building_health, children) in object_query.iter() {
+        for child in children.iter() {
+            let Ok(opt_graphics_children) = graphics_query.get(*child) else {
+                continue;
+            };
+            let rings: Vec<(Entity, u32)> = opt_graphics_children
+                .map(|graphics_children| {
+                    graphics_children
+                        .iter()
+                        .filter_map(|entity| {
+                            rings_query
+                                .get(*entity)
+                                .ok()
+                                .map(|ring| (*entity, ring.health))
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            if rings.len() == 1 && rings[0].1 == building_health.health {
+                continue;
+            }
+            for (ring, _) in rings {
+                commands.entity(ring).despawn_recursive();
+            }
+            spawn_health_ring(&mut commands, *child, building_health);
+        }
+    }
+}
+
+/// Full health buildings don't get a ring
+fn spawn_health_ring(
+    commands: &mut Commands,
+    object_graphics: Entity,
+    building_health: &BuildingHealth,
+) {
+    if building_health.is_full() {
+        return;
+    }
+    let health_fraction = building_health.health as f32 / building_health.max_health as f32;
+
+    let ring = commands
+        .spawn(bevy_vector_shapes::shapes::ShapeBundle::arc(
+            &ShapeConfig {
+                transform: Transform {
+                    translation: Vec3 {
+                        x: 0.0,
+                        y: 0.0,
+                        z: 0.2,
+                    },
+                    rotation: Default::default(),
+                    scale: Vec3::ONE,
+                },
+                color: Color::RED,
+                thickness: TILE_OUTLINE,
+                thickness_type: ThicknessType::World,
+                alignment: Default::default(),
+                hollow: true,
+                cap: Default::default(),
+                roundness: 0.0,
+                corner_radii: Vec4::new(0.0, 0.0, 0.0, 0.0),
+                render_layers: None,
+                alpha_mode: AlphaMode::Blend,
+                disable_laa: false,
+                instance_id: 0,
+                canvas: None,
+                texture: None,
+                pipeline: ShapePipelineType::Shape2d,
+            },
+            (OBJECT_SIZE / 2.0) + TILE_OUTLINE,
+            0.0,
+            std::f32::consts::TAU * health_fraction,
+        ))
+        .insert(ChildHealthGraphics {
+            health: building_health.health,
+        })
+        .id();
+    commands.entity(object_graphics).push_children(&[ring]);
+}
+
+fn spawn_tier_pips(commands: &mut Commands, object_graphics: Entity, tier: u32) {
+    let pip_size = OBJECT_SIZE / 6.0;
+    let pips: Vec<Entity> = (1..tier)
+        .map(|pip| {
+            commands
+                .spawn(bevy_vector_shapes::shapes::ShapeBundle::rect(
+                    &ShapeConfig {
+                        transform: Transform {
+                            translation: Vec3 {
+                                x: (OBJECT_SIZE / 2.0) - (pip as f32 * pip_size * 1.5),
+                                y: (OBJECT_SIZE / 2.0) - (pip_size / 2.0),
+                                z: 0.1,
+                            },
+                            rotation: Default::default(),
+                            scale: Vec3::ONE,
+                        },
+                        color: Color::WHITE,
+                        thickness: 0.0,
+                        thickness_type: Default::default(),
+                        alignment: Default::default(),
+                        hollow: false,
+                        cap: Default::default(),
+                        roundness: 0.0,
+                        corner_radii: Vec4::new(0.0, 0.0, 0.0, 0.0),
+                        render_layers: None,
+                        alpha_mode: AlphaMode::Blend,
+                        disable_laa: false,
+                        instance_id: 0,
+                        canvas: None,
+                        texture: None,
+                        pipeline: ShapePipelineType::Shape2d,
+                    },
+                    Vec2 {
+                        x: pip_size,
+                        y: pip_size,
+                    },
+                ))
+                .insert(ChildTierGraphics)
+                .id()
+        })
+        .collect();
+    commands.entity(object_graphics).push_children(&pips);
+}
+
+/// Holds the tiles a line placed on the tile under the cursor would reach
+#[derive(Component)]
+pub struct LinePreviewGraphics {
+    tile_pos: TilePos,
+    direction: LineDirection,
+}
+
+/// Previews the reach of the line the player is about to place while a line is selected
+pub fn draw_line_preview(
+    game_info: Res<GameData>,
+    cursor_world_pos: Res<CursorWorldPos>,
+    actions: Query<(&PlayerMarker, &Actions)>,
+    preview_query: Query<(Entity, &LinePreviewGraphics)>,
+    mut commands: Commands,
+) {
+    let map_size = TilemapSize {
+        x: game_info.map_size_x,
+        y: game_info.map_size_y,
+    };
+    let preview = actions
+        .iter()
+        .find(|(player_marker, _)| player_marker.id() == 0)
+        .filter(|(_, actions)| {
+            actions.selected_building == BuildingTypes::Line
+                && actions.click_mode == ClickMode::Build
+        })
+        .and_then(|(_, actions)| {
+            world_pos_to_tile_pos(&cursor_world_pos.cursor_world_pos, &map_size)
+                .map(|tile_pos| (tile_pos, actions.line_direction))
+        });
+
+    let mut up_to_date = false;
+    for (entity, line_preview) in preview_query.iter() {
+        if preview == Some((line_preview.tile_pos, line_preview.direction)) {
+            up_to_date = true;
+        } else {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    if up_to_date {
+        return;
+    }
+    let Some((tile_pos, direction)) = preview else {
+        return;
+    };
+
+    let reach = Line::new(direction).reach() as i32;
+    let mut preview_tiles: Vec<Entity> = vec![];
+    for (step_x, step_y) in direction.steps() {
+        for distance in 1..=reach {
+            let Some(preview_tile_pos) = TilePos::from_i32_pair(
+                tile_pos.x as i32 + step_x * distance,
+                tile_pos.y as i32 + step_y * distance,
+                &map_size,
+            ) else {
+                break;
+            };
+            let card_x = (preview_tile_pos.x as f32 * (TILE_SIZE + TILE_GAP))
+                - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+            let card_y = (preview_tile_pos.y as f32 * (TILE_SIZE + TILE_GAP))
+                - ((game_info.map_size_y as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+
+            preview_tiles.push(
+                commands
+                    .spawn(bevy_vector_shapes::shapes::ShapeBundle::rect(
+                        &ShapeConfig {
+                            transform: Transform {
+                                translation: Vec3 {
+                                    x: card_x,
+                                    y: card_y,
+                                    z: 2.5,
+                                },
+                                rotation: Default::default(),
+                                scale: Vec3::ONE,
+                            },
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.3),
+                            thickness: 0.0,
+                            thickness_type: Default::default(),
+                            alignment: Default::default(),
+                            hollow: false,
+                            cap: Default::default(),
+                            roundness: 0.0,
+                            corner_radii: Vec4::new(0.0, 0.0, 0.0, 0.0),
+                            render_layers: None,
+                            alpha_mode: AlphaMode::Blend,
+                            disable_laa: false,
+                            instance_id: 0,
+                            canvas: None,
+                            texture: None,
+                            pipeline: ShapePipelineType::Shape2d,
+                        },
+                        Vec2 {
+                            x: TILE_SIZE / 2.0,
+                            y: TILE_SIZE / 2.0,
+                        },
+                    ))
+                    .id(),
+            );
+        }
+    }
+
+    commands
+        .spawn((
+            SpatialBundle::default(),
+            LinePreviewGraphics {
+                tile_pos,
+                direction,
+            },
+        ))
+        .push_children(&preview_tiles);
+}
+
+pub fn clear_line_preview(
+    preview_query: Query<Entity, With<LinePreviewGraphics>>,
+    mut commands: Commands,
+) {
+    for entity in preview_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Tile backgrounds are only spawned once so tiles whose terrain changes, like tiles collapsed by
+/// sudden death, get their background recolored here
+pub fn update_tile_backgrounds(
+    tile_query: Query<(&TileTerrainInfo, &Children), Added<UpdateTile>>,
+    mut background_query: Query<&mut Rectangle, With<ChildBackgroundGraphics>>,
+    player_colors: Res<PlayerColors>,
+) {
+    for (tile_terrain_info, children) in tile_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut rectangle) = background_query.get_mut(*child) {
+                rectangle.color = player_colors.get_terrain(tile_terrain_info);
+            }
+        }
+    }
+}
+
+/// Outlines the ring of tiles that sudden death collapses next. The outlines pulse faster the closer
+/// the collapse is
+pub fn draw_sudden_death_warning(
+    game: Res<Game>,
+    game_info: Res<GameData>,
+    time: Res<Time>,
+    mut warning_query: Query<(Entity, &SuddenDeathWarning, &mut Rectangle)>,
+    mut commands: Commands,
+) {
+    let Some(sudden_death_state) = game.game_world.get_resource::<SuddenDeathState>() else {
+        return;
+    };
+    let warned_tiles = &sudden_death_state.warned_tiles;
+
+    if warning_query.iter().count() != warned_tiles.len()
+        || warning_query
+            .iter()
+            .any(|(_, warning, _)| !warned_tiles.contains(&warning.tile_pos))
+    {
+        for (entity, _, _) in warning_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        for tile_pos in warned_tiles.iter() {
+            let card_x = (tile_pos.x as f32 * (TILE_SIZE + TILE_GAP))
+                - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+            let card_y = (tile_pos.y as f32 * (TILE_SIZE + TILE_GAP))
+                - ((game_info.map_size_y as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+
+            commands.spawn((
+                bevy_vector_shapes::shapes::ShapeBundle::rect(
+                    &ShapeConfig {
+                        transform: Transform {
+                            translation: Vec3 {
+                                x: card_x,
+                                y: card_y,
+                                z: 4.0,
+                            },
+                            rotation: Default::default(),
+                            scale: Vec3::ONE,
+                        },
+                        color: Color::RED,
+                        hollow: true,
+                        cap: Default::default(),
+                        thickness: TILE_OUTLINE,
+                        thickness_type: ThicknessType::World,
+                        corner_radii: Default::default(),
+                        render_layers: None,
+                        alpha_mode: AlphaMode::Blend,
+                        disable_laa: false,
+                        instance_id: 0,
+                        canvas: None,
+                        texture: None,
+                        alignment: Default::default(),
+                        roundness: 0.0,
+                        pipeline: ShapePipelineType::Shape2d,
+                    },
+                    Vec2 {
+                        x: TILE_SIZE - TILE_OUTLINE,
+                        y: TILE_SIZE - TILE_OUTLINE,
+                    },
+                ),
+                SuddenDeathWarning {
+                    tile_pos: *tile_pos,
+                },
+            ));
+        }
+        return;
+    }
+
+    let pulse_speed = 3.0 + 12.0 / sudden_death_state.seconds_until_collapse.max(0.5);
+    let alpha = 0.55 + 0.45 * (time.elapsed_seconds() * pulse_speed).sin();
+    for (_, _, mut rectangle) in warning_query.iter_mut() {
+        rectangle.color.set_a(alpha);
+    }
+}
+
+pub fn clear_sudden_death_warning(
+    warning_query: Query<Entity, With<SuddenDeathWarning>>,
+    mut commands: Commands,
+) {
+    for entity in warning_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn draw_puzzle_target(
+    game: Res<Game>,
+    game_info: Res<GameData>,
+    outline_query: Query<Entity, With<PuzzleTargetOutline>>,
+    mut commands: Commands,
+) {
+    let Some(puzzle_state) = game.game_world.get_resource::<PuzzleState>() else {
+        return;
+    };
+    if !outline_query.is_empty() {
+        return;
+    }
+
+    let target = puzzle_state.target;
+    let center_x = ((target.min.0 + target.max.0) as f32 / 2.0 * (TILE_SIZE + TILE_GAP))
+        - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+    let center_y = ((target.min.1 + target.max.1) as f32 / 2.0 * (TILE_SIZE + TILE_GAP))
+        - ((game_info.map_size_y as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+
+    commands.spawn((
+        bevy_vector_shapes::shapes::ShapeBundle::rect(
+            &ShapeConfig {
+                transform: Transform {
+                    translation: Vec3 {
+                        x: center_x,
+                        y: center_y,
+                        z: 4.0,
+                    },
+                    rotation: Default::default(),
+                    scale: Vec3::ONE,
+                },
+                color: Color::GOLD,
+                hollow: true,
+                cap: Default::default(),
+                thickness: TILE_OUTLINE * 2.0,
+                thickness_type: ThicknessType::World,
+                corner_radii: Default::default(),
+                render_layers: None,
+                alpha_mode: AlphaMode::Blend,
+                disable_laa: false,
+                instance_id: 0,
+                canvas: None,
+                texture: None,
+                alignment: Default::default(),
+                roundness: 0.0,
+                pipeline: ShapePipelineType::Shape2d,
+            },
+            Vec2 {
+                x: (target.max.0.saturating_sub(target.min.0) + 1) as f32 * (TILE_SIZE + TILE_GAP),
+                y: (target.max.1.saturating_sub(target.min.1) + 1) as f32 * (TILE_SIZE + TILE_GAP),
+            },
+        ),
+        PuzzleTargetOutline,
+    ));
+}
+
+pub fn clear_puzzle_target(
+    outline_query: Query<Entity, With<PuzzleTargetOutline>>,
+    mut commands: Commands,
+) {
+    for entity in outline_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn update_object_positions(
+    game_info: Res<GameData>,
+    object_query: Query<(&ObjectGridPosition, &Children), Changed<ObjectGridPosition>>,
+    mut graphics_query: Query<&mut Transform, With<ChildObjectGraphics>>,
+) {
+    for (tile_pos, children) in object_query.iter() {
+        let card_x = (tile_pos.tile_position.x as f32 * (TILE_SIZE + TILE_GAP))
+            - ((game_info.map_size_x as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+        let card_y = (tile_pos.tile_position.y as f32 * (TILE_SIZE + TILE_GAP))
+            - ((game_info.map_size_y as f32 * (TILE_SIZE + TILE_GAP)) / 2.0);
+
+        for child in children.iter() {
+            if let Ok(mut transform) = graphics_query.get_mut(*child) {
+                transform.translation.x = card_x;
+                transform.translation.y = card_y;
+            }
+        }
+    }
+}
+
 /*
 pub fn draw_game_over(
     mut term_query: Query<&mut Terminal>,
@@ -1,6 +1,11 @@
 pub mod draw;
 
-use crate::draw::draw::{draw_objects, draw_tile_backgrounds, draw_tiles, TILE_GAP, TILE_SIZE};
+use crate::draw::draw::{
+    clear_line_preview, clear_puzzle_target, clear_sudden_death_warning, draw_line_preview,
+    draw_objects, draw_ownership_patterns, draw_puzzle_target, draw_sudden_death_warning,
+    draw_tile_backgrounds, draw_tiles, update_building_health, update_building_tiers,
+    update_object_positions, update_tile_backgrounds, TILE_GAP, TILE_SIZE,
+};
 use crate::GameState;
 use bevy::app::App;
 use bevy::prelude::*;
@@ -13,10 +18,36 @@ pub struct DrawPlugin;
 impl Plugin for DrawPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            (draw_tile_backgrounds, draw_tiles, draw_objects).in_set(OnUpdate(GameState::Playing)),
+            (
+                draw_tile_backgrounds,
+                update_tile_backgrounds,
+                draw_tiles,
+                draw_ownership_patterns,
+                draw_objects,
+                update_object_positions,
+                update_building_tiers,
+                update_building_health,
+                draw_line_preview,
+                draw_sudden_death_warning,
+                draw_puzzle_target,
+            )
+                .in_set(OnUpdate(GameState::Playing)),
         );
+        app.add_system(clear_line_preview.in_schedule(OnExit(GameState::Playing)));
+        app.add_system(clear_sudden_death_warning.in_schedule(OnExit(GameState::Playing)));
+        app.add_system(clear_puzzle_target.in_schedule(OnExit(GameState::Playing)));
         app.add_systems(
-            (draw_tile_backgrounds, draw_tiles, draw_objects).in_set(OnUpdate(GameState::Ended)),
+            (
+                draw_tile_backgrounds,
+                update_tile_backgrounds,
+                draw_tiles,
+                draw_ownership_patterns,
+                draw_objects,
+                update_object_positions,
+                update_building_tiers,
+                update_building_health,
+            )
+                .in_set(OnUpdate(GameState::Ended)),
         );
 
         // app.add_system(draw_game_over.in_set(OnUpdate(GameState::Ended)));
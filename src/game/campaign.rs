@@ -0,0 +1,81 @@
+//! Campaign missions. A mission fixes the level, the enemies and the game end conditions and can
+//! take buildings and abilities away from the player
+
+use crate::abilities::Abilities;
+use crate::buildings::BuildingTypes;
+use crate::game::end_game::GameEnded;
+use crate::game::GameBuildSettings;
+use crate::loading::campaign_loader::{Campaign, CampaignHandle, CampaignProgress};
+use bevy::prelude::{Assets, FromReflect, Reflect, Res, ResMut, Time};
+use bevy_ggf::game_core::Game;
+
+/// Buildings and abilities the player can't use in a mission
+#[derive(
+    Default, Reflect, FromReflect, Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize,
+)]
+pub struct Restrictions {
+    #[serde(default)]
+    pub buildings: Vec<BuildingTypes>,
+    #[serde(default)]
+    pub abilities: Vec<Abilities>,
+}
+
+impl Restrictions {
+    pub fn allows_building(&self, building_type: BuildingTypes) -> bool {
+        !self.buildings.contains(&building_type)
+    }
+
+    pub fn allows_ability(&self, ability: Abilities) -> bool {
+        !self.abilities.contains(&ability)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buildings.is_empty() && self.abilities.is_empty()
+    }
+}
+
+impl std::fmt::Display for Restrictions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return f.write_str("No restrictions");
+        }
+        let names: Vec<String> = self
+            .buildings
+            .iter()
+            .map(|building_type| format!("No {:?}", building_type))
+            .chain(
+                self.abilities
+                    .iter()
+                    .map(|ability| format!("No {:?}", ability)),
+            )
+            .collect();
+        f.write_str(&names.join(", "))
+    }
+}
+
+/// Rates a won campaign game and keeps the stars in the campaign progress
+pub fn record_campaign_result(
+    game: Res<Game>,
+    game_ended: Res<GameEnded>,
+    game_settings: Res<GameBuildSettings>,
+    campaign_handle: Res<CampaignHandle>,
+    campaign_assets: Res<Assets<Campaign>>,
+    mut campaign_progress: ResMut<CampaignProgress>,
+) {
+    campaign_progress.last_result = None;
+    let Some(mission_index) = game_settings.campaign_mission else {
+        return;
+    };
+    if !game_ended.player_won {
+        return;
+    }
+    let Some(mission) = campaign_assets
+        .get(&campaign_handle.campaign)
+        .and_then(|campaign| campaign.missions.get(mission_index))
+    else {
+        return;
+    };
+
+    let seconds = game.game_world.resource::<Time>().elapsed_seconds();
+    campaign_progress.record(mission_index, mission.stars_for(seconds));
+}
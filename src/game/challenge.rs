@@ -0,0 +1,342 @@
+//! Seeded challenges. A challenge fixes the map, the enemies, the difficulty and the seed of the
+//! [`GameRng`](crate::game::game_rng::GameRng) so it can be shared as a short seed code and
+//! replayed. The daily challenge derives all of it from the date
+
+use crate::game::game_difficulty::GameDifficulty;
+use crate::game::GameBuildSettings;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::RangeInclusive;
+
+/// Crockford's base 32. It leaves out the letters that are easily mixed up with digits
+const CODE_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// The characters in a seed code, not counting the dashes between groups
+const CODE_LENGTH: usize = 12;
+
+const CODE_GROUP_LENGTH: usize = 4;
+
+/// Bits of a code that hold the challenge. The rest of the code is a checksum to catch typos
+const PAYLOAD_BITS: u32 = 50;
+
+/// Mixed into the day so the daily challenges don't line up with codes that use small seeds
+const DAILY_SALT: u64 = 0x636c_7273_6461_696c;
+
+const DAILY_MAP_SIZES: RangeInclusive<u32> = 30..=60;
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+const DIFFICULTIES: [GameDifficulty; 3] = [
+    GameDifficulty::Easy,
+    GameDifficulty::Medium,
+    GameDifficulty::Hard,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Challenge {
+    pub seed: u32,
+    pub map_type: usize,
+    /// Only used by random maps, levels always have their own size
+    pub map_size: u32,
+    pub enemy_count: usize,
+    pub game_difficulty: GameDifficulty,
+}
+
+impl Challenge {
+    /// The challenge for the given day. Everyone playing on the same day gets the same challenge
+    pub fn daily(day: u64, game_build_settings: &GameBuildSettings) -> Challenge {
+        let mut rng = StdRng::seed_from_u64(day ^ DAILY_SALT);
+        // The random map is always playable so there is at least one map to pick from
+        let maps: Vec<usize> = (0..game_build_settings.max_map.max(1))
+            .filter(|map_type| game_build_settings.is_playable_map(*map_type))
+            .collect();
+        let map_type = maps[rng.gen_range(0..maps.len())];
+        let map_size = match game_build_settings.level_sizes.lists.get(&map_type) {
+            Some((size, _)) => *size,
+            None => rng.gen_range(DAILY_MAP_SIZES),
+        };
+        let enemy_count = rng.gen_range(1..=game_build_settings.max_enemy_count_on(map_type));
+        let game_difficulty = DIFFICULTIES[rng.gen_range(0..DIFFICULTIES.len())].clone();
+        Challenge {
+            seed: rng.gen(),
+            map_type,
+            map_size,
+            enemy_count,
+            game_difficulty,
+        }
+    }
+
+    /// The challenge the settings are set up for, if they are for one
+    pub fn from_settings(game_build_settings: &GameBuildSettings) -> Option<Challenge> {
+        Some(Challenge {
+            seed: game_build_settings.seed?,
+            map_type: game_build_settings.map_type,
+            map_size: game_build_settings.map_size,
+            enemy_count: game_build_settings.enemy_count,
+            game_difficulty: game_build_settings.game_difficulty.clone(),
+        })
+    }
+
+    /// Makes sure the challenge can be played with the maps of this version of the game
+    pub fn check(&self, game_build_settings: &GameBuildSettings) -> Result<(), String> {
+        if self.map_type >= game_build_settings.max_map {
+            return Err(String::from(
+                "This seed code uses a map that isn't in this version of the game",
+            ));
+        }
+        if self.map_type == 0 && !(30..=100).contains(&self.map_size) {
+            return Err(format!(
+                "A {0}x{0} map is too big or too small",
+                self.map_size
+            ));
+        }
+        if !game_build_settings.is_playable_map(self.map_type) {
+            return Err(String::from(
+                "This seed code uses a map without room for any enemies",
+            ));
+        }
+        let max_enemy_count = game_build_settings.max_enemy_count_on(self.map_type);
+        if !(1..=max_enemy_count).contains(&self.enemy_count) {
+            return Err(format!(
+                "This map only has room for up to {} enemies",
+                max_enemy_count
+            ));
+        }
+        Ok(())
+    }
+
+    /// The shareable seed code, for example `1A2B-3C4D-5E6F`
+    pub fn code(&self) -> String {
+        let difficulty = DIFFICULTIES
+            .iter()
+            .position(|difficulty| *difficulty == self.game_difficulty)
+            .unwrap_or(0) as u64;
+        let payload = self.seed as u64
+            | (self.map_type as u64 & 0x3f) << 32
+            | (self.map_size as u64 & 0x7f) << 38
+            | (self.enemy_count as u64 & 0x7) << 45
+            | difficulty << 48;
+        let value = payload | code_checksum(payload) << PAYLOAD_BITS;
+
+        let characters: Vec<char> = (0..CODE_LENGTH)
+            .rev()
+            .map(|index| CODE_ALPHABET[(value >> (index * 5)) as usize & 0x1f] as char)
+            .collect();
+        characters
+            .chunks(CODE_GROUP_LENGTH)
+            .map(|group| group.iter().collect::<String>())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    /// Reads a seed code. Dashes, spaces and case are ignored and letters that look like digits
+    /// are read as those digits
+    pub fn from_code(code: &str) -> Result<Challenge, String> {
+        let mut value: u64 = 0;
+        let mut length = 0;
+        for character in code
+            .chars()
+            .filter(|character| *character != '-' && !character.is_whitespace())
+        {
+            let character = match character.to_ascii_uppercase() {
+                'O' => '0',
+                'I' | 'L' => '1',
+                character => character,
+            };
+            let Some(digit) = CODE_ALPHABET
+                .iter()
+                .position(|code_character| *code_character as char == character)
+            else {
+                return Err(format!("'{}' can't be part of a seed code", character));
+            };
+            length += 1;
+            if length > CODE_LENGTH {
+                break;
+            }
+            value = value << 5 | digit as u64;
+        }
+        if length != CODE_LENGTH {
+            return Err(format!("Seed codes are {} characters long", CODE_LENGTH));
+        }
+
+        let payload = value & ((1 << PAYLOAD_BITS) - 1);
+        if value >> PAYLOAD_BITS != code_checksum(payload) {
+            return Err(String::from("That seed code has a typo in it"));
+        }
+        let Some(game_difficulty) = DIFFICULTIES.get((payload >> 48) as usize).cloned() else {
+            return Err(String::from("That seed code has a typo in it"));
+        };
+        Ok(Challenge {
+            seed: payload as u32,
+            map_type: (payload >> 32 & 0x3f) as usize,
+            map_size: (payload >> 38 & 0x7f) as u32,
+            enemy_count: (payload >> 45 & 0x7) as usize,
+            game_difficulty,
+        })
+    }
+}
+
+fn code_checksum(payload: u64) -> u64 {
+    payload.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> (64 - (CODE_LENGTH as u32 * 5 - PAYLOAD_BITS))
+}
+
+/// Days since 1970-01-01 in UTC, so the daily challenge changes at the same time everywhere
+#[cfg(not(target_arch = "wasm32"))]
+pub fn current_day() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs() / SECONDS_PER_DAY)
+}
+
+/// Days since 1970-01-01 in UTC, so the daily challenge changes at the same time everywhere
+#[cfg(target_arch = "wasm32")]
+pub fn current_day() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64 / SECONDS_PER_DAY
+}
+
+/// Formats a day from [`current_day`] as a year-month-day date
+pub fn format_day(day: u64) -> String {
+    // Howard Hinnant's civil_from_days, shifted so the year starts in March
+    let days = day + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day_of_month = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = match month_index < 10 {
+        true => month_index + 3,
+        false => month_index - 9,
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+    format!("{}-{:02}-{:02}", year, month, day_of_month)
+}
+
+/// The seed code in the `seed` query parameter of the page, for links like `?seed=1A2B-3C4D-5E6F`
+#[cfg(target_arch = "wasm32")]
+pub fn url_seed_code() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get("seed")
+}
+
+/// Only the web build has a page url to read a seed code from
+#[cfg(not(target_arch = "wasm32"))]
+pub fn url_seed_code() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(seed: u32, game_difficulty: GameDifficulty) -> Challenge {
+        Challenge {
+            seed,
+            map_type: 5,
+            map_size: 45,
+            enemy_count: 7,
+            game_difficulty,
+        }
+    }
+
+    fn challenge_with_seed_one() -> Challenge {
+        Challenge {
+            seed: 1,
+            map_type: 0,
+            map_size: 40,
+            enemy_count: 2,
+            game_difficulty: GameDifficulty::Medium,
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        let challenges = [
+            challenge(4_000_000_000, GameDifficulty::Hard),
+            challenge(0, GameDifficulty::Easy),
+            challenge(1, GameDifficulty::Medium),
+            Challenge {
+                seed: u32::MAX,
+                map_type: 63,
+                map_size: 127,
+                enemy_count: 7,
+                game_difficulty: GameDifficulty::Hard,
+            },
+        ];
+        for challenge in challenges {
+            assert_eq!(Challenge::from_code(&challenge.code()), Ok(challenge));
+        }
+        assert_eq!(
+            challenge(4_000_000_000, GameDifficulty::Hard).code(),
+            "MWQB-8QQ6-PA00"
+        );
+    }
+
+    #[test]
+    fn typos_are_rejected() {
+        let code = "MWQB-8QQ6-PA00";
+        for (index, character) in code.char_indices() {
+            if character == '-' {
+                continue;
+            }
+            for typo in CODE_ALPHABET.iter().map(|typo| *typo as char) {
+                if typo == character {
+                    continue;
+                }
+                let mut typo_code = String::from(code);
+                typo_code.replace_range(index..index + 1, &typo.to_string());
+                assert_eq!(
+                    Challenge::from_code(&typo_code),
+                    Err(String::from("That seed code has a typo in it")),
+                    "{} was accepted",
+                    typo_code
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn look_alike_letters_are_read_as_digits() {
+        let expected = Challenge::from_code("M8AA-0000-0001");
+        assert_eq!(expected, Ok(challenge_with_seed_one()));
+        assert_eq!(Challenge::from_code("m8aa-oooo-OOOl"), expected);
+        assert_eq!(Challenge::from_code("M8AA OOOO 000I"), expected);
+        assert_eq!(Challenge::from_code("m8aaoooo000i"), expected);
+    }
+
+    #[test]
+    fn codes_of_the_wrong_length_are_rejected() {
+        let length_error = Err(String::from("Seed codes are 12 characters long"));
+        assert_eq!(Challenge::from_code(""), length_error);
+        assert_eq!(Challenge::from_code("MWQB-8QQ6-PA0"), length_error);
+        assert_eq!(Challenge::from_code("MWQB-8QQ6-PA00-0"), length_error);
+    }
+
+    #[test]
+    fn unknown_characters_are_rejected() {
+        assert_eq!(
+            Challenge::from_code("MWQB-8QQ6-PAU0"),
+            Err(String::from("'U' can't be part of a seed code"))
+        );
+    }
+
+    #[test]
+    fn unknown_difficulties_are_rejected() {
+        // Has a valid checksum but difficulty index 3
+        assert_eq!(
+            Challenge::from_code("4BTA-0400-016J"),
+            Err(String::from("That seed code has a typo in it"))
+        );
+    }
+
+    #[test]
+    fn days_are_formatted_as_dates() {
+        assert_eq!(format_day(0), "1970-01-01");
+        assert_eq!(format_day(11_016), "2000-02-29");
+        assert_eq!(format_day(19_782), "2024-02-29");
+        assert_eq!(format_day(20_744), "2026-10-18");
+    }
+}
@@ -1,5 +1,8 @@
 use crate::audio::UiSoundEvents;
-use crate::game::GameBuildSettings;
+use crate::buildings::check_is_colorable;
+use crate::game::game_mode::GameMode;
+use crate::game::sudden_death::SuddenDeath;
+use crate::game::{GameBuildSettings, GameData};
 use crate::mapping::map::MapTileStorage;
 use crate::mapping::MapTileIndex;
 use crate::objects::{ObjectIndex, TileToObjectIndex};
@@ -13,11 +16,12 @@ use bevy::reflect::Reflect;
 use bevy::utils::HashMap;
 use bevy_ecs_tilemap::tiles::TilePos;
 use bevy_ggf::game_core::Game;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::mapping::tiles::Tile;
 use bevy_ggf::object::Object;
 use bevy_ggf::player::{Player, PlayerMarker};
 
-#[derive(Reflect, Clone, Debug, PartialEq)]
+#[derive(Reflect, Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum GameEndConditions {
     Domination,
     Percentage { target_percentage: f32 },
@@ -73,11 +77,28 @@ pub fn check_game_ended(
     }
 }
 
+/// The amount of tiles that percentages of the board are measured against. With sudden death only
+/// the tiles that are still in play count
+pub fn in_play_tile_count(
+    game_settings: &GameBuildSettings,
+    terrain: &Query<&TileTerrainInfo, With<Tile>>,
+) -> u32 {
+    match game_settings.sudden_death {
+        SuddenDeath::Off => game_settings.map_size * game_settings.map_size,
+        _ => terrain
+            .iter()
+            .filter(|tile_terrain_info| check_is_colorable(tile_terrain_info))
+            .count() as u32,
+    }
+}
+
 // This system runs in the game world to see if someone won
 pub fn update_game_end_state(
     tiles: Query<&PlayerMarker, With<Tile>>,
+    terrain: Query<&TileTerrainInfo, With<Tile>>,
     mut commands: Commands,
     game_settings: Res<GameBuildSettings>,
+    game_data: Res<GameData>,
 ) {
     let mut player_tiles: HashMap<usize, u32> = HashMap::new();
     for player_marker in tiles.iter() {
@@ -86,6 +107,11 @@ pub fn update_game_end_state(
         player_tiles.insert(player_marker.id(), count.saturating_add(1));
     }
 
+    // The neutral faction can't win and doesn't have to be beaten
+    if let Some(neutral_player) = game_data.neutral_player {
+        player_tiles.remove(&neutral_player);
+    }
+
     // no matter what conditions we are in if the player is dead we just lose the game
     if !player_tiles.contains_key(&0) {
         // ai has won
@@ -102,6 +128,30 @@ pub fn update_game_end_state(
         });
     }
 
+    // Puzzles are decided by their target and time limit in update_puzzle
+    if game_settings.puzzle.is_some() {
+        return;
+    }
+
+    // Survival can't be won. It only ends once the player is beaten or an ai takes the board
+    if game_settings.game_mode == GameMode::Survival {
+        let tile_count = in_play_tile_count(&game_settings, &terrain);
+        let target_percentage = match game_settings.game_end_conditions {
+            GameEndConditions::Domination => 1.0,
+            GameEndConditions::Percentage { target_percentage } => target_percentage,
+        };
+        for (id, count) in player_tiles.iter() {
+            if *id != 0 && *count as f32 / tile_count as f32 >= target_percentage {
+                commands.insert_resource(GameEnded {
+                    player_won: false,
+                    winning_id: *id,
+                });
+                return;
+            }
+        }
+        return;
+    }
+
     match game_settings.game_end_conditions {
         GameEndConditions::Domination => {
             player_tiles.remove(&0);
@@ -116,7 +166,7 @@ pub fn update_game_end_state(
             }
         }
         GameEndConditions::Percentage { target_percentage } => {
-            let tile_count = game_settings.map_size * game_settings.map_size;
+            let tile_count = in_play_tile_count(&game_settings, &terrain);
             for (id, count) in player_tiles.iter() {
                 if *count as f32 / tile_count as f32 >= target_percentage {
                     let player_won = *id == 0;
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use bevy::reflect::Reflect;
 
-#[derive(Reflect, Clone, Debug, PartialEq)]
+#[derive(Reflect, Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
 pub enum GameDifficulty {
     Easy,
     Medium,
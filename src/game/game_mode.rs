@@ -0,0 +1,37 @@
+use std::fmt::Display;
+
+use bevy::reflect::{FromReflect, Reflect};
+
+#[derive(Default, Reflect, FromReflect, Clone, Copy, Eq, Debug, PartialEq)]
+pub enum GameMode {
+    /// Every player starts at once and the first to reach the game end condition wins
+    #[default]
+    FreeForAll,
+    /// The player starts alone while ever stronger ai factions spawn in waves
+    Survival,
+}
+
+impl Display for GameMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameMode::FreeForAll => f.write_str("Free-for-all"),
+            GameMode::Survival => f.write_str("Survival"),
+        }
+    }
+}
+
+impl GameMode {
+    pub fn next(&self) -> GameMode {
+        match self {
+            GameMode::FreeForAll => GameMode::Survival,
+            GameMode::Survival => GameMode::Survival,
+        }
+    }
+
+    pub fn prev(&self) -> GameMode {
+        match self {
+            GameMode::FreeForAll => GameMode::FreeForAll,
+            GameMode::Survival => GameMode::FreeForAll,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! The randomness of the simulation. Every random roll in the game world goes through [`GameRng`]
+//! so games started from the same seed roll the same numbers
+
+use bevy::prelude::{Deref, DerefMut, Resource};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn from_seed(seed: u32) -> GameRng {
+        GameRng(StdRng::seed_from_u64(seed as u64))
+    }
+
+    pub fn from_entropy() -> GameRng {
+        GameRng(StdRng::from_entropy())
+    }
+}
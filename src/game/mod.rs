@@ -1,35 +1,76 @@
+pub mod campaign;
+pub mod challenge;
 pub mod end_game;
 pub mod game_difficulty;
+pub mod game_mode;
+pub mod game_rng;
+pub mod puzzle;
 pub mod restart_game;
 pub mod state;
+pub mod sudden_death;
+pub mod survival;
 
 use crate::abilities::expand::{simulate_expand_from_cache, Expand};
 use crate::abilities::fortify::{simulate_fortify_from_cache, Fortify};
+use crate::abilities::freeze::{simulate_freeze, Freeze};
 use crate::abilities::nuke::{simulate_nuke_from_cache, Nuke};
+use crate::abilities::shield::{simulate_shield, Shield};
+use crate::abilities::swap::{simulate_swap_from_cache, Swap};
 use crate::abilities::{destroy_abilities, update_ability_timers, Ability};
 use crate::actions::Actions;
-use crate::ai::{run_ai_ability, run_ai_building};
+use crate::ai::{
+    run_ai_ability, run_ai_artillery, run_ai_building, run_ai_painters, run_ai_relocate,
+    run_ai_upgrade,
+};
+use crate::buildings::artillery::{simulate_artillery, Artillery};
 use crate::buildings::building_pathfinding::{SimplePathfindMap, SimplePathfinder};
+use crate::buildings::harvester::{simulate_harvesters_from_cache, Harvester};
+use crate::buildings::health::{BuildingHealth, BuildingLossRule};
 use crate::buildings::line::{simulate_lines_from_cache, Line, LinePathfindMap};
+use crate::buildings::modifiers::{update_building_modifiers, BuildingModifierSource};
+use crate::buildings::objective::{simulate_objectives, Objective};
 use crate::buildings::pulser::{simulate_pulsers_from_cache, Pulser};
+use crate::buildings::relay::Relay;
 use crate::buildings::scatter::{simulate_scatter_from_cache, Scatter};
+use crate::buildings::upgrades::BuildingTier;
+use crate::buildings::wall::Wall;
+use crate::buildings::workshop::Workshop;
 use crate::buildings::{
     destroy_buildings, update_building_timers, Activate, Building, BuildingCooldown,
-    BuildingMarker, Simulate,
+    BuildingMarker, BuildingPointsSpent, Simulate,
 };
 use crate::color_system::{
     handle_color_conflict_guarantees, handle_color_conflicts, update_color_conflicts,
     ColorConflictEvent, ColorConflictGuarantees, ColorConflicts, PlayerTileChangedCount, TileColor,
+    TileStrengthRules,
 };
+use crate::game::campaign::{record_campaign_result, Restrictions};
+use crate::game::challenge::Challenge;
 use crate::game::end_game::{check_game_ended, cleanup_game, update_game_end_state};
+use crate::game::game_rng::GameRng;
+use crate::game::puzzle::{update_puzzle, PuzzleState};
 use crate::game::state::update_main_world_game_state;
+use crate::game::sudden_death::{update_sudden_death, SuddenDeath, SuddenDeathState};
+use crate::game::survival::{
+    record_survival_score, spawn_survival_waves, update_survival_score, SurvivalState,
+    DEFAULT_SURVIVAL_HOLD_PERCENT, MAX_SURVIVAL_HOLD_PERCENT, MIN_SURVIVAL_HOLD_PERCENT,
+};
 use crate::level_loader::{LevelHandle, Levels};
-use crate::mapping::map::MapCommandsExt;
+use crate::loading::campaign_loader::Mission;
+use crate::loading::puzzle_loader::{Puzzle, PuzzleHandle, Puzzles};
+use crate::mapping::map::{random_map_spawn_points, MapCommandsExt};
 use crate::objects::{
     delete_pathfind_object_from_tile_index_cache, simulate_simple_pathfind_object_cache,
     update_objects_index, ObjectIndex, TileToObjectIndex,
 };
 use crate::player::{update_player_points, PlayerPoints};
+use crate::power_ups::{
+    claim_power_ups, spawn_power_ups, update_overclocks, Overclock, PowerUp, PowerUpSpawner,
+    POWER_UP_REWARDS,
+};
+use crate::territory_decay::{update_territory_supply, TerritorySupply};
+use crate::units::painter::{simulate_painters, Painter};
+use crate::units::{destroy_units, Unit};
 use crate::{GamePausedState, GameState};
 
 use bevy::app::App;
@@ -54,7 +95,12 @@ use bevy_ggf::player::{Player, PlayerMarker};
 
 use self::end_game::GameEndConditions;
 use self::game_difficulty::GameDifficulty;
+use self::game_mode::GameMode;
 use self::restart_game::RestartGamePlugin;
+use std::time::Duration;
+
+/// The most players, including the human player, that a single game supports
+pub const MAX_PLAYER_COUNT: usize = 8;
 
 pub struct GameCorePlugin;
 
@@ -64,6 +110,8 @@ impl Plugin for GameCorePlugin {
         app.init_resource_after_loading_state::<_, GameBuildSettings>(GameState::Loading);
         app.add_system(start_game.in_schedule(OnEnter(GameState::Playing)))
             .add_system(cleanup_game.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(record_survival_score.in_schedule(OnEnter(GameState::Ended)))
+            .add_system(record_campaign_result.in_schedule(OnEnter(GameState::Ended)))
             .add_system(
                 check_game_ended
                     .in_base_set(Update)
@@ -72,10 +120,13 @@ impl Plugin for GameCorePlugin {
             .add_system(
                 simulate_game
                     .run_if(
-                        in_state(GameState::Playing).and_then(in_state(GamePausedState::NotPaused)),
+                        in_state(GameState::Playing)
+                            .and_then(in_state(GamePausedState::NotPaused))
+                            .and_then(should_simulate),
                     )
                     .in_schedule(CoreSchedule::FixedUpdate),
             )
+            .add_system(update_simulation_speed.run_if(resource_changed::<GameSpeed>()))
             .add_systems(
                 (update_main_world_game_state, apply_system_buffers)
                     .distributive_run_if(in_state(GameState::Playing))
@@ -83,7 +134,8 @@ impl Plugin for GameCorePlugin {
                     .in_schedule(CoreSchedule::FixedUpdate),
             );
 
-        app.insert_resource(FixedTime::new_from_secs(0.03));
+        app.insert_resource(FixedTime::new_from_secs(SIMULATION_TICK_SECONDS));
+        app.init_resource::<GameSpeed>();
 
         app.register_type::<GameBuildSettings>();
     }
@@ -94,12 +146,91 @@ pub fn setup_game_resource(mut world: &mut World) {
     world.insert_resource(game_build_settings);
 }
 
+/// How much game time a single simulation tick covers, regardless of the [`GameSpeed`]
+pub const SIMULATION_TICK_SECONDS: f32 = 0.03;
+
+const GAME_SPEEDS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+
+/// How fast the simulation runs. Every tick advances the game world by exactly
+/// [`SIMULATION_TICK_SECONDS`] so the speed only changes how often ticks happen and never what
+/// happens in them
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct GameSpeed {
+    speed_index: usize,
+    /// Stops the simulation without opening the pause menu. Single ticks can be stepped while held
+    pub held: bool,
+}
+
+impl Default for GameSpeed {
+    fn default() -> Self {
+        GameSpeed {
+            speed_index: 1,
+            held: false,
+        }
+    }
+}
+
+impl std::fmt::Display for GameSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x", self.multiplier())
+    }
+}
+
+impl GameSpeed {
+    pub fn multiplier(&self) -> f32 {
+        GAME_SPEEDS[self.speed_index]
+    }
+
+    pub fn can_slow_down(&self) -> bool {
+        self.speed_index > 0
+    }
+
+    pub fn can_speed_up(&self) -> bool {
+        self.speed_index < GAME_SPEEDS.len() - 1
+    }
+
+    pub fn slow_down(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    pub fn speed_up(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(GAME_SPEEDS.len() - 1);
+    }
+
+    pub fn toggle_held(&mut self) {
+        self.held = !self.held;
+    }
+
+    /// The real time between simulation ticks at this speed
+    pub fn tick_period(&self) -> Duration {
+        Duration::from_secs_f32(SIMULATION_TICK_SECONDS / self.multiplier())
+    }
+}
+
+/// Inserted to run a single simulation tick while the [`GameSpeed`] is held. Removed once the tick
+/// has run
+#[derive(Default, Resource)]
+pub struct StepGame;
+
+fn should_simulate(game_speed: Res<GameSpeed>, step_game: Option<Res<StepGame>>) -> bool {
+    !game_speed.held || step_game.is_some()
+}
+
+fn update_simulation_speed(game_speed: Res<GameSpeed>, mut fixed_time: ResMut<FixedTime>) {
+    fixed_time.period = game_speed.tick_period();
+}
+
 pub fn simulate_game(world: &mut World) {
+    world.remove_resource::<StepGame>();
     world.resource_scope(|world, mut game: Mut<Game>| {
         world.resource_scope(|world, mut game_runtime: Mut<GameRuntime<TestRunner>>| {
+            // Advance the game worlds time by a fixed tick rather than real time so that cooldowns
+            // and timers in the game world run the same at every game speed
             game.game_world
                 .resource_scope(|_world, mut time: Mut<Time>| {
-                    time.update();
+                    let next_update = time.last_update().unwrap_or(time.startup())
+                        + Duration::from_secs_f32(SIMULATION_TICK_SECONDS);
+                    time.update_with_instant(next_update);
                 });
             game_runtime.simulate(&mut game.game_world);
             world.resource_scope(|_world, mut game_commands: Mut<GameCommands>| {
@@ -115,14 +246,36 @@ pub struct GameBuildSettings {
     pub enemy_count: usize,
     pub map_type: usize,
     pub max_map: usize,
+    pub game_mode: GameMode,
     pub level_sizes: LevelsSizes,
     pub game_end_conditions: GameEndConditions,
     pub game_difficulty: GameDifficulty,
+    /// Percentage of the points spent on a building that are refunded when it is demolished
+    pub demolish_refund_percent: u32,
+    pub building_loss_rule: BuildingLossRule,
+    pub tile_strength_rules: TileStrengthRules,
+    /// Whether owned tiles that are cut off from every building of their owner decay
+    pub territory_decay: bool,
+    /// Whether pickups spawn on neutral and contested tiles
+    pub power_ups: bool,
+    pub sudden_death: SuddenDeath,
+    /// The share of the board in percent the player has to hold for the survival score to count up
+    pub survival_hold_percent: u32,
+    /// The campaign mission being played, if any
+    pub campaign_mission: Option<usize>,
+    /// The puzzle being played, if any
+    pub puzzle: Option<usize>,
+    pub restrictions: Restrictions,
+    /// The seed of the challenge being played, if any. Every other game rolls a fresh seed
+    pub seed: Option<u32>,
 }
 
 #[derive(Reflect, Clone, Eq, Debug, PartialEq)]
 pub struct LevelsSizes {
     pub lists: HashMap<usize, (u32, u32)>,
+    /// How many spawn points each level has. Random maps aren't in here as they can place any
+    /// number of players
+    pub spawn_counts: HashMap<usize, usize>,
 }
 
 impl GameBuildSettings {
@@ -135,9 +288,122 @@ impl GameBuildSettings {
 
     pub fn increase_enemy_count(&mut self) {
         self.enemy_count = self.enemy_count.saturating_add(1);
-        if self.enemy_count > 3 {
-            self.enemy_count = 3
+        if self.enemy_count > self.max_enemy_count() {
+            self.enemy_count = self.max_enemy_count()
+        }
+    }
+
+    /// How many enemies are on the map when the game starts. Survival starts with the player alone
+    pub fn starting_enemy_count(&self) -> usize {
+        match self.game_mode {
+            GameMode::FreeForAll => self.enemy_count,
+            GameMode::Survival => 0,
+        }
+    }
+
+    /// How many ai players the game has. Survival keeps every slot free for its waves
+    pub fn ai_player_count(&self) -> usize {
+        match self.game_mode {
+            GameMode::FreeForAll => self.enemy_count,
+            GameMode::Survival => MAX_PLAYER_COUNT - 1,
+        }
+    }
+
+    /// Applies a campaign mission. Every setting the mission doesn't fix goes back to its default
+    pub fn start_mission(&mut self, mission_index: usize, mission: &Mission, map_type: usize) {
+        self.game_mode = GameMode::FreeForAll;
+        self.map_type = map_type;
+        self.map_size = match self.level_sizes.lists.get(&map_type) {
+            Some((size, _)) => *size,
+            None => mission.map_size.unwrap_or(30),
+        };
+        self.enemy_count = mission.enemy_count.min(self.max_enemy_count()).max(1);
+        self.game_difficulty = mission.difficulty.clone();
+        self.game_end_conditions = mission.end_conditions.clone();
+        self.building_loss_rule = BuildingLossRule::Destroy;
+        self.tile_strength_rules = TileStrengthRules::default();
+        self.territory_decay = false;
+        self.power_ups = false;
+        self.sudden_death = SuddenDeath::Off;
+        self.restrictions = mission.restrictions.clone();
+        self.campaign_mission = Some(mission_index);
+        self.puzzle = None;
+        self.seed = None;
+    }
+
+    /// Applies a puzzle. The board comes from the puzzle so only the rules that aren't part of it
+    /// are reset
+    pub fn start_puzzle(&mut self, puzzle_index: usize, puzzle: &Puzzle) {
+        self.game_mode = GameMode::FreeForAll;
+        self.map_size = puzzle.width().max(puzzle.height());
+        self.enemy_count = puzzle.enemy_count();
+        self.building_loss_rule = BuildingLossRule::Destroy;
+        self.tile_strength_rules = TileStrengthRules::default();
+        self.territory_decay = false;
+        self.power_ups = false;
+        self.sudden_death = SuddenDeath::Off;
+        self.restrictions = puzzle.inventory.restrictions();
+        self.campaign_mission = None;
+        self.puzzle = Some(puzzle_index);
+        self.seed = None;
+    }
+
+    /// Applies a seeded challenge. Like a mission, every setting the challenge doesn't fix goes back
+    /// to its default so the same code always plays the same game
+    pub fn start_challenge(&mut self, challenge: &Challenge) {
+        self.game_mode = GameMode::FreeForAll;
+        self.map_type = challenge.map_type;
+        self.map_size = match self.level_sizes.lists.get(&challenge.map_type) {
+            Some((size, _)) => *size,
+            None => challenge.map_size,
+        };
+        self.enemy_count = challenge.enemy_count;
+        self.game_difficulty = challenge.game_difficulty.clone();
+        self.game_end_conditions = GameEndConditions::Percentage {
+            target_percentage: 0.8,
+        };
+        self.building_loss_rule = BuildingLossRule::Destroy;
+        self.tile_strength_rules = TileStrengthRules::default();
+        self.territory_decay = false;
+        self.power_ups = false;
+        self.sudden_death = SuddenDeath::Off;
+        self.restrictions = Restrictions::default();
+        self.campaign_mission = None;
+        self.puzzle = None;
+        self.seed = Some(challenge.seed);
+    }
+
+    /// Drops the settings of the last campaign mission, puzzle or challenge so a regular game can be
+    /// started
+    pub fn leave_scenario(&mut self) {
+        self.seed = None;
+        if self.campaign_mission.take().is_none() && self.puzzle.take().is_none() {
+            return;
         }
+        self.restrictions = Restrictions::default();
+        self.game_end_conditions = GameEndConditions::Percentage {
+            target_percentage: 0.8,
+        };
+        // A puzzle can leave more enemies behind than the selected map has room for
+        self.enemy_count = self.enemy_count.min(self.max_enemy_count()).max(1);
+    }
+
+    /// The most enemies the currently selected map can hold
+    pub fn max_enemy_count(&self) -> usize {
+        self.max_enemy_count_on(self.map_type)
+    }
+
+    /// The most enemies the map can hold. Levels with less than two spawn points can't hold any
+    pub fn max_enemy_count_on(&self, map_type: usize) -> usize {
+        match self.level_sizes.spawn_counts.get(&map_type) {
+            Some(spawn_count) => spawn_count.saturating_sub(1).min(MAX_PLAYER_COUNT - 1),
+            None => MAX_PLAYER_COUNT - 1,
+        }
+    }
+
+    /// Whether the map has room for the player and at least one enemy
+    pub fn is_playable_map(&self, map_type: usize) -> bool {
+        self.max_enemy_count_on(map_type) > 0
     }
 
     pub fn increase_map_size(&mut self, modifier: bool) {
@@ -169,23 +435,44 @@ impl GameBuildSettings {
         }
     }
 
-    pub fn next_map(&mut self) {
-        self.map_type = self.map_type.saturating_add(1);
+    pub fn increase_survival_hold_percent(&mut self) {
+        self.survival_hold_percent =
+            (self.survival_hold_percent + 5).min(MAX_SURVIVAL_HOLD_PERCENT);
+    }
+
+    pub fn decrease_survival_hold_percent(&mut self) {
+        self.survival_hold_percent = self
+            .survival_hold_percent
+            .saturating_sub(5)
+            .max(MIN_SURVIVAL_HOLD_PERCENT);
+    }
 
-        if self.map_type > self.max_map - 1 {
-            self.map_type = self.max_map - 1;
+    /// Selects the next map. Levels without room for an enemy are skipped
+    pub fn next_map(&mut self) {
+        if let Some(map_type) =
+            (self.map_type + 1..self.max_map).find(|map_type| self.is_playable_map(*map_type))
+        {
+            self.map_type = map_type;
         }
 
         if self.map_type > 0 {
             self.map_size = self.level_sizes.lists[&self.map_type].0;
         }
+        self.enemy_count = self.enemy_count.min(self.max_enemy_count());
     }
 
+    /// Selects the previous map. Levels without room for an enemy are skipped
     pub fn prev_map(&mut self) {
-        self.map_type = self.map_type.saturating_sub(1);
+        if let Some(map_type) = (0..self.map_type)
+            .rev()
+            .find(|map_type| self.is_playable_map(*map_type))
+        {
+            self.map_type = map_type;
+        }
         if self.map_type > 0 {
             self.map_size = self.level_sizes.lists[&self.map_type].0;
         }
+        self.enemy_count = self.enemy_count.min(self.max_enemy_count());
     }
 }
 
@@ -195,6 +482,7 @@ impl FromWorld for GameBuildSettings {
             world.resource_scope(|_world, assets: Mut<Assets<Levels>>| {
                 let mut levels_sizes = LevelsSizes {
                     lists: Default::default(),
+                    spawn_counts: Default::default(),
                 };
 
                 for (i, level) in assets.get(&maps.levels).unwrap().levels.iter().enumerate() {
@@ -202,6 +490,9 @@ impl FromWorld for GameBuildSettings {
                         levels_sizes
                             .lists
                             .insert(i, (level.tiles[0].len() as u32, level.tiles.len() as u32));
+                        levels_sizes
+                            .spawn_counts
+                            .insert(i, level.spawn_points.len());
                     }
                 }
 
@@ -210,11 +501,23 @@ impl FromWorld for GameBuildSettings {
                     enemy_count: 1,
                     map_type: 0,
                     max_map: assets.get(&maps.levels).unwrap().levels.len(),
+                    game_mode: GameMode::FreeForAll,
                     level_sizes: levels_sizes,
                     game_end_conditions: GameEndConditions::Percentage {
                         target_percentage: 0.8,
                     },
                     game_difficulty: GameDifficulty::Medium,
+                    demolish_refund_percent: 50,
+                    building_loss_rule: BuildingLossRule::Destroy,
+                    tile_strength_rules: TileStrengthRules::default(),
+                    territory_decay: false,
+                    power_ups: false,
+                    sudden_death: SuddenDeath::Off,
+                    survival_hold_percent: DEFAULT_SURVIVAL_HOLD_PERCENT,
+                    campaign_mission: None,
+                    puzzle: None,
+                    restrictions: Restrictions::default(),
+                    seed: None,
                 };
             })
         })
@@ -231,6 +534,28 @@ pub struct GameData {
     pub terrain_classes: HashMap<String, TerrainClass>,
     pub terrain_types: HashMap<String, TerrainType>,
     pub stacking_classes: HashMap<String, StackingClass>,
+    /// The id of the extra player that owns the neutral structures of a level, if it has any
+    pub neutral_player: Option<usize>,
+}
+
+/// The stack rules of noncolorable tiles. Nothing of any stacking class fits on them
+pub fn noncolorable_tile_stack_rules(game_data: &GameData) -> TileObjectStacks {
+    let mut stacking_classes: Vec<&StackingClass> = game_data.stacking_classes.values().collect();
+    stacking_classes.sort_by(|a, b| a.name.cmp(&b.name));
+    TileObjectStacks::new(
+        stacking_classes
+            .into_iter()
+            .map(|stacking_class| {
+                (
+                    stacking_class.clone(),
+                    TileObjectStacksCount {
+                        current_count: 0,
+                        max_count: 0,
+                    },
+                )
+            })
+            .collect(),
+    )
 }
 
 pub fn start_game(world: &mut World) {
@@ -238,6 +563,7 @@ pub fn start_game(world: &mut World) {
     let Some(game_build_settings) = world.remove_resource::<GameBuildSettings>() else {
         return;
     };
+    world.insert_resource(GameSpeed::default());
 
     let mut game_data = GameData::default();
 
@@ -248,6 +574,15 @@ pub fn start_game(world: &mut World) {
         });
     });
 
+    let puzzle = game_build_settings.puzzle.and_then(|puzzle_index| {
+        let puzzle_handle = world.resource::<PuzzleHandle>();
+        world
+            .resource::<Assets<Puzzles>>()
+            .get(&puzzle_handle.puzzles)
+            .and_then(|puzzles| puzzles.puzzles.get(puzzle_index))
+            .cloned()
+    });
+
     let stacking_class_building: StackingClass = StackingClass {
         name: String::from("Building"),
     };
@@ -256,6 +591,14 @@ pub fn start_game(world: &mut World) {
         name: String::from("Ability"),
     };
 
+    let stacking_class_units: StackingClass = StackingClass {
+        name: String::from("Unit"),
+    };
+
+    let stacking_class_power_ups: StackingClass = StackingClass {
+        name: String::from("PowerUp"),
+    };
+
     game_data.stacking_classes.insert(
         stacking_class_building.name.clone(),
         stacking_class_building.clone(),
@@ -264,6 +607,14 @@ pub fn start_game(world: &mut World) {
         stacking_class_abilities.name.clone(),
         stacking_class_abilities.clone(),
     );
+    game_data.stacking_classes.insert(
+        stacking_class_units.name.clone(),
+        stacking_class_units.clone(),
+    );
+    game_data.stacking_classes.insert(
+        stacking_class_power_ups.name.clone(),
+        stacking_class_power_ups.clone(),
+    );
 
     let terrain_classes: Vec<TerrainClass> = vec![
         TerrainClass {
@@ -289,13 +640,24 @@ pub fn start_game(world: &mut World) {
             name: String::from("BasicNonColorable"),
             terrain_class: terrain_classes[1].clone(),
         },
+        TerrainType {
+            name: String::from("Fertile"),
+            terrain_class: terrain_classes[0].clone(),
+        },
+        TerrainType {
+            name: String::from("Hardened"),
+            terrain_class: terrain_classes[0].clone(),
+        },
+        TerrainType {
+            name: String::from("Conduit"),
+            terrain_class: terrain_classes[0].clone(),
+        },
     ];
-    game_data
-        .terrain_types
-        .insert(terrain_types[0].name.clone(), terrain_types[0].clone());
-    game_data
-        .terrain_types
-        .insert(terrain_types[1].name.clone(), terrain_types[1].clone());
+    for terrain_type in terrain_types.iter() {
+        game_data
+            .terrain_types
+            .insert(terrain_type.name.clone(), terrain_type.clone());
+    }
 
     let object_class_building: ObjectClass = ObjectClass {
         name: String::from("Building"),
@@ -316,6 +678,62 @@ pub fn start_game(world: &mut World) {
         name: String::from("Scatter"),
         object_group: object_group_colorers.clone(),
     };
+    let object_type_artillery: ObjectType = ObjectType {
+        name: String::from("Artillery"),
+        object_group: object_group_colorers.clone(),
+    };
+
+    let object_group_defenders: ObjectGroup = ObjectGroup {
+        name: String::from("Defenders"),
+        object_class: object_class_building.clone(),
+    };
+    let object_type_wall: ObjectType = ObjectType {
+        name: String::from("Wall"),
+        object_group: object_group_defenders.clone(),
+    };
+
+    let object_group_economy: ObjectGroup = ObjectGroup {
+        name: String::from("Economy"),
+        object_class: object_class_building.clone(),
+    };
+    let object_type_harvester: ObjectType = ObjectType {
+        name: String::from("Harvester"),
+        object_group: object_group_economy.clone(),
+    };
+
+    let object_group_support: ObjectGroup = ObjectGroup {
+        name: String::from("Support"),
+        object_class: object_class_building.clone(),
+    };
+    let object_type_relay: ObjectType = ObjectType {
+        name: String::from("Relay"),
+        object_group: object_group_support.clone(),
+    };
+    let object_type_workshop: ObjectType = ObjectType {
+        name: String::from("Workshop"),
+        object_group: object_group_support.clone(),
+    };
+
+    let object_group_objectives: ObjectGroup = ObjectGroup {
+        name: String::from("Objectives"),
+        object_class: object_class_building.clone(),
+    };
+    let object_type_objective: ObjectType = ObjectType {
+        name: String::from("Objective"),
+        object_group: object_group_objectives.clone(),
+    };
+
+    let object_class_unit: ObjectClass = ObjectClass {
+        name: String::from("Unit"),
+    };
+    let object_group_painters: ObjectGroup = ObjectGroup {
+        name: String::from("Painters"),
+        object_class: object_class_unit.clone(),
+    };
+    let object_type_painter: ObjectType = ObjectType {
+        name: String::from("Painter"),
+        object_group: object_group_painters.clone(),
+    };
 
     let object_type_nuke: ObjectType = ObjectType {
         name: String::from("Nuke"),
@@ -329,15 +747,75 @@ pub fn start_game(world: &mut World) {
         name: String::from("Expand"),
         object_group: object_group_colorers.clone(),
     };
+    let object_type_freeze: ObjectType = ObjectType {
+        name: String::from("Freeze"),
+        object_group: object_group_defenders.clone(),
+    };
+    let object_type_swap: ObjectType = ObjectType {
+        name: String::from("Swap"),
+        object_group: object_group_colorers.clone(),
+    };
+    let object_type_shield: ObjectType = ObjectType {
+        name: String::from("Shield"),
+        object_group: object_group_defenders.clone(),
+    };
+
+    let object_class_power_up: ObjectClass = ObjectClass {
+        name: String::from("PowerUp"),
+    };
+    let object_group_power_ups: ObjectGroup = ObjectGroup {
+        name: String::from("PowerUps"),
+        object_class: object_class_power_up.clone(),
+    };
+    for reward in POWER_UP_REWARDS {
+        let object_type_power_up = ObjectType {
+            name: String::from(reward.object_type_name()),
+            object_group: object_group_power_ups.clone(),
+        };
+        game_data
+            .object_types
+            .insert(object_type_power_up.name.clone(), object_type_power_up);
+    }
 
     game_data.object_classes.insert(
         object_class_building.name.clone(),
         object_class_building.clone(),
     );
+    game_data.object_classes.insert(
+        object_class_power_up.name.clone(),
+        object_class_power_up.clone(),
+    );
+    game_data.object_groups.insert(
+        object_group_power_ups.name.clone(),
+        object_group_power_ups.clone(),
+    );
+    game_data
+        .object_classes
+        .insert(object_class_unit.name.clone(), object_class_unit.clone());
     game_data.object_groups.insert(
         object_group_colorers.name.clone(),
         object_group_colorers.clone(),
     );
+    game_data.object_groups.insert(
+        object_group_defenders.name.clone(),
+        object_group_defenders.clone(),
+    );
+    game_data.object_groups.insert(
+        object_group_economy.name.clone(),
+        object_group_economy.clone(),
+    );
+    game_data.object_groups.insert(
+        object_group_support.name.clone(),
+        object_group_support.clone(),
+    );
+    game_data.object_groups.insert(
+        object_group_objectives.name.clone(),
+        object_group_objectives.clone(),
+    );
+    game_data.object_groups.insert(
+        object_group_painters.name.clone(),
+        object_group_painters.clone(),
+    );
     game_data
         .object_types
         .insert(object_type_pulser.name.clone(), object_type_pulser.clone());
@@ -348,6 +826,32 @@ pub fn start_game(world: &mut World) {
         object_type_scatter.name.clone(),
         object_type_scatter.clone(),
     );
+    game_data
+        .object_types
+        .insert(object_type_wall.name.clone(), object_type_wall.clone());
+    game_data.object_types.insert(
+        object_type_harvester.name.clone(),
+        object_type_harvester.clone(),
+    );
+    game_data
+        .object_types
+        .insert(object_type_relay.name.clone(), object_type_relay.clone());
+    game_data.object_types.insert(
+        object_type_artillery.name.clone(),
+        object_type_artillery.clone(),
+    );
+    game_data.object_types.insert(
+        object_type_workshop.name.clone(),
+        object_type_workshop.clone(),
+    );
+    game_data.object_types.insert(
+        object_type_objective.name.clone(),
+        object_type_objective.clone(),
+    );
+    game_data.object_types.insert(
+        object_type_painter.name.clone(),
+        object_type_painter.clone(),
+    );
 
     game_data
         .object_types
@@ -359,6 +863,15 @@ pub fn start_game(world: &mut World) {
     game_data
         .object_types
         .insert(object_type_expand.name.clone(), object_type_expand.clone());
+    game_data
+        .object_types
+        .insert(object_type_freeze.name.clone(), object_type_freeze.clone());
+    game_data
+        .object_types
+        .insert(object_type_swap.name.clone(), object_type_swap.clone());
+    game_data
+        .object_types
+        .insert(object_type_shield.name.clone(), object_type_shield.clone());
 
     let tile_stack_rules = TileObjectStacks::new(vec![
         (
@@ -375,46 +888,73 @@ pub fn start_game(world: &mut World) {
                 max_count: 1,
             },
         ),
-    ]);
-
-    let noncolorable_tile_stack_rules = TileObjectStacks::new(vec![
         (
-            stacking_class_building.clone(),
+            stacking_class_units.clone(),
             TileObjectStacksCount {
                 current_count: 0,
-                max_count: 0,
+                max_count: 1,
             },
         ),
         (
-            stacking_class_abilities.clone(),
+            stacking_class_power_ups.clone(),
             TileObjectStacksCount {
                 current_count: 0,
-                max_count: 0,
+                max_count: 1,
             },
         ),
     ]);
 
-    let tile_movement_costs = vec![(
-        TerrainType {
-            name: String::from("Grassland"),
-            terrain_class: terrain_classes[0].clone(),
-        },
-        TileMovementCosts {
-            movement_type_cost: Default::default(),
-        },
-    )];
+    let noncolorable_tile_stack_rules = noncolorable_tile_stack_rules(&game_data);
+
+    // Terrain without a cost for painters, like the non colorable terrain, can't be walked on.
+    // Hardened tiles are slow going, every other colorable terrain costs a single step
+    let painter_costs = [
+        (&terrain_types[0], 1),
+        (&terrain_types[2], 1),
+        (&terrain_types[3], 2),
+        (&terrain_types[4], 1),
+    ];
+    let tile_movement_costs: Vec<(TerrainType, TileMovementCosts)> = painter_costs
+        .into_iter()
+        .map(|(terrain_type, cost)| {
+            (
+                terrain_type.clone(),
+                TileMovementCosts {
+                    movement_type_cost: [(Painter::movement_type(), cost)].into_iter().collect(),
+                },
+            )
+        })
+        .collect();
 
     let mut game_commands = GameCommands::new();
-    let map_size = TilemapSize {
-        x: game_build_settings.map_size,
-        y: game_build_settings.map_size,
+    let map_size = match &puzzle {
+        Some(puzzle) => TilemapSize {
+            x: puzzle.width(),
+            y: puzzle.height(),
+        },
+        None => TilemapSize {
+            x: game_build_settings.map_size,
+            y: game_build_settings.map_size,
+        },
     };
     game_data.map_size_x = map_size.x;
     game_data.map_size_y = map_size.y;
+    // The neutral faction comes right after the last enemy
+    if puzzle.is_none() && game_build_settings.map_type != 0 && level_data.has_neutral_faction() {
+        game_data.neutral_player = Some(game_build_settings.ai_player_count() + 1);
+    }
 
     let mut commands: Vec<Box<dyn GameCommand>> = vec![];
 
     match game_build_settings.map_type {
+        _ if puzzle.is_some() => {
+            commands.push(Box::new(game_commands.spawn_puzzle(
+                terrain_types,
+                puzzle.clone().unwrap(),
+                tile_stack_rules,
+                noncolorable_tile_stack_rules,
+            )) as Box<dyn GameCommand>);
+        }
         0 => {
             commands.push(Box::new(game_commands.spawn_random_map(
                 map_size,
@@ -422,26 +962,14 @@ pub fn start_game(world: &mut World) {
                 tile_stack_rules,
             )) as Box<dyn GameCommand>);
 
-            let inset_count = game_build_settings.map_size / 4;
+            let spawn_points = random_map_spawn_points(
+                game_build_settings.map_size,
+                game_build_settings.starting_enemy_count() + 1,
+            );
 
-            for player_id in 0..=game_build_settings.enemy_count {
-                let player_spawn_pos = match player_id {
-                    0 => TilePos {
-                        x: inset_count,
-                        y: inset_count,
-                    },
-                    1 => TilePos {
-                        x: game_build_settings.map_size - inset_count,
-                        y: game_build_settings.map_size - inset_count,
-                    },
-                    2 => TilePos {
-                        x: game_build_settings.map_size - inset_count,
-                        y: inset_count,
-                    },
-                    _ => TilePos {
-                        x: inset_count,
-                        y: game_build_settings.map_size - inset_count,
-                    },
+            for player_id in 0..=game_build_settings.starting_enemy_count() {
+                let Some(player_spawn_pos) = spawn_points.get(player_id).copied() else {
+                    continue;
                 };
                 let tile_position = player_spawn_pos.into();
                 commands.push(Box::new(game_commands.spawn_object(
@@ -483,11 +1011,15 @@ pub fn start_game(world: &mut World) {
                 noncolorable_tile_stack_rules,
             )) as Box<dyn GameCommand>);
 
-            for player_id in 0..=game_build_settings.enemy_count {
-                let player_spawn_pos = TilePos::new(
-                    level_data.spawn_points[player_id].0 as u32,
-                    level_data.spawn_points[player_id].1 as u32,
-                );
+            for player_id in 0..=game_build_settings.starting_enemy_count() {
+                if level_data.has_starting_buildings(player_id) {
+                    continue;
+                }
+                // The menus only allow as many players as a level has spawn points
+                let Some((x, y)) = level_data.spawn_points.get(player_id).copied() else {
+                    continue;
+                };
+                let player_spawn_pos = TilePos::new(x as u32, y as u32);
                 commands.push(Box::new(game_commands.spawn_object(
                     (
                         ObjectGridPosition {
@@ -527,6 +1059,7 @@ pub fn start_game(world: &mut World) {
         world,
         game_data,
         game_build_settings,
+        puzzle.map(|puzzle| PuzzleState::new(&puzzle)),
     );
 }
 
@@ -555,6 +1088,7 @@ pub fn setup_game(
     world: &mut World,
     game_data: GameData,
     game_build_settings: GameBuildSettings,
+    puzzle_state: Option<PuzzleState>,
 ) {
     let mut schedule = Schedule::new();
     schedule.configure_sets((GameSets::Pre, GameSets::Core, GameSets::Post).chain());
@@ -562,8 +1096,12 @@ pub fn setup_game(
         (
             update_objects_index,
             apply_system_buffers,
-            update_building_timers,
-            update_ability_timers,
+            (
+                update_building_timers,
+                update_ability_timers,
+                update_building_modifiers,
+                update_overclocks,
+            ),
             apply_system_buffers,
             simulate_simple_pathfind_object_cache::<
                 Building<Pulser>,
@@ -581,20 +1119,47 @@ pub fn setup_game(
                 SimplePathfindMap<Building<Scatter>>,
             >,
             simulate_simple_pathfind_object_cache::<
-                Ability<Nuke>,
-                SimplePathfinder<Ability<Nuke>>,
-                SimplePathfindMap<Ability<Nuke>>,
-            >,
-            simulate_simple_pathfind_object_cache::<
-                Ability<Expand>,
-                SimplePathfinder<Ability<Expand>>,
-                SimplePathfindMap<Ability<Expand>>,
+                Building<Wall>,
+                SimplePathfinder<Building<Wall>>,
+                SimplePathfindMap<Building<Wall>>,
             >,
             simulate_simple_pathfind_object_cache::<
-                Ability<Fortify>,
-                SimplePathfinder<Ability<Fortify>>,
-                SimplePathfindMap<Ability<Fortify>>,
+                Building<Harvester>,
+                SimplePathfinder<Building<Harvester>>,
+                SimplePathfindMap<Building<Harvester>>,
             >,
+            (
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Nuke>,
+                    SimplePathfinder<Ability<Nuke>>,
+                    SimplePathfindMap<Ability<Nuke>>,
+                >,
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Expand>,
+                    SimplePathfinder<Ability<Expand>>,
+                    SimplePathfindMap<Ability<Expand>>,
+                >,
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Fortify>,
+                    SimplePathfinder<Ability<Fortify>>,
+                    SimplePathfindMap<Ability<Fortify>>,
+                >,
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Freeze>,
+                    SimplePathfinder<Ability<Freeze>>,
+                    SimplePathfindMap<Ability<Freeze>>,
+                >,
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Swap>,
+                    SimplePathfinder<Ability<Swap>>,
+                    SimplePathfindMap<Ability<Swap>>,
+                >,
+                simulate_simple_pathfind_object_cache::<
+                    Ability<Shield>,
+                    SimplePathfinder<Ability<Shield>>,
+                    SimplePathfindMap<Ability<Shield>>,
+                >,
+            ),
             apply_system_buffers,
             simulate_pulsers_from_cache,
         )
@@ -604,19 +1169,52 @@ pub fn setup_game(
     schedule.add_systems(
         (
             simulate_lines_from_cache.after(simulate_pulsers_from_cache),
-            simulate_scatter_from_cache,
-            simulate_nuke_from_cache,
-            simulate_expand_from_cache,
-            simulate_fortify_from_cache,
+            // Everything that rolls the GameRng runs in a fixed order so seeded games replay
+            (
+                simulate_scatter_from_cache,
+                simulate_harvesters_from_cache,
+                simulate_objectives,
+                simulate_artillery,
+                simulate_painters,
+            )
+                .chain(),
+            (
+                simulate_nuke_from_cache,
+                simulate_expand_from_cache,
+                simulate_fortify_from_cache,
+                simulate_freeze,
+                simulate_swap_from_cache,
+                simulate_shield,
+            )
+                .chain(),
             update_color_conflicts,
-            run_ai_building,
-            run_ai_ability,
+            // run_ai_building resets the actions of every ai player so it has to run first, and the
+            // upgrade and relocate ais only act when the building ai didn't place anything
+            (
+                run_ai_building,
+                run_ai_ability,
+                run_ai_artillery,
+                run_ai_upgrade,
+                run_ai_painters,
+                run_ai_relocate,
+            )
+                .chain(),
             handle_color_conflict_guarantees,
             handle_color_conflicts,
             apply_system_buffers,
-            destroy_buildings,
+            (destroy_buildings, destroy_units),
             destroy_abilities,
+            (
+                update_territory_supply,
+                claim_power_ups,
+                update_sudden_death,
+                update_survival_score,
+                update_puzzle,
+            )
+                .chain(),
             apply_system_buffers,
+            spawn_power_ups,
+            spawn_survival_waves,
         )
             .chain()
             .in_base_set(GameSets::Core),
@@ -639,20 +1237,47 @@ pub fn setup_game(
                 SimplePathfindMap<Building<Scatter>>,
             >,
             delete_pathfind_object_from_tile_index_cache::<
-                Ability<Nuke>,
-                SimplePathfinder<Ability<Nuke>>,
-                SimplePathfindMap<Ability<Nuke>>,
-            >,
-            delete_pathfind_object_from_tile_index_cache::<
-                Ability<Expand>,
-                SimplePathfinder<Ability<Expand>>,
-                SimplePathfindMap<Ability<Expand>>,
+                Building<Wall>,
+                SimplePathfinder<Building<Wall>>,
+                SimplePathfindMap<Building<Wall>>,
             >,
             delete_pathfind_object_from_tile_index_cache::<
-                Ability<Fortify>,
-                SimplePathfinder<Ability<Fortify>>,
-                SimplePathfindMap<Ability<Fortify>>,
+                Building<Harvester>,
+                SimplePathfinder<Building<Harvester>>,
+                SimplePathfindMap<Building<Harvester>>,
             >,
+            (
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Nuke>,
+                    SimplePathfinder<Ability<Nuke>>,
+                    SimplePathfindMap<Ability<Nuke>>,
+                >,
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Expand>,
+                    SimplePathfinder<Ability<Expand>>,
+                    SimplePathfindMap<Ability<Expand>>,
+                >,
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Fortify>,
+                    SimplePathfinder<Ability<Fortify>>,
+                    SimplePathfindMap<Ability<Fortify>>,
+                >,
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Freeze>,
+                    SimplePathfinder<Ability<Freeze>>,
+                    SimplePathfindMap<Ability<Freeze>>,
+                >,
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Swap>,
+                    SimplePathfinder<Ability<Swap>>,
+                    SimplePathfindMap<Ability<Swap>>,
+                >,
+                delete_pathfind_object_from_tile_index_cache::<
+                    Ability<Shield>,
+                    SimplePathfinder<Ability<Shield>>,
+                    SimplePathfindMap<Ability<Shield>>,
+                >,
+            ),
             apply_system_buffers,
             update_player_points,
             apply_system_buffers,
@@ -672,18 +1297,27 @@ pub fn setup_game(
     game.setup_movement(tile_movement_costs);
     game.setup_mapping();
 
-    for player_id in 0..=game_build_settings.enemy_count {
+    for player_id in 0..=game_build_settings.ai_player_count() {
         if player_id == 0 {
             let (player_id, entity_mut) = game.add_player(true);
             let entity = entity_mut.id();
+            // Puzzles place from the inventory so the player starts without points
+            let building_points = match puzzle_state {
+                Some(_) => 0,
+                None => 50,
+            };
             game.game_world.entity_mut(entity).insert(PlayerPoints {
-                building_points: 50,
+                building_points,
                 ability_points: 0,
             });
             world
                 .spawn_empty()
                 .insert(Actions::default())
                 .insert(PlayerMarker::new(player_id));
+        } else if puzzle_state.is_some() {
+            // Puzzle enemies only have what the puzzle gives them so, like the neutral faction, they
+            // get no points or actions
+            game.add_player(false);
         } else {
             let (player_id, entity_mut) = game.add_player(false);
             let entity = entity_mut.id();
@@ -697,7 +1331,26 @@ pub fn setup_game(
         }
     }
 
+    // The neutral faction gets no points or actions so neither the ai nor the income touch it
+    if game_data.neutral_player.is_some() {
+        game.add_player(false);
+    }
+
     game.game_world.init_resource::<ColorConflicts>();
+    game.game_world.init_resource::<TerritorySupply>();
+    game.game_world.init_resource::<PowerUpSpawner>();
+    game.game_world.init_resource::<SuddenDeathState>();
+    if game_build_settings.game_mode == GameMode::Survival {
+        game.game_world.init_resource::<SurvivalState>();
+    }
+    if let Some(puzzle_state) = puzzle_state {
+        game.game_world.insert_resource(puzzle_state);
+    }
+    game.game_world
+        .insert_resource(match game_build_settings.seed {
+            Some(seed) => GameRng::from_seed(seed),
+            None => GameRng::from_entropy(),
+        });
     game.game_world
         .init_resource::<Events<ColorConflictEvent>>();
     game.game_world
@@ -712,14 +1365,27 @@ pub fn setup_game(
     game.register_component::<Building<Pulser>>();
     game.register_component::<Building<Line>>();
     game.register_component::<Building<Scatter>>();
+    game.register_component::<Building<Wall>>();
+    game.register_component::<Building<Harvester>>();
+    game.register_component::<Building<Relay>>();
+    game.register_component::<Building<Artillery>>();
+    game.register_component::<Building<Workshop>>();
+    game.register_component::<Building<Objective>>();
+    game.register_component::<Unit<Painter>>();
+    game.register_component::<BuildingModifierSource>();
+    game.register_component::<BuildingTier>();
+    game.register_component::<BuildingHealth>();
+    game.register_component::<PowerUp>();
 
     game.register_component::<Activate>();
     game.register_component::<BuildingCooldown>();
+    game.register_component::<BuildingPointsSpent>();
 
     game.register_component::<TileColor>();
 
     game.register_component::<PlayerPoints>();
     game.register_component::<Actions>();
+    game.register_component::<Overclock>();
 
     world.insert_resource(game_data.clone());
     game.game_world.insert_resource(game_data);
@@ -0,0 +1,207 @@
+//! Puzzle mode. A puzzle starts from a fixed board and hands the player a limited inventory instead
+//! of points. It is solved by capturing every colorable tile of the target region before the time
+//! runs out
+
+use crate::abilities::Abilities;
+use crate::buildings::{check_is_colorable, BuildingTypes};
+use crate::game::campaign::Restrictions;
+use crate::game::end_game::GameEnded;
+use crate::loading::puzzle_loader::Puzzle;
+use bevy::prelude::{Commands, FromReflect, Query, Reflect, Res, ResMut, Resource, With};
+use bevy::time::Time;
+use bevy::utils::HashMap;
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::player::PlayerMarker;
+
+const ALL_BUILDINGS: [BuildingTypes; 8] = [
+    BuildingTypes::Pulser,
+    BuildingTypes::Scatter,
+    BuildingTypes::Line,
+    BuildingTypes::Wall,
+    BuildingTypes::Harvester,
+    BuildingTypes::Relay,
+    BuildingTypes::Artillery,
+    BuildingTypes::Workshop,
+];
+
+const ALL_ABILITIES: [Abilities; 6] = [
+    Abilities::Nuke,
+    Abilities::Fortify,
+    Abilities::Expand,
+    Abilities::Freeze,
+    Abilities::Swap,
+    Abilities::Shield,
+];
+
+/// How many of each building and ability the player has left to place
+#[derive(Default, Reflect, FromReflect, Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct PuzzleInventory {
+    #[serde(default)]
+    pub buildings: Vec<(BuildingTypes, u32)>,
+    #[serde(default)]
+    pub abilities: Vec<(Abilities, u32)>,
+}
+
+impl PuzzleInventory {
+    pub fn building_count(&self, building_type: BuildingTypes) -> u32 {
+        self.buildings
+            .iter()
+            .filter(|(inventory_type, _)| *inventory_type == building_type)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    pub fn ability_count(&self, ability: Abilities) -> u32 {
+        self.abilities
+            .iter()
+            .filter(|(inventory_ability, _)| *inventory_ability == ability)
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Uses up one of the building. Returns false if there were none left
+    pub fn take_building(&mut self, building_type: BuildingTypes) -> bool {
+        match self
+            .buildings
+            .iter_mut()
+            .find(|(inventory_type, count)| *inventory_type == building_type && *count > 0)
+        {
+            Some((_, count)) => {
+                *count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Uses up one of the ability. Returns false if there were none left
+    pub fn take_ability(&mut self, ability: Abilities) -> bool {
+        match self
+            .abilities
+            .iter_mut()
+            .find(|(inventory_ability, count)| *inventory_ability == ability && *count > 0)
+        {
+            Some((_, count)) => {
+                *count -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restricts everything that isn't in the inventory so the game ui only shows what can be used
+    pub fn restrictions(&self) -> Restrictions {
+        Restrictions {
+            buildings: ALL_BUILDINGS
+                .into_iter()
+                .filter(|building_type| self.building_count(*building_type) == 0)
+                .collect(),
+            abilities: ALL_ABILITIES
+                .into_iter()
+                .filter(|ability| self.ability_count(*ability) == 0)
+                .collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for PuzzleInventory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let items: Vec<String> = self
+            .buildings
+            .iter()
+            .map(|(building_type, count)| format!("{} {:?}", count, building_type))
+            .chain(
+                self.abilities
+                    .iter()
+                    .map(|(ability, count)| format!("{} {:?}", count, ability)),
+            )
+            .collect();
+        match items.is_empty() {
+            true => f.write_str("Nothing"),
+            false => f.write_str(&items.join(", ")),
+        }
+    }
+}
+
+/// A rectangle of tiles, inclusive of both corners, that the player has to capture
+#[derive(Default, Reflect, FromReflect, Clone, Copy, Debug, PartialEq, serde::Deserialize)]
+pub struct PuzzleTarget {
+    pub min: (usize, usize),
+    pub max: (usize, usize),
+}
+
+impl PuzzleTarget {
+    pub fn contains(&self, tile_pos: &TilePos) -> bool {
+        (self.min.0..=self.max.0).contains(&(tile_pos.x as usize))
+            && (self.min.1..=self.max.1).contains(&(tile_pos.y as usize))
+    }
+}
+
+/// Only inserted into the game world of puzzle games
+#[derive(Resource)]
+pub struct PuzzleState {
+    pub inventory: PuzzleInventory,
+    pub target: PuzzleTarget,
+    pub seconds_left: f32,
+    pub captured_tiles: u32,
+    pub target_tiles: u32,
+}
+
+impl PuzzleState {
+    pub fn new(puzzle: &Puzzle) -> PuzzleState {
+        PuzzleState {
+            inventory: puzzle.inventory.clone(),
+            target: puzzle.target,
+            seconds_left: puzzle.time_limit_seconds as f32,
+            captured_tiles: 0,
+            target_tiles: 0,
+        }
+    }
+}
+
+/// Counts down the time limit and ends the game once the target is captured or the time is up
+pub fn update_puzzle(
+    puzzle_state: Option<ResMut<PuzzleState>>,
+    time: Res<Time>,
+    tiles: Query<(&TilePos, &TileTerrainInfo, Option<&PlayerMarker>), With<Tile>>,
+    mut commands: Commands,
+) {
+    let Some(mut puzzle_state) = puzzle_state else {
+        return;
+    };
+    puzzle_state.seconds_left -= time.delta_seconds();
+
+    let mut target_owners: HashMap<usize, u32> = HashMap::new();
+    let mut target_tiles = 0;
+    for (tile_pos, tile_terrain_info, player_marker) in tiles.iter() {
+        if !puzzle_state.target.contains(tile_pos) || !check_is_colorable(tile_terrain_info) {
+            continue;
+        }
+        target_tiles += 1;
+        if let Some(player_marker) = player_marker {
+            *target_owners.entry(player_marker.id()).or_insert(0) += 1;
+        }
+    }
+    puzzle_state.target_tiles = target_tiles;
+    puzzle_state.captured_tiles = target_owners.get(&0).copied().unwrap_or(0);
+
+    if target_tiles > 0 && puzzle_state.captured_tiles == target_tiles {
+        commands.insert_resource(GameEnded {
+            player_won: true,
+            winning_id: 0,
+        });
+    } else if puzzle_state.seconds_left <= 0.0 {
+        // The enemy holding the most of the target counts as the winner
+        let winning_id = target_owners
+            .iter()
+            .filter(|(id, _)| **id != 0)
+            .max_by_key(|(_, count)| **count)
+            .map_or(1, |(id, _)| *id);
+        commands.insert_resource(GameEnded {
+            player_won: false,
+            winning_id,
+        });
+    }
+}
@@ -1,5 +1,5 @@
 use crate::audio::ObjectSpawnedSound;
-use crate::color_system::{TileColor, TileColorStrength};
+use crate::color_system::TileColor;
 use crate::draw::{DrawObject, DrawTile, UpdateTile};
 use crate::mapping::MapTileIndex;
 use crate::objects::ObjectIndex;
@@ -14,12 +14,12 @@ use bevy_ggf::game_core::saving::ComponentBinaryState;
 use bevy_ggf::game_core::state::{ObjectState, PlayerState, TileState};
 use bevy_ggf::game_core::Game;
 use bevy_ggf::mapping::tiles::Tile;
-use bevy_ggf::object::ObjectId;
+use bevy_ggf::object::{ObjectGridPosition, ObjectId};
 use bevy_ggf::player::{Player, PlayerMarker};
 
 #[derive(Component)]
 pub struct OldObjectState {
-    pub tile_color_strength: Option<TileColorStrength>,
+    pub tile_color_strength: Option<u32>,
 }
 
 impl Default for OldObjectState {
@@ -56,7 +56,7 @@ pub fn update_main_world_game_state(
         ),
         Without<ObjectId>,
     >,
-    mut object_query: Query<(Entity, &ObjectId)>,
+    mut object_query: Query<(Entity, &ObjectId, &ObjectGridPosition)>,
     mut player_query: Query<(Entity, &Player)>,
     mut game: ResMut<Game>,
     mut commands: Commands,
@@ -128,7 +128,13 @@ pub fn update_main_world_game_state(
     let objects: Vec<ObjectState> = game_state.objects.into_iter().collect();
     for object in objects {
         let entity = if let Some(entity) = object_index.hashmap.get(&object.object_id) {
-            if let Ok((entity, _)) = object_query.get(*entity) {
+            if let Ok((entity, _, object_grid_position)) = object_query.get(*entity) {
+                // Units move around the map so keep the position in sync with the game world
+                if TilePos::from(object_grid_position.tile_position)
+                    != TilePos::from(object.object_grid_position.tile_position)
+                {
+                    commands.entity(entity).insert(object.object_grid_position);
+                }
                 entity
             } else {
                 continue;
@@ -181,7 +187,7 @@ pub fn update_main_world_game_state(
 
     let despawned_objects: Vec<ObjectId> = game_state.despawned_objects.into_iter().collect();
     for object in despawned_objects {
-        if let Some((entity, _)) = object_query.iter_mut().find(|(_, id)| id == &&object) {
+        if let Some((entity, _, _)) = object_query.iter_mut().find(|(_, id, _)| id == &&object) {
             commands.entity(entity).despawn_recursive();
         }
     }
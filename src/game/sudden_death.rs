@@ -0,0 +1,201 @@
+use crate::buildings::check_is_colorable;
+use crate::color_system::{PlayerTileChangedCount, TileColor};
+use crate::game::{noncolorable_tile_stack_rules, GameBuildSettings, GameData};
+use crate::mapping::HardenedTile;
+use bevy::prelude::{Commands, Query, Res, ResMut, Resource, With, Without};
+use bevy::reflect::{FromReflect, Reflect};
+use bevy::time::Time;
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage, TilemapSize};
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{Object, ObjectGridPosition};
+use bevy_ggf::player::PlayerMarker;
+use std::fmt::Display;
+
+/// Whether and how fast the board shrinks. Once sudden death starts the outermost ring of colorable
+/// tiles turns noncolorable every collapse interval
+#[derive(Default, Reflect, FromReflect, Clone, Copy, Eq, Debug, PartialEq)]
+pub enum SuddenDeath {
+    #[default]
+    Off,
+    Late,
+    Standard,
+    Early,
+}
+
+impl Display for SuddenDeath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.timings() {
+            None => f.write_str("Off"),
+            Some((start_after, collapse_interval)) => write!(
+                f,
+                "After {}:{:02}, every {}s",
+                start_after / 60,
+                start_after % 60,
+                collapse_interval
+            ),
+        }
+    }
+}
+
+impl SuddenDeath {
+    /// The seconds until the first ring collapses and the seconds between every following collapse
+    pub fn timings(&self) -> Option<(u32, u32)> {
+        match self {
+            SuddenDeath::Off => None,
+            SuddenDeath::Late => Some((300, 15)),
+            SuddenDeath::Standard => Some((180, 10)),
+            SuddenDeath::Early => Some((90, 8)),
+        }
+    }
+
+    pub fn next(&self) -> SuddenDeath {
+        match self {
+            SuddenDeath::Off => SuddenDeath::Late,
+            SuddenDeath::Late => SuddenDeath::Standard,
+            SuddenDeath::Standard => SuddenDeath::Early,
+            SuddenDeath::Early => SuddenDeath::Early,
+        }
+    }
+
+    pub fn prev(&self) -> SuddenDeath {
+        match self {
+            SuddenDeath::Off => SuddenDeath::Off,
+            SuddenDeath::Late => SuddenDeath::Off,
+            SuddenDeath::Standard => SuddenDeath::Late,
+            SuddenDeath::Early => SuddenDeath::Standard,
+        }
+    }
+}
+
+/// Tracks when the next ring collapses. Read by the main world to draw the warning overlay
+#[derive(Default, Resource)]
+pub struct SuddenDeathState {
+    elapsed: f32,
+    next_collapse: Option<f32>,
+    /// The tiles of the ring that collapses next. Only filled while the collapse is telegraphed
+    pub warned_tiles: Vec<TilePos>,
+    pub seconds_until_collapse: f32,
+}
+
+/// How many tiles lie between the tile and the closest edge of the map
+pub fn edge_distance(tile_pos: &TilePos, map_size: &TilemapSize) -> u32 {
+    tile_pos
+        .x
+        .min(tile_pos.y)
+        .min(map_size.x - 1 - tile_pos.x)
+        .min(map_size.y - 1 - tile_pos.y)
+}
+
+/// Telegraphs the outermost ring of colorable tiles for a whole collapse interval and then turns it
+/// noncolorable, stack rules included so nothing new can be placed on it. Buildings already on the
+/// collapsed tiles are destroyed by `destroy_buildings`
+pub fn update_sudden_death(
+    mut sudden_death_state: ResMut<SuddenDeathState>,
+    game_settings: Res<GameBuildSettings>,
+    game_data: Res<GameData>,
+    time: Res<Time>,
+    mut player_tiles_changed_count: ResMut<PlayerTileChangedCount>,
+    mut tiles: Query<
+        (
+            &TilePos,
+            &mut TileTerrainInfo,
+            &mut TileObjectStacks,
+            Option<&PlayerMarker>,
+        ),
+        With<Tile>,
+    >,
+    objects: Query<
+        (&ObjectGridPosition, &ObjectStackingClass),
+        (With<Object>, Without<DespawnObject>, Without<Tile>),
+    >,
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    mut commands: Commands,
+) {
+    let Some((start_after, collapse_interval)) = game_settings.sudden_death.timings() else {
+        return;
+    };
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    let sudden_death_state = sudden_death_state.as_mut();
+    sudden_death_state.elapsed += time.delta_seconds();
+    let next_collapse = *sudden_death_state
+        .next_collapse
+        .get_or_insert(start_after as f32);
+
+    sudden_death_state.seconds_until_collapse = next_collapse - sudden_death_state.elapsed;
+    if sudden_death_state.seconds_until_collapse > collapse_interval as f32 {
+        return;
+    }
+
+    if sudden_death_state.warned_tiles.is_empty() {
+        let Some(ring) = tiles
+            .iter()
+            .filter(|(_, tile_terrain_info, _, _)| check_is_colorable(tile_terrain_info))
+            .map(|(tile_pos, _, _, _)| edge_distance(tile_pos, &tile_storage.size))
+            .min()
+        else {
+            return;
+        };
+        sudden_death_state.warned_tiles = tiles
+            .iter()
+            .filter(|(tile_pos, tile_terrain_info, _, _)| {
+                check_is_colorable(tile_terrain_info)
+                    && edge_distance(tile_pos, &tile_storage.size) == ring
+            })
+            .map(|(tile_pos, _, _, _)| *tile_pos)
+            .collect();
+    }
+
+    if sudden_death_state.seconds_until_collapse > 0.0 {
+        return;
+    }
+
+    let noncolorable = game_data
+        .terrain_types
+        .get("BasicNonColorable")
+        .unwrap()
+        .clone();
+    for tile_pos in sudden_death_state.warned_tiles.drain(..) {
+        let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+            continue;
+        };
+        let Ok((_, mut tile_terrain_info, mut tile_object_stacks, opt_player_marker)) =
+            tiles.get_mut(tile_entity)
+        else {
+            continue;
+        };
+        tile_terrain_info.terrain_type = noncolorable.clone();
+
+        // The objects still on the tile keep counting towards the new stacks so removing them
+        // later lines up
+        let mut collapsed_stacks = noncolorable_tile_stack_rules(&game_data);
+        for (_, object_stacking_class) in objects.iter().filter(|(object_grid_position, _)| {
+            TilePos::from(object_grid_position.tile_position) == tile_pos
+        }) {
+            collapsed_stacks.increment_object_class_count(object_stacking_class);
+        }
+        *tile_object_stacks = collapsed_stacks;
+
+        if opt_player_marker.map_or(false, |player_marker| player_marker.id() == 0) {
+            player_tiles_changed_count.player_lost_tiles = player_tiles_changed_count
+                .player_lost_tiles
+                .saturating_add(1);
+        }
+        commands
+            .entity(tile_entity)
+            .remove::<PlayerMarker>()
+            .remove::<TileColor>()
+            .remove::<HardenedTile>()
+            .insert(Changed::default());
+    }
+    sudden_death_state.next_collapse = Some(next_collapse + collapse_interval as f32);
+}
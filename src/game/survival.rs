@@ -0,0 +1,255 @@
+//! Survival mode. The player starts alone and new ai factions keep spawning at the edges of the map,
+//! every wave with more points and a faster ai than the last. The score is how long the player holds
+//! at least [`GameBuildSettings::survival_hold_percent`] percent of the board
+
+use crate::buildings::line::LineDirection;
+use crate::buildings::{building_spawn_command, check_is_colorable, BuildingMarker, BuildingTypes};
+use crate::game::end_game::in_play_tile_count;
+use crate::game::game_difficulty::GameDifficulty;
+use crate::game::game_rng::GameRng;
+use crate::game::sudden_death::edge_distance;
+use crate::game::{GameBuildSettings, GameData};
+use crate::level_loader::{LevelHandle, Levels};
+use crate::loading::leaderboard_loader::{SurvivalLeaderboard, SurvivalScore};
+use crate::player::PlayerPoints;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{
+    Assets, Component, Entity, Query, Res, ResMut, Resource, With, Without, World,
+};
+use bevy::time::Time;
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::game_core::Game;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::player::{Player, PlayerMarker};
+use rand::seq::SliceRandom;
+
+/// The percentage of the board the player has to hold for the survival score to count up, unless
+/// the menu sets another one
+pub const DEFAULT_SURVIVAL_HOLD_PERCENT: u32 = 20;
+
+/// The lowest and highest hold percentage the menu can set
+pub const MIN_SURVIVAL_HOLD_PERCENT: u32 = 5;
+pub const MAX_SURVIVAL_HOLD_PERCENT: u32 = 80;
+
+/// The seconds before the first wave spawns
+const FIRST_WAVE_SECONDS: f32 = 15.0;
+
+/// The seconds between two waves
+const WAVE_INTERVAL_SECONDS: f32 = 60.0;
+
+/// The seconds until a wave tries again when every ai faction is still alive or the edges of the map
+/// are full
+const WAVE_RETRY_SECONDS: f32 = 5.0;
+
+/// Only inserted into the game world of survival games
+#[derive(Resource)]
+pub struct SurvivalState {
+    pub waves_spawned: u32,
+    pub seconds_until_wave: f32,
+    /// The seconds the player has held at least the survival hold percentage of the board
+    pub score: f32,
+}
+
+impl Default for SurvivalState {
+    fn default() -> Self {
+        SurvivalState {
+            waves_spawned: 0,
+            seconds_until_wave: FIRST_WAVE_SECONDS,
+            score: 0.0,
+        }
+    }
+}
+
+/// Put on the player of an ai faction spawned by a wave
+#[derive(Clone, Copy, Debug, Component)]
+pub struct SurvivalWave {
+    pub wave: u32,
+    /// Replaces the building speed of the game difficulty for this faction
+    pub building_speed: f64,
+}
+
+impl SurvivalWave {
+    pub fn new(wave: u32, game_difficulty: &GameDifficulty) -> SurvivalWave {
+        let escalation = wave.saturating_sub(1) as f64;
+        SurvivalWave {
+            wave,
+            building_speed: (game_difficulty.ai_building_speed() * (1.0 + 0.5 * escalation))
+                .min(1.0),
+        }
+    }
+
+    pub fn starting_points(&self) -> u32 {
+        50u32
+            .saturating_add(50u32.saturating_mul(self.wave.saturating_sub(1)))
+            .min(PlayerPoints::MAX_BUILDING_POINTS)
+    }
+}
+
+/// Formats survival seconds as minutes and seconds
+pub fn format_survival_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+pub fn update_survival_score(
+    survival_state: Option<ResMut<SurvivalState>>,
+    game_settings: Res<GameBuildSettings>,
+    time: Res<Time>,
+    tiles: Query<&PlayerMarker, With<Tile>>,
+    terrain: Query<&TileTerrainInfo, With<Tile>>,
+) {
+    let Some(mut survival_state) = survival_state else {
+        return;
+    };
+
+    let player_tiles = tiles
+        .iter()
+        .filter(|player_marker| player_marker.id() == 0)
+        .count();
+    let tile_count = in_play_tile_count(&game_settings, &terrain);
+    if player_tiles as f32 / tile_count as f32 >= game_settings.survival_hold_percent as f32 / 100.0
+    {
+        survival_state.score += time.delta_seconds();
+    }
+}
+
+/// Counts down to the next wave and spawns it as a Pulser for an ai faction that is out of the game,
+/// on a free tile as close to the edge of the map as possible
+pub fn spawn_survival_waves(world: &mut World) {
+    let delta_seconds = world.resource::<Time>().delta_seconds();
+    let Some(mut survival_state) = world.get_resource_mut::<SurvivalState>() else {
+        return;
+    };
+    survival_state.seconds_until_wave -= delta_seconds;
+    if survival_state.seconds_until_wave > 0.0 {
+        return;
+    }
+    survival_state.seconds_until_wave = WAVE_RETRY_SECONDS;
+    let wave = survival_state.waves_spawned + 1;
+
+    let game_data = world.resource::<GameData>().clone();
+    let building_stacking_class = ObjectStackingClass {
+        stack_class: game_data.stacking_classes.get("Building").unwrap().clone(),
+    };
+
+    let mut system_state: SystemState<(
+        Query<(Entity, &Player), With<PlayerPoints>>,
+        Query<&PlayerMarker, (With<BuildingMarker>, Without<DespawnObject>, Without<Tile>)>,
+        Query<
+            (
+                &TilePos,
+                &TileTerrainInfo,
+                &TileObjectStacks,
+                Option<&PlayerMarker>,
+            ),
+            With<Tile>,
+        >,
+        Query<(&MapId, &TileStorage)>,
+    )> = SystemState::new(world);
+    let (players, buildings, tiles, tile_storage_query) = system_state.get(world);
+
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    // Factions are reused once they are beaten so waves can go on for as long as the player lasts
+    let Some((player_entity, player_id)) = players
+        .iter()
+        .filter(|(_, player)| player.id() != 0)
+        .filter(|(_, player)| {
+            !buildings
+                .iter()
+                .any(|player_marker| player_marker.id() == player.id())
+                && !tiles.iter().any(|(_, _, _, tile_player_marker)| {
+                    tile_player_marker
+                        .map_or(false, |player_marker| player_marker.id() == player.id())
+                })
+        })
+        .map(|(entity, player)| (entity, player.id()))
+        .min_by_key(|(_, player_id)| *player_id)
+    else {
+        return;
+    };
+
+    let free_tiles: Vec<(TilePos, u32)> = tiles
+        .iter()
+        .filter(
+            |(_, tile_terrain_info, tile_object_stacks, tile_player_marker)| {
+                tile_player_marker.is_none()
+                    && check_is_colorable(tile_terrain_info)
+                    && tile_object_stacks.has_space(&building_stacking_class)
+            },
+        )
+        .map(|(tile_pos, _, _, _)| (*tile_pos, edge_distance(tile_pos, &tile_storage.size)))
+        .collect();
+    let Some(closest_distance) = free_tiles.iter().map(|(_, distance)| *distance).min() else {
+        return;
+    };
+    let edge_tiles: Vec<TilePos> = free_tiles
+        .iter()
+        .filter(|(_, distance)| *distance == closest_distance)
+        .map(|(tile_pos, _)| *tile_pos)
+        .collect();
+    let Some(tile_pos) = edge_tiles
+        .choose(&mut world.resource_mut::<GameRng>().0)
+        .copied()
+    else {
+        return;
+    };
+
+    let survival_wave =
+        SurvivalWave::new(wave, &world.resource::<GameBuildSettings>().game_difficulty);
+    let mut player = world.entity_mut(player_entity);
+    player.insert((survival_wave, Changed::default()));
+    if let Some(mut player_points) = player.get_mut::<PlayerPoints>() {
+        player_points.building_points = survival_wave.starting_points();
+        player_points.ability_points = 0;
+    }
+
+    let mut spawn = building_spawn_command(
+        &game_data,
+        BuildingTypes::Pulser,
+        LineDirection::default(),
+        player_id,
+        tile_pos,
+        0,
+    );
+    let _ = spawn.execute(world);
+
+    let mut survival_state = world.resource_mut::<SurvivalState>();
+    survival_state.waves_spawned = wave;
+    survival_state.seconds_until_wave = WAVE_INTERVAL_SECONDS;
+}
+
+/// Adds the score of a finished survival game to the local leaderboard
+pub fn record_survival_score(
+    game: Res<Game>,
+    game_settings: Res<GameBuildSettings>,
+    level_handle: Res<LevelHandle>,
+    level_assets: Res<Assets<Levels>>,
+    mut leaderboard: ResMut<SurvivalLeaderboard>,
+) {
+    leaderboard.last_rank = None;
+    let Some(survival_state) = game.game_world.get_resource::<SurvivalState>() else {
+        return;
+    };
+
+    let map = match level_assets.get(&level_handle.levels) {
+        Some(levels) => levels.levels[game_settings.map_type].name.clone(),
+        None => String::new(),
+    };
+    leaderboard.add_score(SurvivalScore {
+        seconds_held: survival_state.score,
+        waves: survival_state.waves_spawned,
+        map,
+        difficulty: game_settings.game_difficulty.to_string(),
+        hold_percent: game_settings.survival_hold_percent,
+    });
+}
@@ -13,7 +13,12 @@ mod mapping;
 mod objects;
 mod pathfinding;
 mod player;
+mod power_ups;
+mod territory_decay;
+#[cfg(test)]
+mod test_support;
 mod ui;
+mod units;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
@@ -25,7 +30,9 @@ use crate::color_system::ColorSystemPlugin;
 use crate::draw::DrawPlugin;
 use crate::framework::FrameworkPlugin;
 use crate::game::GameCorePlugin;
+use crate::loading::campaign_loader::Campaign;
 use crate::loading::colors_loader::PalettesAssets;
+use crate::loading::puzzle_loader::Puzzles;
 use crate::mapping::MapPlugin;
 use crate::ui::UiPlugin;
 use bevy::app::App;
@@ -86,6 +93,8 @@ impl Plugin for GamePlugin {
         app.add_plugins(BggfDefaultPlugins);
         app.add_plugin(RonAssetPlugin::<level_loader::Levels>::new(&["levels.ron"]));
         app.add_plugin(RonAssetPlugin::<PalettesAssets>::new(&["palettes.ron"]));
+        app.add_plugin(RonAssetPlugin::<Campaign>::new(&["campaign.ron"]));
+        app.add_plugin(RonAssetPlugin::<Puzzles>::new(&["puzzles.ron"]));
 
         app.add_state::<GameState>()
             .add_state::<GamePausedState>()
@@ -0,0 +1,108 @@
+use crate::game::campaign::Restrictions;
+use crate::game::end_game::GameEndConditions;
+use crate::game::game_difficulty::GameDifficulty;
+use crate::loading::persistence::{load_user_file, save_user_file};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy_asset_loader::prelude::AssetCollection;
+
+#[derive(AssetCollection, Resource, TypeUuid)]
+#[uuid = "0b7f4a5e-55f3-4c0b-9a8e-2f61d3c9e7a4"]
+pub struct CampaignHandle {
+    #[asset(path = "defaults.campaign.ron")]
+    pub campaign: Handle<Campaign>,
+}
+
+/// The missions of the campaign in the order they unlock
+#[derive(serde::Deserialize, TypeUuid)]
+#[uuid = "6c2e81d4-94b1-4a57-b3f0-7d1a0e5c4b92"]
+pub struct Campaign {
+    pub missions: Vec<Mission>,
+}
+
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct Mission {
+    pub name: String,
+    pub briefing: String,
+    /// The name of the level in `defaults.levels.ron` the mission is played on
+    pub level: String,
+    /// The size of the map when the level is randomly generated
+    #[serde(default)]
+    pub map_size: Option<u32>,
+    pub enemy_count: usize,
+    pub difficulty: GameDifficulty,
+    pub end_conditions: GameEndConditions,
+    #[serde(default)]
+    pub restrictions: Restrictions,
+    /// Winning within this many seconds earns two stars
+    pub two_star_seconds: u32,
+    /// Winning within this many seconds earns three stars
+    pub three_star_seconds: u32,
+}
+
+impl Mission {
+    /// The stars a win after the given seconds earns. Every win earns at least one
+    pub fn stars_for(&self, seconds: f32) -> u32 {
+        if seconds <= self.three_star_seconds as f32 {
+            3
+        } else if seconds <= self.two_star_seconds as f32 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// The best stars earned in every mission on this machine
+#[derive(Default, Resource, serde::Deserialize, serde::Serialize)]
+pub struct CampaignProgress {
+    pub stars: Vec<u32>,
+    /// The mission and stars of the last finished campaign game, if it was won
+    #[serde(skip)]
+    pub last_result: Option<(usize, u32)>,
+}
+
+impl FromWorld for CampaignProgress {
+    fn from_world(_world: &mut World) -> Self {
+        load_campaign_progress()
+    }
+}
+
+impl CampaignProgress {
+    pub fn stars(&self, mission: usize) -> u32 {
+        self.stars.get(mission).copied().unwrap_or(0)
+    }
+
+    /// Missions unlock once the one before them is won
+    pub fn is_unlocked(&self, mission: usize) -> bool {
+        mission == 0 || self.stars(mission - 1) > 0
+    }
+
+    /// Keeps the stars if they beat the best for the mission and saves the progress
+    pub fn record(&mut self, mission: usize, stars: u32) {
+        self.last_result = Some((mission, stars));
+        if stars <= self.stars(mission) {
+            return;
+        }
+
+        if self.stars.len() <= mission {
+            self.stars.resize(mission + 1, 0);
+        }
+        self.stars[mission] = stars;
+        if let Err(error) = save_campaign_progress(self) {
+            warn!("Failed to save the campaign progress: {}", error);
+        }
+    }
+}
+
+/// The name of the user file the campaign progress is saved in
+const CAMPAIGN_PROGRESS_FILE: &str = "user.campaign.ron";
+
+/// Reads the saved campaign progress if there is any
+pub fn load_campaign_progress() -> CampaignProgress {
+    load_user_file(CAMPAIGN_PROGRESS_FILE).unwrap_or_default()
+}
+
+pub fn save_campaign_progress(campaign_progress: &CampaignProgress) -> Result<(), String> {
+    save_user_file(CAMPAIGN_PROGRESS_FILE, campaign_progress)
+}
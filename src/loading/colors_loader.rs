@@ -1,3 +1,4 @@
+use crate::loading::persistence::{load_user_file, save_user_file};
 use crate::ui::Palette;
 use bevy::prelude::*;
 use bevy::reflect::TypeUuid;
@@ -10,8 +11,30 @@ pub struct PalettesHandle {
     pub palettes: Handle<PalettesAssets>,
 }
 
-#[derive(serde::Deserialize, TypeUuid)]
+#[derive(serde::Deserialize, serde::Serialize, TypeUuid)]
 #[uuid = "48584d80-0365-4f24-99a3-6afd4e5b275a"]
 pub struct PalettesAssets {
     pub palettes: Vec<Palette>,
 }
+
+/// The name of the user file palettes made in the palette editor are saved in
+const USER_PALETTES_FILE: &str = "user.palettes.ron";
+
+/// Reads the saved user palettes if there are any
+pub fn load_user_palettes() -> Vec<Palette> {
+    let Some(palettes_assets) = load_user_file::<PalettesAssets>(USER_PALETTES_FILE) else {
+        return vec![];
+    };
+    palettes_assets
+        .palettes
+        .into_iter()
+        .map(|mut palette| {
+            palette.user_created = true;
+            palette
+        })
+        .collect()
+}
+
+pub fn save_user_palettes(palettes: Vec<Palette>) -> Result<(), String> {
+    save_user_file(USER_PALETTES_FILE, &PalettesAssets { palettes })
+}
@@ -0,0 +1,72 @@
+use crate::game::survival::DEFAULT_SURVIVAL_HOLD_PERCENT;
+use crate::loading::persistence::{load_user_file, save_user_file};
+use bevy::prelude::*;
+
+/// How many scores the survival leaderboard keeps
+const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct SurvivalScore {
+    /// The seconds the player held enough of the board
+    pub seconds_held: f32,
+    pub waves: u32,
+    pub map: String,
+    pub difficulty: String,
+    /// The share of the board in percent the player had to hold
+    #[serde(default = "default_hold_percent")]
+    pub hold_percent: u32,
+}
+
+/// Scores saved before the hold percentage could be changed were all held at the default
+fn default_hold_percent() -> u32 {
+    DEFAULT_SURVIVAL_HOLD_PERCENT
+}
+
+/// The best survival scores on this machine, best first
+#[derive(Default, Resource, serde::Deserialize, serde::Serialize)]
+pub struct SurvivalLeaderboard {
+    pub scores: Vec<SurvivalScore>,
+    /// Where the score of the last finished game placed, if it made the leaderboard
+    #[serde(skip)]
+    pub last_rank: Option<usize>,
+}
+
+impl FromWorld for SurvivalLeaderboard {
+    fn from_world(_world: &mut World) -> Self {
+        load_leaderboard()
+    }
+}
+
+impl SurvivalLeaderboard {
+    /// Adds the score and saves the leaderboard if the score made it in
+    pub fn add_score(&mut self, score: SurvivalScore) {
+        let rank = self
+            .scores
+            .iter()
+            .position(|other| score.seconds_held > other.seconds_held)
+            .unwrap_or(self.scores.len());
+        if rank >= LEADERBOARD_SIZE {
+            self.last_rank = None;
+            return;
+        }
+
+        self.scores.insert(rank, score);
+        self.scores.truncate(LEADERBOARD_SIZE);
+        self.last_rank = Some(rank);
+        if let Err(error) = save_leaderboard(self) {
+            warn!("Failed to save the survival leaderboard: {}", error);
+        }
+    }
+}
+
+/// The name of the user file the leaderboard is saved in
+const LEADERBOARD_FILE: &str = "user.leaderboard.ron";
+
+/// Reads the saved leaderboard if there is one
+pub fn load_leaderboard() -> SurvivalLeaderboard {
+    load_user_file(LEADERBOARD_FILE).unwrap_or_default()
+}
+
+pub fn save_leaderboard(leaderboard: &SurvivalLeaderboard) -> Result<(), String> {
+    save_user_file(LEADERBOARD_FILE, leaderboard)
+}
@@ -24,10 +24,75 @@ pub struct Level {
     pub name: String,
     pub spawn_points: Vec<(usize, usize)>,
     pub tiles: Vec<Vec<TileType>>,
+    /// Objects that are already on the map when the game starts. Players that are given any
+    /// buildings here don't get the default Pulser on their spawn point
+    #[serde(default)]
+    pub objects: Vec<LevelObject>,
+    /// Areas of the map that are already owned when the game starts
+    #[serde(default)]
+    pub regions: Vec<LevelRegion>,
+}
+
+impl Level {
+    /// Whether anything in the level belongs to the neutral faction
+    pub fn has_neutral_faction(&self) -> bool {
+        self.objects
+            .iter()
+            .any(|object| object.owner == LevelOwner::Neutral)
+            || self
+                .regions
+                .iter()
+                .any(|region| region.owner == LevelOwner::Neutral)
+    }
+
+    /// Whether the level gives the player starting on the given spawn point their own buildings
+    pub fn has_starting_buildings(&self, player_id: usize) -> bool {
+        self.objects.iter().any(|object| {
+            object.owner == LevelOwner::Player(player_id)
+                && matches!(object.object, LevelObjectKind::Building(_))
+        })
+    }
+}
+
+/// Who a pre-placed object or region belongs to
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelOwner {
+    /// The player starting on the spawn point with this index. Ignored if there are less players
+    Player(usize),
+    /// An extra hostile faction that isn't controlled by anyone
+    Neutral,
+}
+
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LevelObjectKind {
+    Building(BuildingTypes),
+    Objective,
+}
+
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone)]
+pub struct LevelObject {
+    pub object: LevelObjectKind,
+    pub position: (usize, usize),
+    pub owner: LevelOwner,
+}
+
+/// A rectangle of tiles, inclusive of both corners, that starts out owned at the given strength
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone)]
+pub struct LevelRegion {
+    pub owner: LevelOwner,
+    pub min: (usize, usize),
+    pub max: (usize, usize),
+    pub strength: u32,
 }
 
 #[derive(serde::Deserialize, Reflect, FromReflect, Clone)]
 pub enum TileType {
     Colorable,
     NonColorable,
+    /// Counts double towards the income of the player that owns it
+    Fertile,
+    /// Takes two hits to lose a single strength level
+    Hardened,
+    /// Building footprints pass through it without using up any of their range
+    Conduit,
 }
@@ -1,8 +1,15 @@
+pub mod campaign_loader;
 pub mod colors_loader;
+pub mod leaderboard_loader;
 pub mod level_loader;
+pub mod persistence;
+pub mod puzzle_loader;
 
+use crate::loading::campaign_loader::{CampaignHandle, CampaignProgress};
 use crate::loading::colors_loader::PalettesHandle;
+use crate::loading::leaderboard_loader::SurvivalLeaderboard;
 use crate::loading::level_loader::LevelHandle;
+use crate::loading::puzzle_loader::{validate_puzzles, PuzzleHandle};
 use crate::ui::PlayerColors;
 use crate::GameState;
 use bevy::prelude::*;
@@ -23,9 +30,14 @@ impl Plugin for LoadingPlugin {
         .add_collection_to_loading_state::<_, AudioAssets>(GameState::Loading)
         .add_collection_to_loading_state::<_, LevelHandle>(GameState::Loading)
         .add_collection_to_loading_state::<_, TextureAssets>(GameState::Loading)
-        .add_collection_to_loading_state::<_, PalettesHandle>(GameState::Loading);
+        .add_collection_to_loading_state::<_, PalettesHandle>(GameState::Loading)
+        .add_collection_to_loading_state::<_, CampaignHandle>(GameState::Loading)
+        .add_collection_to_loading_state::<_, PuzzleHandle>(GameState::Loading);
 
         app.init_resource_after_loading_state::<_, PlayerColors>(GameState::Loading);
+        app.add_system(validate_puzzles.in_schedule(OnExit(GameState::Loading)));
+        app.init_resource::<SurvivalLeaderboard>();
+        app.init_resource::<CampaignProgress>();
     }
 }
 
@@ -46,6 +58,12 @@ pub struct AudioAssets {
     pub nuke: Handle<AudioSource>,
     #[asset(path = "audio/fortify_expand.wav")]
     pub fortify_expand: Handle<AudioSource>,
+    #[asset(path = "audio/freeze.wav")]
+    pub freeze: Handle<AudioSource>,
+    #[asset(path = "audio/swap.wav")]
+    pub swap: Handle<AudioSource>,
+    #[asset(path = "audio/shield.wav")]
+    pub shield: Handle<AudioSource>,
     //#[asset(path = "audio/gain_tile.wav")]
     // pub gain_tile: Handle<AudioSource>,
     #[asset(path = "audio/lost_tile.wav")]
@@ -72,10 +90,36 @@ pub struct TextureAssets {
     pub scatter: Handle<Image>,
     #[asset(path = "textures/line.png")]
     pub line: Handle<Image>,
+    #[asset(path = "textures/wall.png")]
+    pub wall: Handle<Image>,
+    #[asset(path = "textures/harvester.png")]
+    pub harvester: Handle<Image>,
+    #[asset(path = "textures/relay.png")]
+    pub relay: Handle<Image>,
+    #[asset(path = "textures/artillery.png")]
+    pub artillery: Handle<Image>,
+    #[asset(path = "textures/workshop.png")]
+    pub workshop: Handle<Image>,
+    #[asset(path = "textures/objective.png")]
+    pub objective: Handle<Image>,
+    #[asset(path = "textures/bonus_points.png")]
+    pub bonus_points: Handle<Image>,
+    #[asset(path = "textures/ability_charge.png")]
+    pub ability_charge: Handle<Image>,
+    #[asset(path = "textures/overclock.png")]
+    pub overclock: Handle<Image>,
+    #[asset(path = "textures/painter.png")]
+    pub painter: Handle<Image>,
     #[asset(path = "textures/nuke.png")]
     pub nuke: Handle<Image>,
     #[asset(path = "textures/fortify.png")]
     pub fortify: Handle<Image>,
     #[asset(path = "textures/expand.png")]
     pub expand: Handle<Image>,
+    #[asset(path = "textures/freeze.png")]
+    pub freeze: Handle<Image>,
+    #[asset(path = "textures/swap.png")]
+    pub swap: Handle<Image>,
+    #[asset(path = "textures/shield.png")]
+    pub shield: Handle<Image>,
 }
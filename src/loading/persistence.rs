@@ -0,0 +1,73 @@
+//! Reading and writing the files the game keeps for the player, like their palettes, campaign
+//! progress and survival leaderboard. They live in the data directory of the user instead of the
+//! assets folder, which isn't writable on every install. Wasm builds have nowhere to save them
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// The folder inside the user data directory the files are kept in
+#[cfg(not(target_arch = "wasm32"))]
+const USER_DATA_FOLDER: &str = "clrs";
+
+/// The directory the platform keeps application data of the user in
+#[cfg(not(target_arch = "wasm32"))]
+fn user_data_dir() -> Option<std::path::PathBuf> {
+    use std::env::var_os;
+    use std::path::PathBuf;
+
+    let base = if cfg!(target_os = "windows") {
+        var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    };
+    base.map(|base| base.join(USER_DATA_FOLDER))
+}
+
+/// Reads the user file with the given name. Returns None if there is no such file or it can't be
+/// read, which is logged
+pub fn load_user_file<T: DeserializeOwned>(file_name: &str) -> Option<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let contents = std::fs::read_to_string(user_data_dir()?.join(file_name)).ok()?;
+        match ron::from_str::<T>(&contents) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                bevy::log::warn!("Failed to read {}: {}", file_name, error);
+                None
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = file_name;
+        None
+    }
+}
+
+/// Writes the value into the user file with the given name, creating the user data directory if
+/// it doesn't exist yet
+pub fn save_user_file<T: Serialize>(file_name: &str, value: &T) -> Result<(), String> {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let Some(user_data_dir) = user_data_dir() else {
+            return Err(String::from(
+                "Couldn't find a directory to save user data in",
+            ));
+        };
+        std::fs::create_dir_all(&user_data_dir).map_err(|error| error.to_string())?;
+        let contents = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())?;
+        std::fs::write(user_data_dir.join(file_name), contents).map_err(|error| error.to_string())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    {
+        let _ = (file_name, value);
+        Err(String::from("Saving isn't supported on the web"))
+    }
+}
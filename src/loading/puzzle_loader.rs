@@ -0,0 +1,149 @@
+use crate::buildings::BuildingTypes;
+use crate::game::puzzle::{PuzzleInventory, PuzzleTarget};
+use crate::level_loader::TileType;
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy_asset_loader::prelude::AssetCollection;
+
+#[derive(AssetCollection, Resource, TypeUuid)]
+#[uuid = "3f9d2c71-8a46-4e1b-b5d7-0c62e4a19f38"]
+pub struct PuzzleHandle {
+    #[asset(path = "defaults.puzzles.ron")]
+    pub puzzles: Handle<Puzzles>,
+}
+
+#[derive(serde::Deserialize, TypeUuid)]
+#[uuid = "a1c7e5f0-2b93-4d68-9e14-7f3b8d06c5a2"]
+pub struct Puzzles {
+    pub puzzles: Vec<Puzzle>,
+}
+
+/// A handcrafted starting board. `board`, `owners` and `strengths` are rows of characters in the
+/// same order as the tiles of a level, so the first row is y 0
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone, Debug)]
+pub struct Puzzle {
+    pub name: String,
+    pub description: String,
+    /// `.` is colorable, `#` noncolorable and `f`, `h` and `c` are fertile, hardened and conduit
+    pub board: Vec<String>,
+    /// The id of the player owning each tile or `.` if no one does. Player 0 is the human player
+    pub owners: Vec<String>,
+    /// The strength of each owned tile from 1 to 9. Tiles that are left out start at 1
+    #[serde(default)]
+    pub strengths: Vec<String>,
+    #[serde(default)]
+    pub buildings: Vec<PuzzleBuilding>,
+    /// Everything the player gets to place. Puzzles don't use points
+    pub inventory: PuzzleInventory,
+    pub target: PuzzleTarget,
+    pub time_limit_seconds: u32,
+}
+
+#[derive(serde::Deserialize, Reflect, FromReflect, Clone, Debug)]
+pub struct PuzzleBuilding {
+    pub building: BuildingTypes,
+    pub position: (usize, usize),
+    pub owner: usize,
+}
+
+impl Puzzle {
+    /// Checks that the puzzle describes a board that can be played on
+    pub fn validate(&self) -> Result<(), String> {
+        if self.width() == 0 || self.height() == 0 {
+            return Err(String::from("The board is empty"));
+        }
+        for building in self.buildings.iter() {
+            if building.position.0 >= self.width() as usize
+                || building.position.1 >= self.height() as usize
+            {
+                return Err(format!(
+                    "The {:?} at {:?} is outside of the board",
+                    building.building, building.position
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.board
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0) as u32
+    }
+
+    pub fn height(&self) -> u32 {
+        self.board.len() as u32
+    }
+
+    /// The board as level tiles. Rows that are shorter than the widest one are padded with
+    /// noncolorable tiles
+    pub fn tiles(&self) -> Vec<Vec<TileType>> {
+        let width = self.width() as usize;
+        self.board
+            .iter()
+            .map(|row| {
+                let mut tiles: Vec<TileType> = row
+                    .chars()
+                    .map(|tile| match tile {
+                        '#' => TileType::NonColorable,
+                        'f' => TileType::Fertile,
+                        'h' => TileType::Hardened,
+                        'c' => TileType::Conduit,
+                        _ => TileType::Colorable,
+                    })
+                    .collect();
+                tiles.resize(width, TileType::NonColorable);
+                tiles
+            })
+            .collect()
+    }
+
+    pub fn owner(&self, x: usize, y: usize) -> Option<usize> {
+        self.owners
+            .get(y)
+            .and_then(|row| row.chars().nth(x))
+            .and_then(|owner| owner.to_digit(10))
+            .map(|owner| owner as usize)
+    }
+
+    pub fn strength(&self, x: usize, y: usize) -> u32 {
+        self.strengths
+            .get(y)
+            .and_then(|row| row.chars().nth(x))
+            .and_then(|strength| strength.to_digit(10))
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// The highest player id that owns anything on the board. Every id up to it gets a player
+    pub fn enemy_count(&self) -> usize {
+        let tile_owners = (0..self.owners.len())
+            .flat_map(|y| (0..self.width() as usize).filter_map(move |x| self.owner(x, y)));
+        self.buildings
+            .iter()
+            .map(|building| building.owner)
+            .chain(tile_owners)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Drops every puzzle that can't be played right after loading, so a broken entry is reported
+/// instead of panicking once someone starts it
+pub fn validate_puzzles(
+    puzzle_handle: Res<PuzzleHandle>,
+    mut puzzle_assets: ResMut<Assets<Puzzles>>,
+) {
+    let Some(puzzles) = puzzle_assets.get_mut(&puzzle_handle.puzzles) else {
+        return;
+    };
+    puzzles.puzzles.retain(|puzzle| match puzzle.validate() {
+        Ok(()) => true,
+        Err(error) => {
+            error!("Puzzle {} can't be played: {}", puzzle.name, error);
+            false
+        }
+    });
+}
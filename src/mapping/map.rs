@@ -1,4 +1,9 @@
-use crate::level_loader::{Level, TileType};
+use crate::buildings::line::LineDirection;
+use crate::buildings::{building_spawn_command, check_is_colorable, objective_spawn_command};
+use crate::game::{GameBuildSettings, GameData};
+use crate::level_loader::{Level, LevelObjectKind, LevelOwner, TileType};
+use crate::loading::puzzle_loader::Puzzle;
+use crate::mapping::HardenedTile;
 use bevy::prelude::{Mut, Reflect, Resource, World};
 use bevy_ecs_tilemap::prelude::*;
 use bevy_ggf::game_core::command::{GameCommand, GameCommands};
@@ -8,6 +13,7 @@ use bevy_ggf::mapping::tiles::{
 };
 use bevy_ggf::mapping::{Map, MapId, MapIdProvider};
 use bevy_ggf::movement::TerrainMovementCosts;
+use bevy_ggf::player::PlayerMarker;
 
 #[derive(Default, Resource)]
 pub struct MapTileStorage {
@@ -30,6 +36,14 @@ pub trait MapCommandsExt {
         colorable_tile_stack_rules: TileObjectStacks,
         non_colorable_tile_stack_rules: TileObjectStacks,
     ) -> SpawnMap;
+
+    fn spawn_puzzle(
+        &mut self,
+        map_terrain_vec: Vec<TerrainType>,
+        puzzle: Puzzle,
+        colorable_tile_stack_rules: TileObjectStacks,
+        non_colorable_tile_stack_rules: TileObjectStacks,
+    ) -> SpawnPuzzle;
 }
 
 impl MapCommandsExt for GameCommands {
@@ -75,6 +89,29 @@ impl MapCommandsExt for GameCommands {
             spawned_map_id: None,
         }
     }
+
+    fn spawn_puzzle(
+        &mut self,
+        map_terrain_type_vec: Vec<TerrainType>,
+        puzzle: Puzzle,
+        colorable_tile_stack_rules: TileObjectStacks,
+        non_colorable_tile_stack_rules: TileObjectStacks,
+    ) -> SpawnPuzzle {
+        self.queue.push(SpawnPuzzle {
+            map_terrain_type_vec: map_terrain_type_vec.clone(),
+            colorable_tile_stack_rules: colorable_tile_stack_rules.clone(),
+            non_colorable_tile_stack_rules: non_colorable_tile_stack_rules.clone(),
+            puzzle: puzzle.clone(),
+            spawned_map_id: None,
+        });
+        SpawnPuzzle {
+            map_terrain_type_vec,
+            colorable_tile_stack_rules,
+            non_colorable_tile_stack_rules,
+            puzzle,
+            spawned_map_id: None,
+        }
+    }
 }
 
 #[derive(Clone, Reflect)]
@@ -163,94 +200,321 @@ pub struct SpawnMap {
 
 impl GameCommand for SpawnMap {
     fn execute(&mut self, world: &mut World) -> Result<(), String> {
-        let map_size = TilemapSize {
-            x: self.level_data.tiles.get(0).unwrap().len() as u32,
-            y: self.level_data.tiles.len() as u32,
-        };
-        let mut tile_storage = TileStorage::empty(map_size);
-        let tilemap_type = TilemapType::Square;
-        let tilemap_entity = world.spawn_empty().id();
+        let (tile_storage, id) = spawn_level_tiles(
+            world,
+            &self.level_data.tiles,
+            &self.map_terrain_type_vec,
+            &self.colorable_tile_stack_rules,
+            &self.non_colorable_tile_stack_rules,
+            self.spawned_map_id,
+        );
+        self.spawned_map_id = Some(id);
 
-        world.resource_scope(|world, terrain_movement_costs: Mut<TerrainMovementCosts>| {
-            for y in self.level_data.tiles.iter().enumerate() {
-                for x in y.1.iter().enumerate() {
-                    let tile_pos = TilePos {
-                        x: x.0 as u32,
-                        y: y.0 as u32,
-                    };
-                    let tile_position: TilePosition = tile_pos.into();
+        self.spawn_level_contents(world, &tile_storage)
+    }
+}
+
+/// Spawns the tiles and the map entity for a grid of level tiles. Reuses `spawned_map_id` if the map
+/// has already been spawned before
+fn spawn_level_tiles(
+    world: &mut World,
+    tiles: &Vec<Vec<TileType>>,
+    map_terrain_type_vec: &Vec<TerrainType>,
+    colorable_tile_stack_rules: &TileObjectStacks,
+    non_colorable_tile_stack_rules: &TileObjectStacks,
+    spawned_map_id: Option<MapId>,
+) -> (TileStorage, MapId) {
+    let map_size = TilemapSize {
+        x: tiles.get(0).unwrap().len() as u32,
+        y: tiles.len() as u32,
+    };
+    let mut tile_storage = TileStorage::empty(map_size);
+    let tilemap_type = TilemapType::Square;
+    let tilemap_entity = world.spawn_empty().id();
 
-                    let tile_entity = match x.1 {
-                        TileType::Colorable => world
-                            .spawn(BggfTileBundle {
-                                tile: Tile,
-                                tile_terrain_info: TileTerrainInfo {
-                                    terrain_type: self.map_terrain_type_vec[0].clone(),
-                                },
-                                tile_pos,
-                                tilemap_id: TilemapId(tilemap_entity),
-                            })
-                            .insert(BggfTileObjectBundle {
-                                tile_stack_rules: self.colorable_tile_stack_rules.clone(),
-                                tile_objects: TileObjects::default(),
-                            })
-                            .insert(bevy_ggf::game_core::state::Changed::default())
-                            .insert(TileColor::default())
-                            .id(),
-                        TileType::NonColorable => world
-                            .spawn(BggfTileBundle {
-                                tile: Tile,
-                                tile_terrain_info: TileTerrainInfo {
-                                    terrain_type: self.map_terrain_type_vec[1].clone(),
-                                },
-                                tile_pos,
-                                tilemap_id: TilemapId(tilemap_entity),
-                            })
-                            .insert(BggfTileObjectBundle {
-                                tile_stack_rules: self.non_colorable_tile_stack_rules.clone(),
-                                tile_objects: TileObjects::default(),
-                            })
-                            .insert((
-                                bevy_ggf::game_core::state::Changed::default(),
-                                tile_position,
-                            ))
-                            .id(),
-                    };
+    world.resource_scope(|world, terrain_movement_costs: Mut<TerrainMovementCosts>| {
+        for y in tiles.iter().enumerate() {
+            for x in y.1.iter().enumerate() {
+                let tile_pos = TilePos {
+                    x: x.0 as u32,
+                    y: y.0 as u32,
+                };
+                let tile_position: TilePosition = tile_pos.into();
 
-                    tile_storage.set(&tile_pos, tile_entity);
+                let terrain_type = match x.1 {
+                    TileType::Colorable => &map_terrain_type_vec[0],
+                    TileType::NonColorable => &map_terrain_type_vec[1],
+                    TileType::Fertile => &map_terrain_type_vec[2],
+                    TileType::Hardened => &map_terrain_type_vec[3],
+                    TileType::Conduit => &map_terrain_type_vec[4],
+                };
+
+                let tile_entity = match x.1 {
+                    TileType::Colorable
+                    | TileType::Fertile
+                    | TileType::Hardened
+                    | TileType::Conduit => world
+                        .spawn(BggfTileBundle {
+                            tile: Tile,
+                            tile_terrain_info: TileTerrainInfo {
+                                terrain_type: terrain_type.clone(),
+                            },
+                            tile_pos,
+                            tilemap_id: TilemapId(tilemap_entity),
+                        })
+                        .insert(BggfTileObjectBundle {
+                            tile_stack_rules: colorable_tile_stack_rules.clone(),
+                            tile_objects: TileObjects::default(),
+                        })
+                        .insert(bevy_ggf::game_core::state::Changed::default())
+                        .insert(TileColor::default())
+                        .id(),
+                    TileType::NonColorable => world
+                        .spawn(BggfTileBundle {
+                            tile: Tile,
+                            tile_terrain_info: TileTerrainInfo {
+                                terrain_type: terrain_type.clone(),
+                            },
+                            tile_pos,
+                            tilemap_id: TilemapId(tilemap_entity),
+                        })
+                        .insert(BggfTileObjectBundle {
+                            tile_stack_rules: non_colorable_tile_stack_rules.clone(),
+                            tile_objects: TileObjects::default(),
+                        })
+                        .insert((
+                            bevy_ggf::game_core::state::Changed::default(),
+                            tile_position,
+                        ))
+                        .id(),
+                };
+                if let TileType::Hardened = x.1 {
+                    world
+                        .entity_mut(tile_entity)
+                        .insert(HardenedTile::default());
                 }
+
+                tile_storage.set(&tile_pos, tile_entity);
             }
-        });
+        }
+    });
 
-        let tile_size = TilemapTileSize { x: 16.0, y: 16.0 };
-        let grid_size: TilemapGridSize = tile_size.into();
-        let map_type = TilemapType::default();
+    let tile_size = TilemapTileSize { x: 16.0, y: 16.0 };
+    let grid_size: TilemapGridSize = tile_size.into();
+    let map_type = TilemapType::default();
 
-        // If we have already spawned this map in then just use that
-        let id = self.spawned_map_id.unwrap_or_else(|| {
-            let mut map_id_provider = world.resource_mut::<MapIdProvider>();
-            map_id_provider.next_id_component()
-        });
+    // If we have already spawned this map in then just use that
+    let id = spawned_map_id.unwrap_or_else(|| {
+        let mut map_id_provider = world.resource_mut::<MapIdProvider>();
+        map_id_provider.next_id_component()
+    });
 
-        //world.send_event::<MapSpawned>(MapSpawned { map_id: id });
+    //world.send_event::<MapSpawned>(MapSpawned { map_id: id });
 
-        world.insert_resource(MapTileStorage {
-            tile_storage: tile_storage.clone(),
-            tilemap_size: map_size.clone(),
-        });
+    world.insert_resource(MapTileStorage {
+        tile_storage: tile_storage.clone(),
+        tilemap_size: map_size.clone(),
+    });
 
-        world
-            .entity_mut(tilemap_entity)
-            .insert((grid_size, map_type, map_size, tile_storage, tile_size))
-            .insert(Map {
-                tilemap_type,
-                map_size,
-                tilemap_entity,
-            })
-            .insert(id);
+    world
+        .entity_mut(tilemap_entity)
+        .insert((
+            grid_size,
+            map_type,
+            map_size,
+            tile_storage.clone(),
+            tile_size,
+        ))
+        .insert(Map {
+            tilemap_type,
+            map_size,
+            tilemap_entity,
+        })
+        .insert(id);
+
+    (tile_storage, id)
+}
+
+impl SpawnMap {
+    /// Hands out the starting regions of the level and spawns its pre-placed objects
+    fn spawn_level_contents(
+        &self,
+        world: &mut World,
+        tile_storage: &TileStorage,
+    ) -> Result<(), String> {
+        let game_data = world.resource::<GameData>().clone();
+        let game_build_settings = world.resource::<GameBuildSettings>();
+        let enemy_count = game_build_settings.starting_enemy_count();
+        let tile_strength_rules = game_build_settings.tile_strength_rules;
+
+        let owner_id = |owner: LevelOwner| match owner {
+            LevelOwner::Player(player_id) if player_id <= enemy_count => Some(player_id),
+            LevelOwner::Player(_) => None,
+            LevelOwner::Neutral => game_data.neutral_player,
+        };
+
+        let claim_tile = |world: &mut World, tile_pos: TilePos, player_id: usize, strength: u32| {
+            let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+                return;
+            };
+            if !world
+                .get::<TileTerrainInfo>(tile_entity)
+                .map_or(false, check_is_colorable)
+            {
+                return;
+            }
+            let mut tile_color = tile_strength_rules.new_tile_color();
+            tile_color.strengthen(strength.saturating_sub(1));
+            world
+                .entity_mut(tile_entity)
+                .insert((PlayerMarker::new(player_id), tile_color));
+        };
+
+        for region in self.level_data.regions.iter() {
+            let Some(player_id) = owner_id(region.owner) else {
+                continue;
+            };
+            for x in region.min.0..=region.max.0 {
+                for y in region.min.1..=region.max.1 {
+                    claim_tile(
+                        world,
+                        TilePos::new(x as u32, y as u32),
+                        player_id,
+                        region.strength,
+                    );
+                }
+            }
+        }
+
+        for level_object in self.level_data.objects.iter() {
+            let Some(player_id) = owner_id(level_object.owner) else {
+                continue;
+            };
+            let tile_pos = TilePos::new(
+                level_object.position.0 as u32,
+                level_object.position.1 as u32,
+            );
+            // Pre-placed objects start on a fully strengthened tile of their owner so they aren't
+            // damaged before they get to do anything
+            claim_tile(world, tile_pos, player_id, tile_strength_rules.max_strength);
+
+            let mut spawn = match level_object.object {
+                LevelObjectKind::Building(building_type) => building_spawn_command(
+                    &game_data,
+                    building_type,
+                    LineDirection::default(),
+                    player_id,
+                    tile_pos,
+                    0,
+                ),
+                LevelObjectKind::Objective => {
+                    objective_spawn_command(&game_data, player_id, tile_pos)
+                }
+            };
+            spawn.execute(world)?;
+        }
 
+        Ok(())
+    }
+}
+
+/// Builds the board of a puzzle directly: every tile starts with the owner and strength the puzzle
+/// gives it and the pre-placed buildings take the place of spawn points
+#[derive(Clone, Reflect)]
+pub struct SpawnPuzzle {
+    map_terrain_type_vec: Vec<TerrainType>,
+    colorable_tile_stack_rules: TileObjectStacks,
+    non_colorable_tile_stack_rules: TileObjectStacks,
+    puzzle: Puzzle,
+    spawned_map_id: Option<MapId>,
+}
+
+impl GameCommand for SpawnPuzzle {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let (tile_storage, id) = spawn_level_tiles(
+            world,
+            &self.puzzle.tiles(),
+            &self.map_terrain_type_vec,
+            &self.colorable_tile_stack_rules,
+            &self.non_colorable_tile_stack_rules,
+            self.spawned_map_id,
+        );
         self.spawned_map_id = Some(id);
 
+        let game_data = world.resource::<GameData>().clone();
+        let tile_strength_rules = world.resource::<GameBuildSettings>().tile_strength_rules;
+
+        for x in 0..self.puzzle.width() {
+            for y in 0..self.puzzle.height() {
+                let Some(player_id) = self.puzzle.owner(x as usize, y as usize) else {
+                    continue;
+                };
+                let Some(tile_entity) = tile_storage.get(&TilePos::new(x, y)) else {
+                    continue;
+                };
+                if !world
+                    .get::<TileTerrainInfo>(tile_entity)
+                    .map_or(false, check_is_colorable)
+                {
+                    continue;
+                }
+                let mut tile_color = tile_strength_rules.new_tile_color();
+                tile_color.strengthen(self.puzzle.strength(x as usize, y as usize) - 1);
+                world
+                    .entity_mut(tile_entity)
+                    .insert((PlayerMarker::new(player_id), tile_color));
+            }
+        }
+
+        for building in self.puzzle.buildings.iter() {
+            building_spawn_command(
+                &game_data,
+                building.building,
+                LineDirection::default(),
+                building.owner,
+                TilePos::new(building.position.0 as u32, building.position.1 as u32),
+                0,
+            )
+            .execute(world)?;
+        }
+
         Ok(())
     }
 }
+
+/// Spaces `player_count` spawn points evenly around a square ring inset from the edges of a random
+/// map. Player 0 always starts in the bottom left corner and every other player follows around the
+/// ring so that each player gets the same amount of room.
+pub fn random_map_spawn_points(map_size: u32, player_count: usize) -> Vec<TilePos> {
+    let inset_count = map_size / 4;
+    let min = inset_count as f32;
+    let max = map_size.saturating_sub(inset_count) as f32;
+    let side_length = max - min;
+
+    // Maps too small to have a ring stack everyone on the same tile instead of dividing by zero
+    if side_length <= 0.0 {
+        return vec![TilePos::new(inset_count, inset_count); player_count];
+    }
+
+    let mut spawn_points = vec![];
+
+    for player_id in 0..player_count {
+        let distance = side_length * 4.0 * player_id as f32 / player_count as f32;
+        let side = (distance / side_length) as u32;
+        let offset = distance - side as f32 * side_length;
+
+        let (x, y) = match side {
+            0 => (min + offset, min),
+            1 => (max, min + offset),
+            2 => (max - offset, max),
+            _ => (min, max - offset),
+        };
+
+        spawn_points.push(TilePos {
+            x: x.round() as u32,
+            y: y.round() as u32,
+        });
+    }
+
+    spawn_points
+}
@@ -1,9 +1,10 @@
 use bevy::app::App;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::system::Resource;
-use bevy::prelude::Plugin;
+use bevy::prelude::{Component, Plugin};
 use bevy::utils::HashMap;
 use bevy_ecs_tilemap::tiles::TilePos;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
 
 pub mod map;
 
@@ -19,3 +20,34 @@ impl Plugin for MapPlugin {
 pub struct MapTileIndex {
     pub hashmap: HashMap<TilePos, Entity>,
 }
+
+/// How many times a fully strengthened tile of this terrain counts towards the income of its owner
+pub fn terrain_income_multiplier(tile_terrain_info: &TileTerrainInfo) -> u32 {
+    match tile_terrain_info.terrain_type.name.as_str() {
+        "Fertile" => 2,
+        _ => 1,
+    }
+}
+
+/// Inserted onto hardened tiles. Remembers whether the tile absorbed the last hit so that only every
+/// second hit takes away a strength level
+#[derive(Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Component)]
+pub struct HardenedTile {
+    pub absorbed_hit: bool,
+}
+
+impl HardenedTile {
+    /// Returns true if the tile absorbs the hit and shouldn't lose any strength
+    pub fn absorb_hit(&mut self) -> bool {
+        self.absorbed_hit = !self.absorbed_hit;
+        self.absorbed_hit
+    }
+}
+
+/// How much of its range a building footprint uses up to reach into a tile of this terrain
+pub fn terrain_step_cost(tile_terrain_info: &TileTerrainInfo) -> u32 {
+    match tile_terrain_info.terrain_type.name.as_str() {
+        "Conduit" => 0,
+        _ => 1,
+    }
+}
@@ -6,7 +6,7 @@ use bevy::{
         component::Component,
         entity::Entity,
         query::{Added, With, Without},
-        system::{Query, ResMut, Resource, SystemState},
+        system::{Commands, Query, ResMut, Resource, SystemState},
         world::{Mut, World},
     },
     utils::HashMap,
@@ -21,6 +21,7 @@ use bevy_ggf::{
 };
 
 use crate::{
+    buildings::Simulate,
     color_system::ColorConflictCallback,
     pathfinding::{
         AddObjectToTileToObjectIndex, IsColorableNodeCheck, RemoveObjectFromTileToObjectIndex,
@@ -52,6 +53,48 @@ pub struct TileToObjectIndex {
     pub map: HashMap<TilePos, Vec<ObjectId>>,
 }
 
+/// Wakes up every sleeping object that can affect the given tile so that it is simulated again
+pub fn wake_objects_on_tile(
+    tile_pos: &TilePos,
+    tile_to_object_index: &TileToObjectIndex,
+    object_index: &ObjectIndex,
+    object_query: &Query<(Entity, Option<&Simulate>), With<ObjectId>>,
+    commands: &mut Commands,
+) {
+    let Some(object_vec) = tile_to_object_index.map.get(tile_pos) else {
+        return;
+    };
+    for object_id in object_vec.iter() {
+        if let Some(entity) = object_index.hashmap.get(object_id) {
+            if let Ok((entity, opt_simulate)) = object_query.get(*entity) {
+                if opt_simulate.is_none() {
+                    commands.entity(entity).insert(Simulate);
+                }
+            }
+        }
+    }
+}
+
+/// Removes the object from every tile in its [`ObjectCachedMap`] and removes the cache so that
+/// [`simulate_simple_pathfind_object_cache`] recomputes both for it
+pub fn invalidate_object_cache(
+    entity: Entity,
+    object_id: &ObjectId,
+    object_cached_map: &ObjectCachedMap,
+    tile_to_object_index: &mut TileToObjectIndex,
+    commands: &mut Commands,
+) {
+    for tile_position in object_cached_map.cache.iter() {
+        if let Some(index_entry) = tile_to_object_index
+            .map
+            .get_mut(&Into::<TilePos>::into(*tile_position))
+        {
+            index_entry.retain(|element| element != object_id);
+        }
+    }
+    commands.entity(entity).remove::<ObjectCachedMap>();
+}
+
 pub fn update_objects_index(
     mut object_index: ResMut<ObjectIndex>,
     obejcts_query: Query<(Entity, &ObjectId), Added<ObjectId>>,
@@ -111,6 +154,10 @@ pub fn simulate_simple_pathfind_object_cache<
                 pathfind_map.add_to_index(object_id, &mut tile_to_object_index, &mut btree_cache);
 
                 for vec in btree_cache.iter_mut() {
+                    // Pathfind maps iterate in a random order, sorting keeps every roll over the
+                    // cache the same between games with the same seed
+                    vec.1
+                        .sort_unstable_by_key(|tile_pos| (tile_pos.x, tile_pos.y));
                     let mut converted = vec
                         .1
                         .iter()
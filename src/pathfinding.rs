@@ -1,6 +1,7 @@
 ﻿use std::collections::BTreeMap;
 
-use crate::color_system::{TileColor, TileColorStrength};
+use crate::buildings::check_is_colorable;
+use crate::color_system::TileColor;
 use crate::objects::{ObjectCachedMap, TileToObjectIndex};
 use bevy::ecs::system::SystemState;
 use bevy::prelude::{Entity, Query, Resource, With, World};
@@ -69,9 +70,7 @@ pub fn check_if_tile_is_colorable(
             return false;
         }
         return if let Some((player_marker, tile_color)) = options.as_ref() {
-            if player_marker.id() == player_id
-                && TileColorStrength::Five == tile_color.tile_color_strength
-            {
+            if player_marker.id() == player_id && tile_color.max_strength() {
                 world.insert_resource(system_state);
                 return false;
             }
@@ -117,7 +116,7 @@ impl TileMoveCheck for IsColorableNodeCheck {
             world.insert_resource(system_state);
             return false;
         };
-        let bool = tile_terrain_info.terrain_type.name == String::from("BasicColorable");
+        let bool = check_is_colorable(tile_terrain_info);
         world.insert_resource(system_state);
         bool
     }
@@ -184,3 +183,49 @@ impl TileMoveCheck for NodeIsPlayersCheck {
         return false;
     }
 }
+
+#[derive(Resource)]
+pub struct NodeIsNotEnemyCheckQueryState {
+    pub query: SystemState<(
+        Query<'static, 'static, &'static PlayerMarker, With<ObjectId>>,
+        Query<'static, 'static, Option<&'static PlayerMarker>, With<Tile>>,
+    )>,
+}
+
+/// Only allows moving into tiles that are neutral or owned by the moving objects player
+pub struct NodeIsNotEnemyCheck;
+
+impl TileMoveCheck for NodeIsNotEnemyCheck {
+    fn is_valid_move(
+        &self,
+        moving_entity: Entity,
+        tile_entity: Entity,
+        _checking_tile_pos: &TilePos,
+        _move_from_tile_pos: &TilePos,
+        world: &mut World,
+    ) -> bool {
+        let mut system_state = match world.remove_resource::<NodeIsNotEnemyCheckQueryState>() {
+            None => {
+                let system_state: SystemState<(
+                    Query<&PlayerMarker, With<ObjectId>>,
+                    Query<Option<&PlayerMarker>, With<Tile>>,
+                )> = SystemState::new(world);
+                NodeIsNotEnemyCheckQueryState {
+                    query: system_state,
+                }
+            }
+            Some(res) => res,
+        };
+        let (object_query, tile_query) = system_state.query.get(world);
+
+        let valid = match (object_query.get(moving_entity), tile_query.get(tile_entity)) {
+            (Ok(player_marker), Ok(Some(tile_player_marker))) => {
+                player_marker.id() == tile_player_marker.id()
+            }
+            (Ok(_), Ok(None)) => true,
+            _ => false,
+        };
+        world.insert_resource(system_state);
+        valid
+    }
+}
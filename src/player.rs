@@ -1,8 +1,12 @@
-use crate::color_system::{increase_building_points, TileColor, TileColorStrength};
+use crate::color_system::{increase_building_points, TileColor};
+use crate::game::game_rng::GameRng;
+use crate::game::puzzle::PuzzleState;
+use crate::mapping::terrain_income_multiplier;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use bevy::window::PrimaryWindow;
 use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::mapping::tiles::Tile;
 use bevy_ggf::player::{Player, PlayerMarker};
 use ns_defaults::camera::CursorWorldPos;
@@ -38,6 +42,29 @@ impl SaveId for PlayerPoints {
     }
 }
 
+impl PlayerPoints {
+    /// Building points stop growing once a player has this many
+    pub const MAX_BUILDING_POINTS: u32 = 200;
+    /// Ability points stop growing once a player has this many
+    pub const MAX_ABILITY_POINTS: u32 = 150;
+
+    /// Gives the player building points without going over [`PlayerPoints::MAX_BUILDING_POINTS`]
+    pub fn add_building_points(&mut self, amount: u32) {
+        self.building_points = self
+            .building_points
+            .saturating_add(amount)
+            .min(PlayerPoints::MAX_BUILDING_POINTS);
+    }
+
+    /// Gives the player ability points without going over [`PlayerPoints::MAX_ABILITY_POINTS`]
+    pub fn add_ability_points(&mut self, amount: u32) {
+        self.ability_points = self
+            .ability_points
+            .saturating_add(amount)
+            .min(PlayerPoints::MAX_ABILITY_POINTS);
+    }
+}
+
 /// This plugin handles player related stuff like movement
 /// Player logic is only active during the State `GameState::Playing`
 impl Plugin for PlayerPlugin {
@@ -45,27 +72,36 @@ impl Plugin for PlayerPlugin {
 }
 
 pub fn update_player_points(
-    tile_query: Query<(&Tile, &PlayerMarker, &TileColor)>,
+    tile_query: Query<(&Tile, &TileTerrainInfo, &PlayerMarker, &TileColor)>,
     mut player_query: Query<(Entity, &mut PlayerPoints, &Player)>,
     mut points_timer: Local<Timer>,
     time: Res<Time>,
+    puzzle_state: Option<Res<PuzzleState>>,
+    mut rng: ResMut<GameRng>,
     mut commands: Commands,
 ) {
+    // Puzzles hand out a fixed inventory instead of points
+    if puzzle_state.is_some() {
+        return;
+    }
     points_timer.tick(time.delta());
     if points_timer.finished() {
         let mut player_points_hashmap: HashMap<usize, u32> = HashMap::new();
-        for (_, tile_marker, tile_color) in tile_query.iter() {
-            if let TileColorStrength::Five = tile_color.tile_color_strength {
+        for (_, tile_terrain_info, tile_marker, tile_color) in tile_query.iter() {
+            if tile_color.max_strength() {
                 let count = player_points_hashmap.entry(tile_marker.id()).or_insert(0);
                 let count = *count;
-                player_points_hashmap.insert(tile_marker.id(), count.saturating_add(1));
+                player_points_hashmap.insert(
+                    tile_marker.id(),
+                    count.saturating_add(terrain_income_multiplier(tile_terrain_info)),
+                );
             }
         }
 
         for (entity, mut player_points, player_id) in player_query.iter_mut() {
             let points = *player_points_hashmap.entry(player_id.id()).or_insert(0) / 16;
             for _ in 0..points {
-                increase_building_points(&mut player_points);
+                increase_building_points(&mut player_points, &mut rng);
             }
         }
         points_timer.set_duration(Duration::from_secs_f32(1.0));
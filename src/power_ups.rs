@@ -0,0 +1,386 @@
+//! Optional power-ups. Pickups periodically spawn on neutral or contested tiles and are claimed by
+//! the first player to strengthen the tile they sit on enough
+
+use crate::abilities::ABILITY_COST;
+use crate::buildings::{check_is_colorable, get_neighbors_tilepos};
+use crate::color_system::TileColor;
+use crate::game::game_rng::GameRng;
+use crate::game::{GameBuildSettings, GameData};
+use crate::mapping::map::MapTileStorage;
+use crate::player::PlayerPoints;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{
+    Commands, Component, Entity, FromReflect, Query, Reflect, Res, Resource, Timer, TimerMode,
+    With, Without, World,
+};
+use bevy::time::Time;
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::command::GameCommand;
+use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks, TileObjects};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{Object, ObjectGridPosition, ObjectIdProvider, ObjectInfo};
+use bevy_ggf::player::{Player, PlayerMarker};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+/// The least amount of ticks between two pickups spawning
+const POWER_UP_SPAWN_INTERVAL: u32 = 400;
+
+/// The most pickups that can be on the board at once
+const MAX_POWER_UPS: usize = 3;
+
+/// The strength a player needs on the tile of a pickup to claim it. Capped to the max strength of
+/// the current tile strength rules
+const POWER_UP_CLAIM_STRENGTH: u32 = 3;
+
+#[derive(Default, Resource)]
+pub struct PowerUpSpawner {
+    ticks_since_spawn: u32,
+}
+
+/// A pickup waiting on the board to be claimed
+#[derive(
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct PowerUp {
+    pub reward: PowerUpReward,
+}
+
+impl SaveId for PowerUp {
+    fn save_id(&self) -> BinaryComponentId {
+        30
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        30
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+#[derive(
+    Default, Clone, Copy, Eq, Hash, Debug, PartialEq, Reflect, FromReflect, Serialize, Deserialize,
+)]
+pub enum PowerUpReward {
+    /// Gives building points
+    #[default]
+    BonusPoints,
+    /// Gives enough ability points for a single ability
+    AbilityCharge,
+    /// Every building of the player recharges twice as fast for a while
+    Overclock,
+}
+
+pub const POWER_UP_REWARDS: [PowerUpReward; 3] = [
+    PowerUpReward::BonusPoints,
+    PowerUpReward::AbilityCharge,
+    PowerUpReward::Overclock,
+];
+
+impl PowerUpReward {
+    /// The name of the object type the pickup is spawned as
+    pub fn object_type_name(&self) -> &'static str {
+        match self {
+            PowerUpReward::BonusPoints => "BonusPoints",
+            PowerUpReward::AbilityCharge => "AbilityCharge",
+            PowerUpReward::Overclock => "Overclock",
+        }
+    }
+}
+
+/// Put on a player while their buildings recharge faster. Removed once the timer runs out
+#[derive(Default, Clone, Debug, Component, Reflect, FromReflect, Serialize, Deserialize)]
+pub struct Overclock {
+    pub timer: Timer,
+}
+
+impl SaveId for Overclock {
+    fn save_id(&self) -> BinaryComponentId {
+        32
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        32
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+impl Overclock {
+    /// How many times faster the buildings of an overclocked player recharge
+    pub const COOLDOWN_MULTIPLIER: u32 = 2;
+}
+
+fn power_up_claim_strength(game_settings: &GameBuildSettings) -> u32 {
+    POWER_UP_CLAIM_STRENGTH.min(game_settings.tile_strength_rules.max_strength)
+}
+
+/// Spawns a random pickup every [`POWER_UP_SPAWN_INTERVAL`] ticks on a neutral tile or on a weak
+/// tile bordering another player
+pub fn spawn_power_ups(world: &mut World) {
+    let game_settings = world.resource::<GameBuildSettings>();
+    if !game_settings.power_ups {
+        return;
+    }
+    let claim_strength = power_up_claim_strength(game_settings);
+
+    let mut power_up_spawner = world.resource_mut::<PowerUpSpawner>();
+    power_up_spawner.ticks_since_spawn = power_up_spawner.ticks_since_spawn.saturating_add(1);
+    if power_up_spawner.ticks_since_spawn < POWER_UP_SPAWN_INTERVAL {
+        return;
+    }
+    power_up_spawner.ticks_since_spawn = 0;
+
+    let game_data = world.resource::<GameData>();
+    let power_up_stacking_class = ObjectStackingClass {
+        stack_class: game_data.stacking_classes.get("PowerUp").unwrap().clone(),
+    };
+
+    let mut system_state: SystemState<(
+        Query<&PowerUp, (With<Object>, Without<DespawnObject>)>,
+        Query<
+            (
+                &TilePos,
+                &TileTerrainInfo,
+                &TileObjectStacks,
+                Option<(&PlayerMarker, &TileColor)>,
+            ),
+            With<Tile>,
+        >,
+        Query<(&MapId, &TileStorage)>,
+    )> = SystemState::new(world);
+    let (power_ups, tiles, tile_storage_query) = system_state.get(world);
+
+    if power_ups.iter().count() >= MAX_POWER_UPS {
+        return;
+    }
+
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    let candidates: Vec<TilePos> = tiles
+        .iter()
+        .filter(
+            |(tile_pos, tile_terrain_info, tile_object_stacks, tile_options)| {
+                if !check_is_colorable(tile_terrain_info)
+                    || !tile_object_stacks.has_space(&power_up_stacking_class)
+                {
+                    return false;
+                }
+                let Some((tile_player_marker, tile_color)) = tile_options else {
+                    return true;
+                };
+                tile_color.strength < claim_strength
+                    && get_neighbors_tilepos(**tile_pos, &tile_storage.size)
+                        .iter()
+                        .filter_map(|neighbor| tile_storage.get(neighbor))
+                        .filter_map(|neighbor_entity| tiles.get(neighbor_entity).ok())
+                        .any(|(_, _, _, neighbor_options)| {
+                            neighbor_options.map_or(false, |(neighbor_player_marker, _)| {
+                                neighbor_player_marker != *tile_player_marker
+                            })
+                        })
+            },
+        )
+        .map(|(tile_pos, _, _, _)| *tile_pos)
+        .collect();
+
+    let mut rng = world.resource_mut::<GameRng>();
+    let Some(tile_pos) = candidates.choose(&mut rng.0).copied() else {
+        return;
+    };
+    let reward = *POWER_UP_REWARDS.choose(&mut rng.0).unwrap();
+
+    let _ = SpawnPowerUp {
+        reward,
+        target_tile_pos: tile_pos,
+    }
+    .execute(world);
+}
+
+/// Spawns a pickup on the target tile. Pickups don't belong to any player so unlike objects spawned
+/// through `spawn_object` they are spawned without a [`PlayerMarker`]
+#[derive(Reflect, FromReflect, Clone)]
+pub struct SpawnPowerUp {
+    pub reward: PowerUpReward,
+    pub target_tile_pos: TilePos,
+}
+
+impl GameCommand for SpawnPowerUp {
+    fn execute(&mut self, world: &mut World) -> Result<(), String> {
+        let game_data = world.resource::<GameData>();
+        let power_up_stacking_class = ObjectStackingClass {
+            stack_class: game_data.stacking_classes.get("PowerUp").unwrap().clone(),
+        };
+        let object_type = game_data
+            .object_types
+            .get(self.reward.object_type_name())
+            .unwrap()
+            .clone();
+
+        let tile_storage = &world.resource::<MapTileStorage>().tile_storage;
+        let Some(tile_entity) = tile_storage.get(&self.target_tile_pos) else {
+            return Err(String::from("Target is outside of the map"));
+        };
+        if !world
+            .get::<TileObjectStacks>(tile_entity)
+            .map_or(false, |tile_object_stacks| {
+                tile_object_stacks.has_space(&power_up_stacking_class)
+            })
+        {
+            return Err(String::from("No space for a pickup on the target tile"));
+        }
+
+        let object_id = world.resource_mut::<ObjectIdProvider>().next_id_component();
+        let power_up_entity = world
+            .spawn((
+                ObjectGridPosition {
+                    tile_position: self.target_tile_pos.into(),
+                },
+                power_up_stacking_class.clone(),
+                Object,
+                ObjectInfo { object_type },
+                PowerUp {
+                    reward: self.reward,
+                },
+                object_id,
+                Changed::default(),
+            ))
+            .id();
+
+        let mut tile = world.entity_mut(tile_entity);
+        if let Some(mut tile_objects) = tile.get_mut::<TileObjects>() {
+            tile_objects.add_object(power_up_entity);
+        }
+        if let Some(mut tile_object_stacks) = tile.get_mut::<TileObjectStacks>() {
+            tile_object_stacks.increment_object_class_count(&power_up_stacking_class);
+        }
+        tile.insert(Changed::default());
+
+        Ok(())
+    }
+}
+
+/// Hands every pickup whose tile is owned at the claim strength to the owner of the tile. Pickups on
+/// tiles that can't be colored anymore are removed
+pub fn claim_power_ups(
+    power_ups: Query<
+        (Entity, &ObjectGridPosition, &ObjectStackingClass, &PowerUp),
+        (With<Object>, Without<DespawnObject>),
+    >,
+    mut tiles: Query<
+        (
+            &TileTerrainInfo,
+            Option<(&PlayerMarker, &TileColor)>,
+            &mut TileObjectStacks,
+        ),
+        With<Tile>,
+    >,
+    mut players: Query<(Entity, &Player, &mut PlayerPoints)>,
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    game_settings: Res<GameBuildSettings>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+    let claim_strength = power_up_claim_strength(&game_settings);
+
+    for (entity, object_grid_position, object_stacking_class, power_up) in power_ups.iter() {
+        let Some(tile_entity) = tile_storage.get(&object_grid_position.tile_position.into()) else {
+            continue;
+        };
+        let Ok((tile_terrain_info, tile_options, mut tile_object_stacks)) =
+            tiles.get_mut(tile_entity)
+        else {
+            continue;
+        };
+        if !check_is_colorable(tile_terrain_info) {
+            commands.entity(entity).insert(DespawnObject);
+            tile_object_stacks.decrement_object_class_count(object_stacking_class);
+            continue;
+        }
+        let Some((tile_player_marker, tile_color)) = tile_options else {
+            continue;
+        };
+        if tile_color.strength < claim_strength {
+            continue;
+        }
+
+        // The neutral faction doesn't have any points and leaves pickups alone
+        let Some((player_entity, _, mut player_points)) = players
+            .iter_mut()
+            .find(|(_, player, _)| player.id() == tile_player_marker.id())
+        else {
+            continue;
+        };
+
+        match power_up.reward {
+            PowerUpReward::BonusPoints => {
+                player_points.add_building_points(25);
+            }
+            PowerUpReward::AbilityCharge => {
+                player_points.add_ability_points(ABILITY_COST);
+            }
+            PowerUpReward::Overclock => {
+                commands.entity(player_entity).insert(Overclock {
+                    timer: Timer::from_seconds(15.0, TimerMode::Once),
+                });
+            }
+        }
+        commands.entity(player_entity).insert(Changed::default());
+
+        commands.entity(entity).insert(DespawnObject);
+        tile_object_stacks.decrement_object_class_count(object_stacking_class);
+    }
+}
+
+pub fn update_overclocks(
+    mut overclocks: Query<(Entity, &mut Overclock)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut overclock) in overclocks.iter_mut() {
+        overclock.timer.tick(time.delta());
+        if overclock.timer.finished() {
+            commands
+                .entity(entity)
+                .remove::<Overclock>()
+                .insert(Changed::default());
+        }
+    }
+}
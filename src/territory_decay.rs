@@ -0,0 +1,165 @@
+//! Optional supply rule. Owned tiles that aren't connected through tiles of the same owner to one of
+//! that owners buildings slowly lose strength until they turn neutral.
+//!
+//! Cut off tiles don't decay every tick. The flood fill is spread over several ticks and a decay
+//! only happens once a fill is done and at least [`SUPPLY_DECAY_INTERVAL`] ticks passed since the
+//! last one, so an island at full strength takes a few seconds to go neutral instead of a few ticks
+
+use crate::buildings::{BuildingMarker, Simulate};
+use crate::color_system::{PlayerTileChangedCount, TileColor};
+use crate::game::GameBuildSettings;
+use crate::objects::{wake_objects_on_tile, ObjectIndex, TileToObjectIndex};
+use bevy::prelude::{Changed, Commands, Entity, Query, Res, ResMut, Resource, With, Without};
+use bevy::utils::{HashMap, HashSet};
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::mapping::tiles::Tile;
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{ObjectGridPosition, ObjectId};
+use bevy_ggf::player::PlayerMarker;
+use std::collections::VecDeque;
+
+/// How many tiles the supply flood fill visits every tick. The fill is spread over as many ticks as
+/// it needs so large maps don't stall a single tick
+const SUPPLY_TILES_PER_TICK: usize = 512;
+
+/// The least amount of ticks between two decays. Decaying every tick would wipe out any tile that
+/// is cut off for a moment, like a corridor that gets broken and retaken in the same fight
+const SUPPLY_DECAY_INTERVAL: u32 = 30;
+
+/// The state of the supply flood fill, which is carried over between ticks
+#[derive(Default, Resource)]
+pub struct TerritorySupply {
+    /// Every tile the running fill reached and the player that supplies it
+    supplied: HashMap<TilePos, usize>,
+    frontier: VecDeque<(TilePos, usize)>,
+    /// Tiles that changed owner since the last decay. They are spared from the next decay as the
+    /// fill might have passed them before they were taken
+    changed_while_filling: HashSet<TilePos>,
+    filling: bool,
+    ticks_since_decay: u32,
+}
+
+/// Runs a part of the supply flood fill every tick. Once the fill is done every owned tile it didn't
+/// reach, and that didn't change owner since the last decay, loses one strength level
+pub fn update_territory_supply(
+    mut territory_supply: ResMut<TerritorySupply>,
+    game_settings: Res<GameBuildSettings>,
+    mut player_tiles_changed_count: ResMut<PlayerTileChangedCount>,
+    buildings: Query<
+        (&PlayerMarker, &ObjectGridPosition),
+        (With<BuildingMarker>, Without<DespawnObject>, Without<Tile>),
+    >,
+    mut tiles: Query<(Entity, &TilePos, Option<(&PlayerMarker, &mut TileColor)>), With<Tile>>,
+    changed_tiles: Query<&TilePos, (With<Tile>, Changed<PlayerMarker>)>,
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    tile_to_object_index: Res<TileToObjectIndex>,
+    object_index: Res<ObjectIndex>,
+    object_query: Query<(Entity, Option<&Simulate>), With<ObjectId>>,
+    mut commands: Commands,
+) {
+    if !game_settings.territory_decay {
+        return;
+    }
+
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    let territory_supply = territory_supply.as_mut();
+    territory_supply.ticks_since_decay = territory_supply.ticks_since_decay.saturating_add(1);
+    // Changes are collected every tick, otherwise the ones from the tick a fill starts on are lost
+    territory_supply
+        .changed_while_filling
+        .extend(changed_tiles.iter().copied());
+
+    if !territory_supply.filling {
+        if territory_supply.ticks_since_decay < SUPPLY_DECAY_INTERVAL {
+            return;
+        }
+        territory_supply.supplied.clear();
+        territory_supply.frontier.clear();
+        for (player_marker, object_grid_position) in buildings.iter() {
+            let tile_pos: TilePos = object_grid_position.tile_position.into();
+            territory_supply
+                .frontier
+                .push_back((tile_pos, player_marker.id()));
+        }
+        territory_supply.filling = true;
+    }
+
+    let mut visited = 0;
+    while visited < SUPPLY_TILES_PER_TICK {
+        let Some((tile_pos, player_id)) = territory_supply.frontier.pop_front() else {
+            break;
+        };
+        visited += 1;
+
+        if territory_supply.supplied.contains_key(&tile_pos) {
+            continue;
+        }
+        let Some(tile_entity) = tile_storage.get(&tile_pos) else {
+            continue;
+        };
+        let Ok((_, _, Some((tile_player_marker, _)))) = tiles.get(tile_entity) else {
+            continue;
+        };
+        if tile_player_marker.id() != player_id {
+            continue;
+        }
+        territory_supply.supplied.insert(tile_pos, player_id);
+
+        for (step_x, step_y) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
+            if let Some(neighbor) = TilePos::from_i32_pair(
+                tile_pos.x as i32 + step_x,
+                tile_pos.y as i32 + step_y,
+                &tile_storage.size,
+            ) {
+                if !territory_supply.supplied.contains_key(&neighbor) {
+                    territory_supply.frontier.push_back((neighbor, player_id));
+                }
+            }
+        }
+    }
+
+    if !territory_supply.frontier.is_empty() {
+        return;
+    }
+
+    for (entity, tile_pos, options) in tiles.iter_mut() {
+        let Some((tile_player_marker, mut tile_color)) = options else {
+            continue;
+        };
+        if territory_supply.supplied.get(tile_pos) == Some(&tile_player_marker.id())
+            || territory_supply.changed_while_filling.contains(tile_pos)
+        {
+            continue;
+        }
+
+        tile_color.damage(1);
+        // Sleeping objects around the tile might be able to act on it again
+        wake_objects_on_tile(
+            tile_pos,
+            &tile_to_object_index,
+            &object_index,
+            &object_query,
+            &mut commands,
+        );
+        if tile_color.is_neutral() {
+            if tile_player_marker.id() == 0 {
+                player_tiles_changed_count.player_lost_tiles = player_tiles_changed_count
+                    .player_lost_tiles
+                    .saturating_add(1);
+            }
+            commands.entity(entity).remove::<PlayerMarker>();
+            commands.entity(entity).remove::<TileColor>();
+        }
+    }
+
+    territory_supply.filling = false;
+    territory_supply.changed_while_filling.clear();
+    territory_supply.ticks_since_decay = 0;
+}
@@ -0,0 +1,41 @@
+//! Helpers shared by the unit tests that run the simulation systems on a bare world. Game time
+//! only moves when a test advances it, so tests never depend on how much real time passes
+
+use crate::buildings::{Simulate, TimeSimulatingStopped};
+use crate::game::SIMULATION_TICK_SECONDS;
+use bevy::ecs::schedule::ExecutorKind;
+use bevy::prelude::{apply_system_buffers, Entity, IntoSystemConfigs, Schedule, Time, World};
+use std::time::Duration;
+
+/// The game time a single simulation tick advances
+pub fn tick() -> Duration {
+    Duration::from_secs_f32(SIMULATION_TICK_SECONDS)
+}
+
+/// A world with a fresh [`Time`] and a single threaded schedule running the given systems
+pub fn timer_world<M>(systems: impl IntoSystemConfigs<M>) -> (World, Schedule) {
+    let mut world = World::new();
+    world.insert_resource(Time::default());
+    let mut schedule = Schedule::new();
+    schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+    schedule.add_systems((systems, apply_system_buffers).chain());
+    (world, schedule)
+}
+
+/// Advances the game world time one simulation tick at a time, the same way `simulate_game` does
+pub fn step(world: &mut World, schedule: &mut Schedule, ticks: u32) {
+    for _ in 0..ticks {
+        let mut time = world.resource_mut::<Time>();
+        let next_update = time.last_update().unwrap_or(time.startup()) + tick();
+        time.update_with_instant(next_update);
+        schedule.run(world);
+    }
+}
+
+/// Stops simulating the entity the same way a building with nothing left to do goes to sleep
+pub fn put_to_sleep(world: &mut World, entity: Entity) {
+    let time_simulating_stopped = TimeSimulatingStopped::new(world.resource::<Time>());
+    let mut entity = world.entity_mut(entity);
+    entity.remove::<Simulate>();
+    entity.insert(time_simulating_stopped);
+}
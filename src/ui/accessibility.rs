@@ -0,0 +1,195 @@
+use std::fmt::Display;
+
+use bevy::prelude::*;
+
+/// Accessibility options that change how ownership is shown on the board
+#[derive(Resource, Default, Clone, Debug, PartialEq)]
+pub struct AccessibilitySettings {
+    /// Draws a per player pattern over owned tiles so ownership doesn't rely on color alone
+    pub ownership_patterns: bool,
+    /// Which color vision deficiency the main menu palette preview simulates
+    pub vision_preview: ColorVisionSimulation,
+}
+
+impl AccessibilitySettings {
+    pub fn toggle_ownership_patterns(&mut self) {
+        self.ownership_patterns = !self.ownership_patterns;
+    }
+}
+
+/// The pattern drawn over tiles owned by a player when [`AccessibilitySettings::ownership_patterns`]
+/// is on. Each player gets a different one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OwnershipPattern {
+    HorizontalStripes,
+    Dots,
+    Cross,
+    DiagonalStripes,
+    DiagonalCross,
+    VerticalStripes,
+    CenterDot,
+    Frame,
+}
+
+impl OwnershipPattern {
+    pub fn for_player(player_id: usize) -> OwnershipPattern {
+        match player_id % 8 {
+            0 => OwnershipPattern::HorizontalStripes,
+            1 => OwnershipPattern::Dots,
+            2 => OwnershipPattern::Cross,
+            3 => OwnershipPattern::DiagonalStripes,
+            4 => OwnershipPattern::DiagonalCross,
+            5 => OwnershipPattern::VerticalStripes,
+            6 => OwnershipPattern::CenterDot,
+            _ => OwnershipPattern::Frame,
+        }
+    }
+
+    /// The rectangles making up the pattern as (offset, size, rotation) relative to the center of a
+    /// tile of `tile_size`
+    pub fn shapes(&self, tile_size: f32) -> Vec<(Vec2, Vec2, f32)> {
+        let line = tile_size * 0.12;
+        let dot = tile_size * 0.2;
+        let quarter = tile_size * 0.25;
+        let diagonal = tile_size * std::f32::consts::SQRT_2 * 0.8;
+        let angle = std::f32::consts::FRAC_PI_4;
+
+        match self {
+            OwnershipPattern::HorizontalStripes => vec![
+                (Vec2::new(0.0, quarter), Vec2::new(tile_size, line), 0.0),
+                (Vec2::new(0.0, -quarter), Vec2::new(tile_size, line), 0.0),
+            ],
+            OwnershipPattern::Dots => vec![
+                (Vec2::new(quarter, quarter), Vec2::splat(dot), 0.0),
+                (Vec2::new(-quarter, quarter), Vec2::splat(dot), 0.0),
+                (Vec2::new(quarter, -quarter), Vec2::splat(dot), 0.0),
+                (Vec2::new(-quarter, -quarter), Vec2::splat(dot), 0.0),
+            ],
+            OwnershipPattern::Cross => vec![
+                (Vec2::ZERO, Vec2::new(tile_size, line), 0.0),
+                (Vec2::ZERO, Vec2::new(line, tile_size), 0.0),
+            ],
+            OwnershipPattern::DiagonalStripes => vec![
+                (Vec2::ZERO, Vec2::new(diagonal, line), angle),
+                (
+                    Vec2::new(-quarter, quarter),
+                    Vec2::new(diagonal * 0.5, line),
+                    angle,
+                ),
+                (
+                    Vec2::new(quarter, -quarter),
+                    Vec2::new(diagonal * 0.5, line),
+                    angle,
+                ),
+            ],
+            OwnershipPattern::DiagonalCross => vec![
+                (Vec2::ZERO, Vec2::new(diagonal, line), angle),
+                (Vec2::ZERO, Vec2::new(diagonal, line), -angle),
+            ],
+            OwnershipPattern::VerticalStripes => vec![
+                (Vec2::new(quarter, 0.0), Vec2::new(line, tile_size), 0.0),
+                (Vec2::new(-quarter, 0.0), Vec2::new(line, tile_size), 0.0),
+            ],
+            OwnershipPattern::CenterDot => vec![(Vec2::ZERO, Vec2::splat(dot * 2.0), 0.0)],
+            OwnershipPattern::Frame => vec![
+                (
+                    Vec2::new(0.0, tile_size * 0.4),
+                    Vec2::new(tile_size, line),
+                    0.0,
+                ),
+                (
+                    Vec2::new(0.0, -tile_size * 0.4),
+                    Vec2::new(tile_size, line),
+                    0.0,
+                ),
+                (
+                    Vec2::new(tile_size * 0.4, 0.0),
+                    Vec2::new(line, tile_size),
+                    0.0,
+                ),
+                (
+                    Vec2::new(-tile_size * 0.4, 0.0),
+                    Vec2::new(line, tile_size),
+                    0.0,
+                ),
+            ],
+        }
+    }
+
+    /// Picks black or white, whichever stands out more against the given player color
+    pub fn contrast_color(player_color: Color) -> Color {
+        let luminance =
+            0.2126 * player_color.r() + 0.7152 * player_color.g() + 0.0722 * player_color.b();
+        if luminance > 0.5 {
+            Color::rgba(0.0, 0.0, 0.0, 0.6)
+        } else {
+            Color::rgba(1.0, 1.0, 1.0, 0.6)
+        }
+    }
+}
+
+/// Color vision deficiencies that the palette preview can simulate
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorVisionSimulation {
+    #[default]
+    Normal,
+    Protanopia,
+    Deuteranopia,
+    Tritanopia,
+}
+
+impl Display for ColorVisionSimulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorVisionSimulation::Normal => f.write_str("Normal"),
+            ColorVisionSimulation::Protanopia => f.write_str("Protanopia"),
+            ColorVisionSimulation::Deuteranopia => f.write_str("Deuteranopia"),
+            ColorVisionSimulation::Tritanopia => f.write_str("Tritanopia"),
+        }
+    }
+}
+
+impl ColorVisionSimulation {
+    pub fn next(&self) -> ColorVisionSimulation {
+        match self {
+            ColorVisionSimulation::Normal => ColorVisionSimulation::Protanopia,
+            ColorVisionSimulation::Protanopia => ColorVisionSimulation::Deuteranopia,
+            ColorVisionSimulation::Deuteranopia => ColorVisionSimulation::Tritanopia,
+            ColorVisionSimulation::Tritanopia => ColorVisionSimulation::Tritanopia,
+        }
+    }
+
+    pub fn prev(&self) -> ColorVisionSimulation {
+        match self {
+            ColorVisionSimulation::Normal => ColorVisionSimulation::Normal,
+            ColorVisionSimulation::Protanopia => ColorVisionSimulation::Normal,
+            ColorVisionSimulation::Deuteranopia => ColorVisionSimulation::Protanopia,
+            ColorVisionSimulation::Tritanopia => ColorVisionSimulation::Deuteranopia,
+        }
+    }
+
+    /// Approximates how the color looks with this deficiency using the Machado, Oliveira and
+    /// Fernandes (2009) matrices at full severity
+    pub fn simulate(&self, color: Color) -> Color {
+        let matrix = match self {
+            ColorVisionSimulation::Normal => return color,
+            ColorVisionSimulation::Protanopia => Mat3::from_cols_array(&[
+                0.152286, 0.114503, -0.003882, 1.052583, 0.786281, -0.048116, -0.204868, 0.099216,
+                1.051998,
+            ]),
+            ColorVisionSimulation::Deuteranopia => Mat3::from_cols_array(&[
+                0.367322, 0.280085, -0.011820, 0.860646, 0.672501, 0.042940, -0.227968, 0.047413,
+                0.968881,
+            ]),
+            ColorVisionSimulation::Tritanopia => Mat3::from_cols_array(&[
+                1.255528, -0.078411, 0.004733, -0.076749, 0.930809, 0.691367, -0.178779, 0.147602,
+                0.303900,
+            ]),
+        };
+
+        let linear = color.as_linear_rgba_f32();
+        let simulated =
+            (matrix * Vec3::new(linear[0], linear[1], linear[2])).clamp(Vec3::ZERO, Vec3::ONE);
+        Color::rgba_linear(simulated.x, simulated.y, simulated.z, linear[3])
+    }
+}
@@ -0,0 +1,371 @@
+use bevy::prelude::*;
+
+use crate::game::end_game::GameEndConditions;
+use crate::game::GameBuildSettings;
+use crate::level_loader::{LevelHandle, Levels};
+use crate::loading::campaign_loader::{Campaign, CampaignHandle, CampaignProgress, Mission};
+use crate::loading::FontAssets;
+use crate::GameState;
+
+use super::menu::back_and_forth_button;
+use super::{modal_panel, BasicButton, DisabledButton, ModalStyle};
+
+pub struct CampaignMenuPlugin;
+
+impl Plugin for CampaignMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (handle_campaign_menu_buttons, update_campaign_menu)
+                .chain()
+                .in_set(OnUpdate(GameState::Menu)),
+        );
+    }
+}
+
+/// The mission currently shown in the campaign menu. Inserted when the menu is opened
+#[derive(Resource)]
+pub struct CampaignMenu {
+    pub selected_mission: usize,
+}
+
+#[derive(Component)]
+struct CampaignCloseButton;
+
+#[derive(Component)]
+struct NextMissionButton;
+
+#[derive(Component)]
+struct PrevMissionButton;
+
+#[derive(Component)]
+struct PlayMissionButton;
+
+#[derive(Component)]
+struct MissionNameText;
+
+#[derive(Component)]
+struct MissionStatusText;
+
+#[derive(Component)]
+struct MissionBriefingText;
+
+#[derive(Component)]
+struct MissionDetailsText;
+
+#[derive(Component)]
+struct MissionRestrictionsText;
+
+fn campaign_text(
+    text: impl Into<String>,
+    font_assets: &Res<FontAssets>,
+    font_size: f32,
+) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: font_assets.fira_sans.clone(),
+            font_size,
+            color: Color::GRAY,
+        },
+    )
+    .with_text_alignment(TextAlignment::Center)
+    .with_style(Style {
+        position_type: PositionType::Relative,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        margin: UiRect::all(Val::Px(5.0)),
+        size: Size::new(Val::Auto, Val::Auto),
+        max_size: Size::new(Val::Percent(90.0), Val::Auto),
+        ..default()
+    })
+}
+
+/// Opens the campaign on the furthest mission the player has unlocked
+pub fn spawn_campaign_menu<MenuMarker: Component + Clone>(
+    menu_marker: MenuMarker,
+    mut commands: &mut Commands,
+    font_assets: &Res<FontAssets>,
+    campaign: &Campaign,
+    campaign_progress: &CampaignProgress,
+) {
+    let selected_mission = (0..campaign.missions.len())
+        .take_while(|mission| campaign_progress.is_unlocked(*mission))
+        .last()
+        .unwrap_or(0);
+
+    let modal_content = modal_panel(
+        menu_marker.clone(),
+        ModalStyle {
+            with_close_button: true,
+            close_button_bundle: None::<CampaignCloseButton>,
+            modal_size: Some(Size::new(Val::Percent(70.0), Val::Percent(80.0))),
+        },
+        &mut commands,
+        &font_assets,
+    );
+
+    commands.entity(modal_content).with_children(|parent| {
+        parent.spawn(campaign_text("CAMPAIGN", font_assets, 55.0));
+
+        back_and_forth_button(
+            parent,
+            font_assets,
+            menu_marker.clone(),
+            PrevMissionButton,
+            true,
+            NextMissionButton,
+            true,
+            "MISSION",
+        );
+
+        parent
+            .spawn(campaign_text(String::new(), font_assets, 45.0))
+            .insert(MissionNameText);
+        parent
+            .spawn(campaign_text(String::new(), font_assets, 30.0))
+            .insert(MissionStatusText);
+        parent
+            .spawn(campaign_text(String::new(), font_assets, 30.0))
+            .insert(MissionBriefingText);
+        parent
+            .spawn(campaign_text(String::new(), font_assets, 30.0))
+            .insert(MissionDetailsText);
+        parent
+            .spawn(campaign_text(String::new(), font_assets, 30.0))
+            .insert(MissionRestrictionsText);
+
+        parent
+            .spawn(ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Auto, Val::Px(50.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: BackgroundColor::from(Color::GRAY),
+                ..Default::default()
+            })
+            .insert(menu_marker)
+            .insert(PlayMissionButton)
+            .insert(BasicButton)
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "PLAY MISSION",
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: Color::BLACK,
+                    },
+                ));
+            });
+    });
+
+    commands.insert_resource(CampaignMenu { selected_mission });
+}
+
+fn mission_details(mission: &Mission) -> String {
+    let goal = match mission.end_conditions {
+        GameEndConditions::Domination => String::from("Eliminate every enemy"),
+        GameEndConditions::Percentage { target_percentage } => {
+            format!("Conquer {}%", (target_percentage * 100.0).round() as u32)
+        }
+    };
+    let enemies = match mission.enemy_count {
+        1 => String::from("1 enemy"),
+        count => format!("{} enemies", count),
+    };
+    format!(
+        "{} - {} - {}\nThree stars under {}s, two under {}s",
+        enemies, mission.difficulty, goal, mission.three_star_seconds, mission.two_star_seconds
+    )
+}
+
+fn handle_campaign_menu_buttons(
+    mut state: ResMut<NextState<GameState>>,
+    campaign_menu: Option<ResMut<CampaignMenu>>,
+    campaign_handle: Res<CampaignHandle>,
+    campaign_assets: Res<Assets<Campaign>>,
+    campaign_progress: Res<CampaignProgress>,
+    level_handle: Res<LevelHandle>,
+    level_assets: Res<Assets<Levels>>,
+    mut game_build_settings: ResMut<GameBuildSettings>,
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&DisabledButton>,
+            Option<&NextMissionButton>,
+            Option<&PrevMissionButton>,
+            Option<&PlayMissionButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    let Some(mut campaign_menu) = campaign_menu else {
+        return;
+    };
+    let Some(campaign) = campaign_assets.get(&campaign_handle.campaign) else {
+        return;
+    };
+
+    for (interaction, option_disabled, option_next, option_prev, option_play) in
+        interaction_query.iter()
+    {
+        if Interaction::Clicked != *interaction {
+            continue;
+        }
+
+        if option_disabled.is_some() {
+            continue;
+        }
+
+        if option_next.is_some() {
+            campaign_menu.selected_mission =
+                (campaign_menu.selected_mission + 1).min(campaign.missions.len().saturating_sub(1));
+        }
+
+        if option_prev.is_some() {
+            campaign_menu.selected_mission = campaign_menu.selected_mission.saturating_sub(1);
+        }
+
+        if option_play.is_some() {
+            let mission_index = campaign_menu.selected_mission;
+            let Some(mission) = campaign.missions.get(mission_index) else {
+                continue;
+            };
+            if !campaign_progress.is_unlocked(mission_index) {
+                continue;
+            }
+            let Some(map_type) = level_assets.get(&level_handle.levels).and_then(|levels| {
+                levels
+                    .levels
+                    .iter()
+                    .position(|level| level.name == mission.level)
+            }) else {
+                warn!(
+                    "Campaign mission {} uses an unknown level {}",
+                    mission.name, mission.level
+                );
+                continue;
+            };
+
+            if !game_build_settings.is_playable_map(map_type) {
+                warn!(
+                    "Campaign mission {} uses level {} which has no spawn point for an enemy",
+                    mission.name, mission.level
+                );
+                continue;
+            }
+
+            game_build_settings.start_mission(mission_index, mission, map_type);
+            state.set(GameState::Playing);
+        }
+    }
+}
+
+fn update_campaign_menu(
+    campaign_menu: Option<Res<CampaignMenu>>,
+    campaign_handle: Res<CampaignHandle>,
+    campaign_assets: Res<Assets<Campaign>>,
+    campaign_progress: Res<CampaignProgress>,
+    mut texts: Query<
+        (
+            &mut Text,
+            Option<&MissionNameText>,
+            Option<&MissionStatusText>,
+            Option<&MissionBriefingText>,
+            Option<&MissionDetailsText>,
+            Option<&MissionRestrictionsText>,
+        ),
+        Or<(
+            With<MissionNameText>,
+            With<MissionStatusText>,
+            With<MissionBriefingText>,
+            With<MissionDetailsText>,
+            With<MissionRestrictionsText>,
+        )>,
+    >,
+    mut buttons: Query<
+        (
+            Entity,
+            &mut BackgroundColor,
+            Option<&DisabledButton>,
+            Option<&NextMissionButton>,
+            Option<&PrevMissionButton>,
+            Option<&PlayMissionButton>,
+        ),
+        Or<(
+            With<NextMissionButton>,
+            With<PrevMissionButton>,
+            With<PlayMissionButton>,
+        )>,
+    >,
+    mut commands: Commands,
+) {
+    let Some(campaign_menu) = campaign_menu else {
+        return;
+    };
+    if !campaign_menu.is_changed() && !campaign_progress.is_changed() {
+        return;
+    }
+    let Some(campaign) = campaign_assets.get(&campaign_handle.campaign) else {
+        return;
+    };
+    let mission_index = campaign_menu.selected_mission;
+    let Some(mission) = campaign.missions.get(mission_index) else {
+        return;
+    };
+    let unlocked = campaign_progress.is_unlocked(mission_index);
+
+    for (
+        mut text,
+        option_name,
+        option_status,
+        option_briefing,
+        option_details,
+        option_restrictions,
+    ) in texts.iter_mut()
+    {
+        if option_name.is_some() {
+            text.sections[0].value = format!("{}. {}", mission_index + 1, mission.name);
+        }
+        if option_status.is_some() {
+            text.sections[0].value = match unlocked {
+                true => format!("Stars: {}/3", campaign_progress.stars(mission_index)),
+                false => String::from("Locked - win the mission before this one first"),
+            };
+        }
+        if option_briefing.is_some() {
+            text.sections[0].value = mission.briefing.clone();
+        }
+        if option_details.is_some() {
+            text.sections[0].value = mission_details(mission);
+        }
+        if option_restrictions.is_some() {
+            text.sections[0].value = mission.restrictions.to_string();
+        }
+    }
+
+    for (entity, mut background_color, option_disabled, option_next, option_prev, option_play) in
+        buttons.iter_mut()
+    {
+        let enabled = match (option_next, option_prev, option_play) {
+            (Some(_), _, _) => mission_index + 1 < campaign.missions.len(),
+            (_, Some(_), _) => mission_index > 0,
+            (_, _, Some(_)) => unlocked,
+            _ => continue,
+        };
+        match (enabled, option_disabled.is_some()) {
+            (true, true) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
+            }
+            (false, false) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
+            }
+            _ => {}
+        }
+    }
+}
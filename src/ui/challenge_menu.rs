@@ -0,0 +1,443 @@
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+
+use crate::game::challenge::{current_day, format_day, url_seed_code, Challenge};
+use crate::game::GameBuildSettings;
+use crate::level_loader::{LevelHandle, Levels};
+use crate::loading::FontAssets;
+use crate::GameState;
+
+use super::palette_editor::{read_clipboard, write_clipboard};
+use super::{modal_panel, BasicButton, DisabledButton, ModalStyle};
+
+pub struct ChallengeMenuPlugin;
+
+impl Plugin for ChallengeMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                handle_challenge_menu_buttons,
+                handle_code_text_input,
+                update_challenge_menu,
+            )
+                .chain()
+                .in_set(OnUpdate(GameState::Menu)),
+        );
+    }
+}
+
+const MAX_CODE_TEXT_LENGTH: usize = 20;
+
+/// The challenge currently shown in the challenge menu. Inserted when the menu is opened
+#[derive(Resource)]
+pub struct ChallengeMenu {
+    pub challenge: Challenge,
+    /// The day of the shown daily challenge. None if the challenge was loaded from a seed code
+    pub daily: Option<u64>,
+    pub code_text: String,
+    pub status: String,
+}
+
+impl ChallengeMenu {
+    fn today(game_build_settings: &GameBuildSettings) -> ChallengeMenu {
+        let day = current_day();
+        ChallengeMenu {
+            challenge: Challenge::daily(day, game_build_settings),
+            daily: Some(day),
+            code_text: String::new(),
+            status: String::new(),
+        }
+    }
+
+    fn load_code(&mut self, code: &str, game_build_settings: &GameBuildSettings) {
+        match Challenge::from_code(code)
+            .and_then(|challenge| challenge.check(game_build_settings).map(|_| challenge))
+        {
+            Ok(challenge) => {
+                self.status = format!("Loaded seed code {}", challenge.code());
+                self.challenge = challenge;
+                self.daily = None;
+            }
+            Err(error) => self.status = error,
+        }
+    }
+}
+
+#[derive(Component)]
+struct ChallengeCloseButton;
+
+#[derive(Component)]
+struct PlayChallengeButton;
+
+#[derive(Component)]
+struct CopyCodeButton;
+
+#[derive(Component)]
+struct LoadCodeButton;
+
+#[derive(Component)]
+struct TodayButton;
+
+#[derive(Component)]
+struct ChallengeNameText;
+
+#[derive(Component)]
+struct ChallengeDetailsText;
+
+#[derive(Component)]
+struct ChallengeCodeText;
+
+#[derive(Component)]
+struct CodeInputText;
+
+#[derive(Component)]
+struct ChallengeStatusText;
+
+fn challenge_text(
+    text: impl Into<String>,
+    font_assets: &Res<FontAssets>,
+    font_size: f32,
+) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: font_assets.fira_sans.clone(),
+            font_size,
+            color: Color::GRAY,
+        },
+    )
+    .with_text_alignment(TextAlignment::Center)
+    .with_style(Style {
+        position_type: PositionType::Relative,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        margin: UiRect::all(Val::Px(5.0)),
+        size: Size::new(Val::Auto, Val::Auto),
+        max_size: Size::new(Val::Percent(90.0), Val::Auto),
+        ..default()
+    })
+}
+
+fn challenge_button(
+    parent: &mut ChildBuilder,
+    font_assets: &Res<FontAssets>,
+    menu_marker: impl Component,
+    marker: impl Component,
+    button_text: &str,
+) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Auto, Val::Px(50.0)),
+                margin: UiRect::all(Val::Px(10.0)),
+                padding: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor::from(Color::GRAY),
+            ..Default::default()
+        })
+        .insert(menu_marker)
+        .insert(marker)
+        .insert(BasicButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                button_text,
+                TextStyle {
+                    font: font_assets.fira_sans.clone(),
+                    font_size: 40.0,
+                    color: Color::BLACK,
+                },
+            ));
+        });
+}
+
+fn row_node() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            size: Size::new(Val::Auto, Val::Auto),
+            position_type: PositionType::Relative,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Row,
+            margin: UiRect::all(Val::Px(5.0)),
+            ..default()
+        },
+        background_color: Color::rgba(0.0, 0.0, 0.0, 0.0).into(),
+        ..default()
+    }
+}
+
+/// Opens on today's challenge, or on the seed code of the page url in the web build
+pub fn spawn_challenge_menu<MenuMarker: Component + Clone>(
+    menu_marker: MenuMarker,
+    mut commands: &mut Commands,
+    font_assets: &Res<FontAssets>,
+    game_build_settings: &GameBuildSettings,
+) {
+    let mut challenge_menu = ChallengeMenu::today(game_build_settings);
+    if let Some(code) = url_seed_code() {
+        challenge_menu.load_code(&code, game_build_settings);
+    }
+
+    let modal_content = modal_panel(
+        menu_marker.clone(),
+        ModalStyle {
+            with_close_button: true,
+            close_button_bundle: None::<ChallengeCloseButton>,
+            modal_size: Some(Size::new(Val::Percent(70.0), Val::Percent(80.0))),
+        },
+        &mut commands,
+        &font_assets,
+    );
+
+    commands.entity(modal_content).with_children(|parent| {
+        parent.spawn(challenge_text("DAILY CHALLENGE", font_assets, 55.0));
+
+        parent
+            .spawn(challenge_text(String::new(), font_assets, 45.0))
+            .insert(ChallengeNameText);
+        parent
+            .spawn(challenge_text(String::new(), font_assets, 30.0))
+            .insert(ChallengeDetailsText);
+        parent
+            .spawn(challenge_text(String::new(), font_assets, 30.0))
+            .insert(ChallengeCodeText);
+
+        parent.spawn(row_node()).with_children(|parent| {
+            challenge_button(
+                parent,
+                font_assets,
+                menu_marker.clone(),
+                PlayChallengeButton,
+                "PLAY CHALLENGE",
+            );
+            challenge_button(
+                parent,
+                font_assets,
+                menu_marker.clone(),
+                CopyCodeButton,
+                "COPY CODE",
+            );
+        });
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(80.0), Val::Px(50.0)),
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    ..default()
+                },
+                background_color: Color::DARK_GRAY.into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(challenge_text(String::new(), font_assets, 25.0))
+                    .insert(CodeInputText);
+            });
+
+        parent.spawn(row_node()).with_children(|parent| {
+            challenge_button(
+                parent,
+                font_assets,
+                menu_marker.clone(),
+                LoadCodeButton,
+                "LOAD CODE",
+            );
+            challenge_button(parent, font_assets, menu_marker, TodayButton, "TODAY");
+        });
+
+        parent
+            .spawn(challenge_text(String::new(), font_assets, 30.0))
+            .insert(ChallengeStatusText);
+    });
+
+    commands.insert_resource(challenge_menu);
+}
+
+fn challenge_details(challenge: &Challenge, levels: &Levels) -> String {
+    let map = match levels.levels.get(challenge.map_type) {
+        Some(level) if challenge.map_type == 0 => {
+            format!(
+                "{} {}x{}",
+                level.name, challenge.map_size, challenge.map_size
+            )
+        }
+        Some(level) => level.name.clone(),
+        None => String::from("Unknown map"),
+    };
+    let enemies = match challenge.enemy_count {
+        1 => String::from("1 enemy"),
+        count => format!("{} enemies", count),
+    };
+    format!("{} - {} - {}", map, enemies, challenge.game_difficulty)
+}
+
+fn handle_challenge_menu_buttons(
+    mut state: ResMut<NextState<GameState>>,
+    challenge_menu: Option<ResMut<ChallengeMenu>>,
+    mut game_build_settings: ResMut<GameBuildSettings>,
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&DisabledButton>,
+            Option<&PlayChallengeButton>,
+            Option<&CopyCodeButton>,
+            Option<&LoadCodeButton>,
+            Option<&TodayButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    let Some(mut challenge_menu) = challenge_menu else {
+        return;
+    };
+
+    for (interaction, option_disabled, option_play, option_copy, option_load, option_today) in
+        interaction_query.iter()
+    {
+        if Interaction::Clicked != *interaction {
+            continue;
+        }
+
+        if option_disabled.is_some() {
+            continue;
+        }
+
+        if option_play.is_some() {
+            if let Err(error) = challenge_menu.challenge.check(&game_build_settings) {
+                challenge_menu.status = error;
+                continue;
+            }
+            game_build_settings.start_challenge(&challenge_menu.challenge);
+            state.set(GameState::Playing);
+        }
+
+        if option_copy.is_some() {
+            challenge_menu.status = match write_clipboard(challenge_menu.challenge.code()) {
+                Ok(_) => String::from("Copied seed code"),
+                Err(error) => error,
+            };
+        }
+
+        if option_load.is_some() {
+            let code = std::mem::take(&mut challenge_menu.code_text);
+            challenge_menu.load_code(&code, &game_build_settings);
+        }
+
+        if option_today.is_some() {
+            *challenge_menu = ChallengeMenu::today(&game_build_settings);
+        }
+    }
+}
+
+/// Typing into the seed code box. Ctrl+V pastes from the clipboard
+fn handle_code_text_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    challenge_menu: Option<ResMut<ChallengeMenu>>,
+    code_input_text: Query<&CodeInputText>,
+) {
+    let Some(mut challenge_menu) = challenge_menu else {
+        received_characters.clear();
+        return;
+    };
+    if code_input_text.is_empty() {
+        received_characters.clear();
+        return;
+    }
+
+    let control = keyboard_input.pressed(KeyCode::LControl)
+        || keyboard_input.pressed(KeyCode::RControl)
+        || keyboard_input.pressed(KeyCode::LWin)
+        || keyboard_input.pressed(KeyCode::RWin);
+
+    if control && keyboard_input.just_pressed(KeyCode::V) {
+        match read_clipboard() {
+            Ok(text) => challenge_menu.code_text.push_str(text.trim()),
+            Err(error) => challenge_menu.status = error,
+        }
+    } else {
+        for received_character in received_characters.iter() {
+            let character = received_character.char;
+            if character.is_ascii_alphanumeric() || character == '-' {
+                challenge_menu
+                    .code_text
+                    .push(character.to_ascii_uppercase());
+            }
+        }
+    }
+    received_characters.clear();
+
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        challenge_menu.code_text.pop();
+    }
+
+    if challenge_menu.code_text.len() > MAX_CODE_TEXT_LENGTH {
+        challenge_menu.code_text.truncate(MAX_CODE_TEXT_LENGTH);
+    }
+}
+
+fn update_challenge_menu(
+    challenge_menu: Option<Res<ChallengeMenu>>,
+    level_handle: Res<LevelHandle>,
+    level_assets: Res<Assets<Levels>>,
+    mut texts: Query<
+        (
+            &mut Text,
+            Option<&ChallengeNameText>,
+            Option<&ChallengeDetailsText>,
+            Option<&ChallengeCodeText>,
+            Option<&CodeInputText>,
+            Option<&ChallengeStatusText>,
+        ),
+        Or<(
+            With<ChallengeNameText>,
+            With<ChallengeDetailsText>,
+            With<ChallengeCodeText>,
+            With<CodeInputText>,
+            With<ChallengeStatusText>,
+        )>,
+    >,
+) {
+    let Some(challenge_menu) = challenge_menu else {
+        return;
+    };
+    if !challenge_menu.is_changed() {
+        return;
+    }
+    let Some(levels) = level_assets.get(&level_handle.levels) else {
+        return;
+    };
+
+    for (mut text, option_name, option_details, option_code, option_input, option_status) in
+        texts.iter_mut()
+    {
+        if option_name.is_some() {
+            text.sections[0].value = match challenge_menu.daily {
+                Some(day) => format!("Daily Challenge {}", format_day(day)),
+                None => String::from("Shared Challenge"),
+            };
+        }
+        if option_details.is_some() {
+            text.sections[0].value = challenge_details(&challenge_menu.challenge, levels);
+        }
+        if option_code.is_some() {
+            text.sections[0].value = format!("Seed code: {}", challenge_menu.challenge.code());
+        }
+        if option_input.is_some() {
+            text.sections[0].value = match challenge_menu.code_text.is_empty() {
+                true => String::from("Type or paste a seed code to play someone else's challenge"),
+                false => challenge_menu.code_text.clone(),
+            };
+        }
+        if option_status.is_some() {
+            text.sections[0].value = challenge_menu.status.clone();
+        }
+    }
+}
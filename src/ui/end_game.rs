@@ -2,9 +2,15 @@
 
 use crate::audio::UiSoundEvents;
 use crate::color_system::TileColor;
+use crate::game::campaign::record_campaign_result;
+use crate::game::challenge::Challenge;
 use crate::game::end_game::GameEnded;
+use crate::game::puzzle::PuzzleState;
 use crate::game::restart_game::{RestartGame, RestartGameEvent};
+use crate::game::survival::{format_survival_time, record_survival_score, SurvivalState};
 use crate::game::{GameBuildSettings, GameData};
+use crate::loading::campaign_loader::CampaignProgress;
+use crate::loading::leaderboard_loader::SurvivalLeaderboard;
 use crate::loading::FontAssets;
 use crate::player::PlayerPoints;
 use crate::ui::{modal_panel, BasicButton, DisabledButton, ModalStyle, PlayerColors};
@@ -13,6 +19,7 @@ use bevy::prelude::CoreSet::Update;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use bevy_ecs_tilemap::tiles::TilePos;
+use bevy_ggf::game_core::Game;
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::mapping::tiles::Tile;
 use bevy_ggf::object::Object;
@@ -27,14 +34,19 @@ pub struct EndGameUiPlugin;
 /// The menu is only drawn during the State `GameState::Menu` and is removed when that state is exited
 impl Plugin for EndGameUiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(setup_menu.in_schedule(OnEnter(GameState::Ended)))
-            .add_system(
-                button_interaction
-                    .in_base_set(Update)
-                    .run_if(in_state(GameState::Ended)),
-            )
-            .add_system(generate_all_player_cubes.run_if(in_state(GameState::Ended)))
-            .add_system(cleanup_menu.in_schedule(OnExit(GameState::Ended)));
+        app.add_system(
+            setup_menu
+                .after(record_survival_score)
+                .after(record_campaign_result)
+                .in_schedule(OnEnter(GameState::Ended)),
+        )
+        .add_system(
+            button_interaction
+                .in_base_set(Update)
+                .run_if(in_state(GameState::Ended)),
+        )
+        .add_system(generate_all_player_cubes.run_if(in_state(GameState::Ended)))
+        .add_system(cleanup_menu.in_schedule(OnExit(GameState::Ended)));
     }
 }
 
@@ -55,7 +67,14 @@ fn setup_menu(
     font_assets: Res<FontAssets>,
     player_colors: Res<PlayerColors>,
     game_ended: Res<GameEnded>,
+    game: Res<Game>,
+    leaderboard: Res<SurvivalLeaderboard>,
+    campaign_progress: Res<CampaignProgress>,
+    game_settings: Res<GameBuildSettings>,
 ) {
+    let survival_state = game.game_world.get_resource::<SurvivalState>();
+    let puzzle_state = game.game_world.get_resource::<PuzzleState>();
+
     commands.init_resource::<CubeTimer>();
 
     let modal = modal_panel(
@@ -69,12 +88,19 @@ fn setup_menu(
         &font_assets,
     );
     commands.entity(modal).with_children(|parent| {
-        let victory_text = match game_ended.player_won {
-            true => "You Won!".to_string(),
-            false => format!("You Lost to AI #{}!", game_ended.winning_id),
+        let victory_text = match (game_ended.player_won, survival_state) {
+            (_, Some(survival_state)) => {
+                format!("You Survived {} Waves!", survival_state.waves_spawned)
+            }
+            (true, None) if puzzle_state.is_some() => "Puzzle Solved!".to_string(),
+            (false, None) if puzzle_state.map_or(false, |puzzle| puzzle.seconds_left <= 0.0) => {
+                "Out of Time!".to_string()
+            }
+            (true, None) => "You Won!".to_string(),
+            (false, None) => format!("You Lost to AI #{}!", game_ended.winning_id),
         };
 
-        let winner_color = match game_ended.player_won {
+        let winner_color = match game_ended.player_won || survival_state.is_some() {
             true => 0,
             false => game_ended.winning_id,
         };
@@ -119,6 +145,101 @@ fn setup_menu(
             }),
         );
 
+        if let Some((_, stars)) = campaign_progress.last_result {
+            parent.spawn(
+                TextBundle::from_sections(vec![TextSection::new(
+                    format!("Mission complete - {}/3 stars", stars),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: player_colors.get_color(0),
+                    },
+                )])
+                .with_text_alignment(TextAlignment::Center)
+                .with_style(Style {
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::vertical(Val::Px(10.0)),
+                    size: Size::new(Val::Auto, Val::Auto),
+                    ..default()
+                }),
+            );
+        }
+
+        if let Some(challenge) = Challenge::from_settings(&game_settings) {
+            parent.spawn(
+                TextBundle::from_sections(vec![TextSection::new(
+                    format!("Seed code: {}", challenge.code()),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: Color::GRAY,
+                    },
+                )])
+                .with_text_alignment(TextAlignment::Center)
+                .with_style(Style {
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::vertical(Val::Px(10.0)),
+                    size: Size::new(Val::Auto, Val::Auto),
+                    ..default()
+                }),
+            );
+        }
+
+        if let Some(survival_state) = survival_state {
+            let rank_text = match leaderboard.last_rank {
+                Some(rank) => format!(" - #{} on the leaderboard", rank + 1),
+                None => String::new(),
+            };
+            let mut sections = vec![TextSection::new(
+                format!(
+                    "Held {}% of the board for {}{}",
+                    game_settings.survival_hold_percent,
+                    format_survival_time(survival_state.score),
+                    rank_text
+                ),
+                TextStyle {
+                    font: font_assets.fira_sans.clone(),
+                    font_size: 40.0,
+                    color: player_colors.get_color(0),
+                },
+            )];
+            for (rank, score) in leaderboard.scores.iter().take(3).enumerate() {
+                sections.push(TextSection::new(
+                    format!(
+                        "\n#{}  {}  {} waves  {} ({}, {}%)",
+                        rank + 1,
+                        format_survival_time(score.seconds_held),
+                        score.waves,
+                        score.map,
+                        score.difficulty,
+                        score.hold_percent
+                    ),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 30.0,
+                        color: Color::GRAY,
+                    },
+                ));
+            }
+
+            parent.spawn(
+                TextBundle::from_sections(sections)
+                    .with_text_alignment(TextAlignment::Center)
+                    .with_style(Style {
+                        position_type: PositionType::Relative,
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        margin: UiRect::vertical(Val::Px(10.0)),
+                        size: Size::new(Val::Auto, Val::Auto),
+                        ..default()
+                    }),
+            );
+        }
+
         parent
             .spawn(NodeBundle {
                 style: Style {
@@ -312,6 +433,9 @@ fn generate_all_player_cubes(
 
     let mut played_sound = false;
 
+    // narrow the columns once there are more than four players so they all fit side by side
+    let cube_scale = 4.0 / players.len().max(4) as f32;
+
     // for each player generate their cubes and add them plus get players points and display them
     for (player_query, player_points) in players.iter().rev() {
         let max_tile_count = match game_build_settings.game_end_conditions {
@@ -344,6 +468,7 @@ fn generate_all_player_cubes(
             max_tile_count,
             &player_colors,
             cube_timer.times_run,
+            cube_scale,
         ) {
             if !played_sound {
                 menu_sound_events.send(UiSoundEvents::PlayerBoxAnimationEndGame);
@@ -367,6 +492,7 @@ fn generate_player_cubes(
     max_tile_count: usize,
     player_colors: &Res<PlayerColors>,
     index: u8,
+    cube_scale: f32,
 ) -> bool {
     let mut played_sound = false;
 
@@ -398,9 +524,9 @@ fn generate_player_cubes(
     let player_tile_count = player_tile_count as f32 / max_tile_count as f32;
 
     let size = if index == 1 {
-        Size::new(Val::Px(100.0), Val::Px(25.0))
+        Size::new(Val::Px(100.0 * cube_scale), Val::Px(25.0))
     } else {
-        Size::new(Val::Px(75.0), Val::Px(40.0))
+        Size::new(Val::Px(75.0 * cube_scale), Val::Px(40.0))
     };
 
     let transform_tween = Tween::new(
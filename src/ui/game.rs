@@ -1,8 +1,12 @@
-﻿use crate::abilities::Abilities;
-use crate::actions::Actions;
-use crate::buildings::BuildingTypes::{self, Line, Pulser, Scatter};
+use crate::abilities::Abilities;
+use crate::actions::{Actions, ClickMode};
+use crate::buildings::BuildingTypes::{
+    self, Artillery, Harvester, Line, Pulser, Relay, Scatter, Wall, Workshop,
+};
 use crate::color_system::TileColor;
-use crate::game::{start_game, GameBuildSettings, GameData};
+use crate::game::puzzle::PuzzleState;
+use crate::game::survival::{format_survival_time, SurvivalState};
+use crate::game::{start_game, GameBuildSettings, GameData, GameSpeed, StepGame};
 use crate::loading::{FontAssets, TextureAssets};
 use crate::player::PlayerPoints;
 use crate::{GamePausedState, GameState};
@@ -10,6 +14,7 @@ use bevy::prelude::CoreSet::Update;
 use bevy::prelude::*;
 use bevy::utils::HashMap;
 use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::game_core::Game;
 use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_ggf::mapping::tiles::Tile;
 use bevy_ggf::object::Object;
@@ -19,6 +24,7 @@ use bevy_tweening::{Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween};
 use std::ops::DerefMut;
 use std::time::Duration;
 
+use crate::ui::menu::back_and_forth_button;
 use crate::ui::{
     BasicButton, DisabledButton, GameButton, GameButtonIcon, PlayerColors, SelectedButton,
 };
@@ -55,11 +61,21 @@ impl Plugin for GameUiPlugin {
                 .in_base_set(Update)
                 .run_if(in_state(GameState::Playing)),
         );
+        app.add_system(
+            update_click_mode_buttons
+                .in_base_set(Update)
+                .run_if(in_state(GameState::Playing)),
+        );
         app.add_system(
             handle_player_cubes_and_stats
                 .in_base_set(Update)
                 .run_if(in_state(GameState::Playing)),
         );
+        app.add_system(
+            update_game_speed_controls
+                .in_base_set(Update)
+                .run_if(in_state(GameState::Playing).and_then(resource_changed::<GameSpeed>())),
+        );
     }
 }
 
@@ -67,12 +83,13 @@ fn update_game_buttons_from_keyboard_selectiron(
     mut interaction_query: Query<
         (
             &mut Interaction,
-            Option<&LineButtonMarker>,
-            Option<&PulserButtonMarker>,
-            Option<&ScatterButtonMarker>,
+            Option<&BuildingButtonMarker>,
             Option<&NukeButtonMarker>,
             Option<&ExpandButtonMarker>,
             Option<&FortifyButtonMarker>,
+            Option<&FreezeButtonMarker>,
+            Option<&SwapButtonMarker>,
+            Option<&ShieldButtonMarker>,
         ),
         (With<Button>, Without<DisabledButton>, With<GameButton>),
     >,
@@ -94,17 +111,13 @@ fn update_game_buttons_from_keyboard_selectiron(
                 last_action.selected_ability = actions.selected_ability;
             }
 
-            for (mut interaction, lbm, pbm, sbm, nbm, ebm, fbm) in interaction_query.iter_mut() {
-                if selected_building_changed {
-                    if lbm.is_some() && actions.selected_building == BuildingTypes::Line {
-                        *interaction = Interaction::Clicked;
-                    }
-                    if pbm.is_some() && actions.selected_building == BuildingTypes::Pulser {
-                        *interaction = Interaction::Clicked;
-                    }
-                    if sbm.is_some() && actions.selected_building == BuildingTypes::Scatter {
-                        *interaction = Interaction::Clicked;
-                    }
+            for (mut interaction, bbm, nbm, ebm, fbm, zbm, swbm, shbm) in
+                interaction_query.iter_mut()
+            {
+                if selected_building_changed
+                    && bbm.map_or(false, |bbm| bbm.0 == actions.selected_building)
+                {
+                    *interaction = Interaction::Clicked;
                 }
 
                 if selected_ability_changed {
@@ -117,6 +130,15 @@ fn update_game_buttons_from_keyboard_selectiron(
                     if fbm.is_some() && actions.selected_ability == Abilities::Fortify {
                         *interaction = Interaction::Clicked;
                     }
+                    if zbm.is_some() && actions.selected_ability == Abilities::Freeze {
+                        *interaction = Interaction::Clicked;
+                    }
+                    if swbm.is_some() && actions.selected_ability == Abilities::Swap {
+                        *interaction = Interaction::Clicked;
+                    }
+                    if shbm.is_some() && actions.selected_ability == Abilities::Shield {
+                        *interaction = Interaction::Clicked;
+                    }
                 }
             }
         }
@@ -238,22 +260,68 @@ struct MainButtonBG;
 struct PauseButton;
 
 #[derive(Component)]
-struct BuildingButtonsGroupMarker;
+struct SlowDownButton;
 
 #[derive(Component)]
-struct AbilitiesButtonsGroupMarker;
+struct SpeedUpButton;
 
 #[derive(Component)]
-struct NewSelectedButton;
+struct HoldButton;
+
+#[derive(Component)]
+struct HoldButtonText;
 
 #[derive(Component)]
-struct PulserButtonMarker;
+struct StepButton;
 
 #[derive(Component)]
-struct LineButtonMarker;
+struct GameSpeedText;
 
 #[derive(Component)]
-struct ScatterButtonMarker;
+struct DemolishButton;
+
+#[derive(Component)]
+struct DemolishButtonText;
+
+#[derive(Component)]
+struct UpgradeButton;
+
+#[derive(Component)]
+struct UpgradeButtonText;
+
+fn hold_button_text(game_speed: &GameSpeed) -> &'static str {
+    match game_speed.held {
+        true => "RESUME",
+        false => "HOLD",
+    }
+}
+
+fn demolish_button_text(actions: &Actions) -> &'static str {
+    match actions.click_mode {
+        ClickMode::Demolish => "BUILD",
+        _ => "DEMOLISH",
+    }
+}
+
+fn upgrade_button_text(actions: &Actions) -> &'static str {
+    match actions.click_mode {
+        ClickMode::Upgrade => "BUILD",
+        _ => "UPGRADE",
+    }
+}
+
+#[derive(Component)]
+struct BuildingButtonsGroupMarker;
+
+#[derive(Component)]
+struct AbilitiesButtonsGroupMarker;
+
+#[derive(Component)]
+struct NewSelectedButton;
+
+/// Marks the button that selects the building type
+#[derive(Component)]
+struct BuildingButtonMarker(BuildingTypes);
 
 #[derive(Component)]
 struct NukeButtonMarker;
@@ -264,6 +332,15 @@ struct FortifyButtonMarker;
 #[derive(Component)]
 struct ExpandButtonMarker;
 
+#[derive(Component)]
+struct FreezeButtonMarker;
+
+#[derive(Component)]
+struct SwapButtonMarker;
+
+#[derive(Component)]
+struct ShieldButtonMarker;
+
 #[derive(Component)]
 struct PlayerCubesParent;
 
@@ -288,6 +365,7 @@ fn setup_menu(
     player_queries: Query<(&Player, &PlayerPoints), Without<PlayerMarker>>,
     game: Res<GameData>,
     game_build_settings: Res<GameBuildSettings>,
+    game_speed: Res<GameSpeed>,
 ) {
     for (player, actions) in player_actions.iter() {
         if player.id() != 0 {
@@ -391,48 +469,39 @@ fn setup_menu(
                                     ..default()
                                 });
 
-                                let mut pulsor_button = None::<NewSelectedButton>;
-                                let mut scatter_button = None::<NewSelectedButton>;
-                                let mut line_button = None::<NewSelectedButton>;
-
-                                match actions.selected_building {
-                                    Pulser => pulsor_button = Some(NewSelectedButton),
-                                    Scatter => scatter_button = Some(NewSelectedButton),
-                                    Line => line_button = Some(NewSelectedButton),
+                                let building_buttons = [
+                                    (Pulser, "Pulser", texture_assets.pulser.clone()),
+                                    (Scatter, "Scatter", texture_assets.scatter.clone()),
+                                    (Line, "Line", texture_assets.line.clone()),
+                                    (Wall, "Wall", texture_assets.wall.clone()),
+                                    (Harvester, "Harvester", texture_assets.harvester.clone()),
+                                    (Relay, "Relay", texture_assets.relay.clone()),
+                                    (Artillery, "Artillery", texture_assets.artillery.clone()),
+                                    (Workshop, "Workshop", texture_assets.workshop.clone()),
+                                ];
+                                for (building_type, button_text, button_icon) in building_buttons {
+                                    if !game_build_settings
+                                        .restrictions
+                                        .allows_building(building_type)
+                                    {
+                                        continue;
+                                    }
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (
+                                            BuildingButtonMarker(building_type),
+                                            BuildingButtonsGroupMarker,
+                                        ),
+                                        button_text,
+                                        button_icon,
+                                        50,
+                                        (actions.selected_building == building_type)
+                                            .then_some(NewSelectedButton),
+                                    );
                                 }
 
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (PulserButtonMarker, BuildingButtonsGroupMarker),
-                                    "Pulser",
-                                    texture_assets.pulser.clone(),
-                                    50,
-                                    pulsor_button,
-                                );
-
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (ScatterButtonMarker, BuildingButtonsGroupMarker),
-                                    "Scatter",
-                                    texture_assets.scatter.clone(),
-                                    50,
-                                    scatter_button,
-                                );
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (LineButtonMarker, BuildingButtonsGroupMarker),
-                                    "Line",
-                                    texture_assets.line.clone(),
-                                    50,
-                                    line_button,
-                                );
-
                                 parent.spawn(
                                     TextBundle::from_section(
                                         "ABILITIES",
@@ -469,44 +538,110 @@ fn setup_menu(
                                 let mut nuke_button = None::<NewSelectedButton>;
                                 let mut fortify_button = None::<NewSelectedButton>;
                                 let mut expand_button = None::<NewSelectedButton>;
+                                let mut freeze_button = None::<NewSelectedButton>;
+                                let mut swap_button = None::<NewSelectedButton>;
+                                let mut shield_button = None::<NewSelectedButton>;
 
                                 match actions.selected_ability {
                                     Abilities::Nuke => nuke_button = Some(NewSelectedButton),
                                     Abilities::Fortify => fortify_button = Some(NewSelectedButton),
                                     Abilities::Expand => expand_button = Some(NewSelectedButton),
+                                    Abilities::Freeze => freeze_button = Some(NewSelectedButton),
+                                    Abilities::Swap => swap_button = Some(NewSelectedButton),
+                                    Abilities::Shield => shield_button = Some(NewSelectedButton),
                                 }
 
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (NukeButtonMarker, AbilitiesButtonsGroupMarker),
-                                    "Nuke",
-                                    texture_assets.nuke.clone(),
-                                    50,
-                                    nuke_button,
-                                );
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Nuke)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (NukeButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Nuke",
+                                        texture_assets.nuke.clone(),
+                                        50,
+                                        nuke_button,
+                                    );
+                                }
 
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (FortifyButtonMarker, AbilitiesButtonsGroupMarker),
-                                    "Fortify",
-                                    texture_assets.fortify.clone(),
-                                    50,
-                                    fortify_button,
-                                );
-                                game_button(
-                                    parent,
-                                    &font_assets,
-                                    GameUiThing,
-                                    (ExpandButtonMarker, AbilitiesButtonsGroupMarker),
-                                    "Expand",
-                                    texture_assets.expand.clone(),
-                                    50,
-                                    expand_button,
-                                );
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Fortify)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (FortifyButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Fortify",
+                                        texture_assets.fortify.clone(),
+                                        50,
+                                        fortify_button,
+                                    );
+                                }
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Expand)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (ExpandButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Expand",
+                                        texture_assets.expand.clone(),
+                                        50,
+                                        expand_button,
+                                    );
+                                }
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Freeze)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (FreezeButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Freeze",
+                                        texture_assets.freeze.clone(),
+                                        50,
+                                        freeze_button,
+                                    );
+                                }
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Swap)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (SwapButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Swap",
+                                        texture_assets.swap.clone(),
+                                        50,
+                                        swap_button,
+                                    );
+                                }
+                                if game_build_settings
+                                    .restrictions
+                                    .allows_ability(Abilities::Shield)
+                                {
+                                    game_button(
+                                        parent,
+                                        &font_assets,
+                                        GameUiThing,
+                                        (ShieldButtonMarker, AbilitiesButtonsGroupMarker),
+                                        "Shield",
+                                        texture_assets.shield.clone(),
+                                        50,
+                                        shield_button,
+                                    );
+                                }
                             });
                     });
 
@@ -553,6 +688,199 @@ fn setup_menu(
                                 ));
                             });
 
+                        parent
+                            .spawn(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Auto, Val::Px(50.0)),
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    padding: UiRect::all(Val::Px(10.0)),
+                                    position_type: PositionType::Relative,
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: BackgroundColor::from(Color::GRAY),
+                                ..Default::default()
+                            })
+                            .insert(BasicButton)
+                            .insert(DemolishButton)
+                            .insert(GameUiThing)
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(TextBundle::from_section(
+                                        demolish_button_text(actions),
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ))
+                                    .insert(DemolishButtonText);
+                            });
+
+                        parent
+                            .spawn(ButtonBundle {
+                                style: Style {
+                                    size: Size::new(Val::Auto, Val::Px(50.0)),
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    padding: UiRect::all(Val::Px(10.0)),
+                                    position_type: PositionType::Relative,
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: BackgroundColor::from(Color::GRAY),
+                                ..Default::default()
+                            })
+                            .insert(BasicButton)
+                            .insert(UpgradeButton)
+                            .insert(GameUiThing)
+                            .with_children(|parent| {
+                                parent
+                                    .spawn(TextBundle::from_section(
+                                        upgrade_button_text(actions),
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ))
+                                    .insert(UpgradeButtonText);
+                            });
+
+                        // node wrapping the game speed controls
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    size: Size::new(Val::Auto, Val::Auto),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    position_type: PositionType::Relative,
+                                    flex_direction: FlexDirection::Column,
+                                    ..default()
+                                },
+                                background_color: Color::rgba(0.65, 0.65, 0.1, 0.0).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                back_and_forth_button(
+                                    parent,
+                                    &font_assets,
+                                    GameUiThing,
+                                    SlowDownButton,
+                                    game_speed.can_slow_down(),
+                                    SpeedUpButton,
+                                    game_speed.can_speed_up(),
+                                    "SPEED",
+                                );
+
+                                parent
+                                    .spawn(
+                                        TextBundle::from_section(
+                                            game_speed.to_string(),
+                                            TextStyle {
+                                                font: font_assets.fira_sans.clone(),
+                                                font_size: 40.0,
+                                                color: Color::GRAY,
+                                            },
+                                        )
+                                        .with_text_alignment(TextAlignment::Center)
+                                        .with_style(
+                                            Style {
+                                                position_type: PositionType::Relative,
+                                                justify_content: JustifyContent::Center,
+                                                align_items: AlignItems::Center,
+                                                margin: UiRect::all(Val::Px(5.0)),
+                                                size: Size::new(Val::Auto, Val::Auto),
+                                                ..default()
+                                            },
+                                        ),
+                                    )
+                                    .insert(GameSpeedText);
+
+                                parent
+                                    .spawn(NodeBundle {
+                                        style: Style {
+                                            size: Size::new(Val::Auto, Val::Auto),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            position_type: PositionType::Relative,
+                                            flex_direction: FlexDirection::Row,
+                                            ..default()
+                                        },
+                                        background_color: Color::rgba(0.65, 0.65, 0.1, 0.0).into(),
+                                        ..default()
+                                    })
+                                    .with_children(|parent| {
+                                        parent
+                                            .spawn(ButtonBundle {
+                                                style: Style {
+                                                    size: Size::new(Val::Auto, Val::Px(50.0)),
+                                                    margin: UiRect::all(Val::Px(5.0)),
+                                                    padding: UiRect::all(Val::Px(10.0)),
+                                                    position_type: PositionType::Relative,
+                                                    justify_content: JustifyContent::Center,
+                                                    align_items: AlignItems::Center,
+                                                    ..Default::default()
+                                                },
+                                                background_color: BackgroundColor::from(
+                                                    Color::GRAY,
+                                                ),
+                                                ..Default::default()
+                                            })
+                                            .insert(BasicButton)
+                                            .insert(HoldButton)
+                                            .insert(GameUiThing)
+                                            .with_children(|parent| {
+                                                parent
+                                                    .spawn(TextBundle::from_section(
+                                                        hold_button_text(&game_speed),
+                                                        TextStyle {
+                                                            font: font_assets.fira_sans.clone(),
+                                                            font_size: 40.0,
+                                                            color: Color::BLACK,
+                                                        },
+                                                    ))
+                                                    .insert(HoldButtonText);
+                                            });
+
+                                        let mut step_button = parent.spawn(ButtonBundle {
+                                            style: Style {
+                                                size: Size::new(Val::Auto, Val::Px(50.0)),
+                                                margin: UiRect::all(Val::Px(5.0)),
+                                                padding: UiRect::all(Val::Px(10.0)),
+                                                position_type: PositionType::Relative,
+                                                justify_content: JustifyContent::Center,
+                                                align_items: AlignItems::Center,
+                                                ..Default::default()
+                                            },
+                                            background_color: match game_speed.held {
+                                                true => BackgroundColor::from(Color::GRAY),
+                                                false => BackgroundColor::from(Color::DARK_GRAY),
+                                            },
+                                            ..Default::default()
+                                        });
+                                        step_button
+                                            .insert(BasicButton)
+                                            .insert(StepButton)
+                                            .insert(GameUiThing)
+                                            .with_children(|parent| {
+                                                parent.spawn(TextBundle::from_section(
+                                                    "STEP",
+                                                    TextStyle {
+                                                        font: font_assets.fira_sans.clone(),
+                                                        font_size: 40.0,
+                                                        color: Color::BLACK,
+                                                    },
+                                                ));
+                                            });
+
+                                        if !game_speed.held {
+                                            step_button.insert(DisabledButton);
+                                        }
+                                    });
+                            });
+
                         // node wrapping the actual buttons
                         parent
                             .spawn(NodeBundle {
@@ -592,30 +920,42 @@ fn button_interaction(
             Entity,
             &Interaction,
             Option<&DisabledButton>,
-            Option<&PulserButtonMarker>,
-            Option<&ScatterButtonMarker>,
-            Option<&LineButtonMarker>,
-            Option<&NukeButtonMarker>,
-            Option<&FortifyButtonMarker>,
-            Option<&ExpandButtonMarker>,
+            Option<&BuildingButtonMarker>,
+            (
+                Option<&NukeButtonMarker>,
+                Option<&FortifyButtonMarker>,
+                Option<&ExpandButtonMarker>,
+                Option<&FreezeButtonMarker>,
+                Option<&SwapButtonMarker>,
+                Option<&ShieldButtonMarker>,
+            ),
             Option<&PauseButton>,
+            Option<&SlowDownButton>,
+            Option<&SpeedUpButton>,
+            Option<&HoldButton>,
+            (
+                Option<&StepButton>,
+                Option<&DemolishButton>,
+                Option<&UpgradeButton>,
+            ),
         ),
         (Changed<Interaction>, (With<Button>, With<GameUiThing>)),
     >,
     mut actions: Query<(&PlayerMarker, &mut Actions)>,
+    mut game_speed: ResMut<GameSpeed>,
     mut commands: Commands,
 ) {
     for (
         entity,
         interaction,
         option_disabled,
-        option_pbm,
-        option_sbm,
-        option_lbm,
-        option_nbm,
-        option_fbm,
-        option_ebm,
+        option_bbm,
+        (option_nbm, option_fbm, option_ebm, option_zbm, option_swbm, option_shbm),
         option_pb,
+        option_sdb,
+        option_sub,
+        option_hb,
+        (option_stb, option_db, option_ub),
     ) in &mut interaction_query
     {
         for (player, mut actions) in actions.iter_mut() {
@@ -630,16 +970,8 @@ fn button_interaction(
                 continue;
             }
 
-            if let Some(_) = option_pbm {
-                actions.selected_building = Pulser;
-                commands.entity(entity).insert(NewSelectedButton);
-            }
-            if let Some(_) = option_sbm {
-                actions.selected_building = Scatter;
-                commands.entity(entity).insert(NewSelectedButton);
-            }
-            if let Some(_) = option_lbm {
-                actions.selected_building = Line;
+            if let Some(building_button_marker) = option_bbm {
+                actions.selected_building = building_button_marker.0;
                 commands.entity(entity).insert(NewSelectedButton);
             }
 
@@ -655,10 +987,104 @@ fn button_interaction(
                 actions.selected_ability = Abilities::Expand;
                 commands.entity(entity).insert(NewSelectedButton);
             }
+            if let Some(_) = option_zbm {
+                actions.selected_ability = Abilities::Freeze;
+                commands.entity(entity).insert(NewSelectedButton);
+            }
+            if let Some(_) = option_swbm {
+                actions.selected_ability = Abilities::Swap;
+                commands.entity(entity).insert(NewSelectedButton);
+            }
+            if let Some(_) = option_shbm {
+                actions.selected_ability = Abilities::Shield;
+                commands.entity(entity).insert(NewSelectedButton);
+            }
 
             if let Some(_) = option_pb {
                 state.set(GamePausedState::Paused);
             }
+
+            if let Some(_) = option_sdb {
+                game_speed.slow_down();
+            }
+            if let Some(_) = option_sub {
+                game_speed.speed_up();
+            }
+            if let Some(_) = option_hb {
+                game_speed.toggle_held();
+            }
+            if let Some(_) = option_stb {
+                commands.insert_resource(StepGame);
+            }
+            if let Some(_) = option_db {
+                actions.click_mode.toggle(ClickMode::Demolish);
+            }
+            if let Some(_) = option_ub {
+                actions.click_mode.toggle(ClickMode::Upgrade);
+            }
+        }
+    }
+}
+
+fn update_click_mode_buttons(
+    mut demolish_texts: Query<&mut Text, (With<DemolishButtonText>, Without<UpgradeButtonText>)>,
+    mut upgrade_texts: Query<&mut Text, (With<UpgradeButtonText>, Without<DemolishButtonText>)>,
+    actions: Query<(&PlayerMarker, &Actions), Changed<Actions>>,
+) {
+    for (player, actions) in actions.iter() {
+        if player.id() != 0 {
+            continue;
+        }
+        for mut text in demolish_texts.iter_mut() {
+            text.sections[0].value = demolish_button_text(actions).to_string();
+        }
+        for mut text in upgrade_texts.iter_mut() {
+            text.sections[0].value = upgrade_button_text(actions).to_string();
+        }
+    }
+}
+
+fn update_game_speed_controls(
+    mut speed_texts: Query<&mut Text, (With<GameSpeedText>, Without<HoldButtonText>)>,
+    mut hold_texts: Query<&mut Text, (With<HoldButtonText>, Without<GameSpeedText>)>,
+    mut buttons: Query<(
+        Entity,
+        Option<&DisabledButton>,
+        Option<&SlowDownButton>,
+        Option<&SpeedUpButton>,
+        Option<&StepButton>,
+        &mut BackgroundColor,
+    )>,
+    game_speed: Res<GameSpeed>,
+    mut commands: Commands,
+) {
+    for mut text in speed_texts.iter_mut() {
+        text.sections[0].value = game_speed.to_string();
+    }
+    for mut text in hold_texts.iter_mut() {
+        text.sections[0].value = hold_button_text(&game_speed).to_string();
+    }
+
+    for (entity, option_disabled_button, option_1, option_2, option_3, mut background_color) in
+        buttons.iter_mut()
+    {
+        let enable_button = match (option_1, option_2, option_3) {
+            (Some(_), _, _) => game_speed.can_slow_down(),
+            (_, Some(_), _) => game_speed.can_speed_up(),
+            (_, _, Some(_)) => game_speed.held,
+            _ => continue,
+        };
+
+        match (enable_button, option_disabled_button) {
+            (true, Some(_)) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
+            }
+            (false, None) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
+            }
+            _ => {}
         }
     }
 }
@@ -761,10 +1187,13 @@ fn handle_player_cubes_and_stats(
     font_assets: Res<FontAssets>,
     game: Res<GameData>,
     game_build_settings: Res<GameBuildSettings>,
+    game_core: Res<Game>,
 ) {
     let Ok((entity, player_cubes_parent)) = player_cubes_parent.get_single() else {
         return;
     };
+    let survival_state = game_core.game_world.get_resource::<SurvivalState>();
+    let puzzle_state = game_core.game_world.get_resource::<PuzzleState>();
     commands.entity(entity).despawn_descendants();
 
     commands.entity(entity).with_children(|mut parent| {
@@ -777,6 +1206,61 @@ fn handle_player_cubes_and_stats(
             &game,
             &game_build_settings,
         );
+
+        if let Some(survival_state) = survival_state {
+            parent.spawn(
+                TextBundle::from_section(
+                    format!(
+                        "Wave {} - Next in {}\nHeld: {}",
+                        survival_state.waves_spawned,
+                        format_survival_time(survival_state.seconds_until_wave.max(0.0)),
+                        format_survival_time(survival_state.score)
+                    ),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: player_colors.get_color(0),
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center)
+                .with_style(Style {
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(10.0)),
+                    size: Size::new(Val::Auto, Val::Auto),
+                    ..default()
+                }),
+            );
+        }
+
+        if let Some(puzzle_state) = puzzle_state {
+            parent.spawn(
+                TextBundle::from_section(
+                    format!(
+                        "Target {}/{} - {} left\n{}",
+                        puzzle_state.captured_tiles,
+                        puzzle_state.target_tiles,
+                        format_survival_time(puzzle_state.seconds_left.max(0.0)),
+                        puzzle_state.inventory
+                    ),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: player_colors.get_color(0),
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center)
+                .with_style(Style {
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(10.0)),
+                    size: Size::new(Val::Auto, Val::Auto),
+                    ..default()
+                }),
+            );
+        }
     });
 }
 
@@ -813,6 +1297,9 @@ fn generate_all_player_cubes(
     let mut players: Vec<(&Player, &PlayerPoints)> = player_queries.iter().collect();
     players.sort_by(|a, b| a.0.id().cmp(&b.0.id()));
 
+    // shrink the cubes once there are more than four players so every row still fits
+    let cube_scale = (4.0 / players.len().max(4) as f32).sqrt();
+
     // for each player generate their cubes and add them plus get players points and display them
     for (player_query, player_points) in players.iter().rev() {
         if player_query.id() == 0 {
@@ -849,6 +1336,7 @@ fn generate_all_player_cubes(
                 .clone() as usize,
             max_tile_count,
             &player_colors,
+            cube_scale,
         );
         if player_query.id() == 0 {
             for (player_query, player_points) in player_queries.iter() {
@@ -903,6 +1391,7 @@ fn generate_player_cubes(
     player_tile_count: usize,
     max_tile_count: usize,
     player_colors: &Res<PlayerColors>,
+    cube_scale: f32,
 ) {
     parent
         .spawn(NodeBundle {
@@ -912,7 +1401,7 @@ fn generate_player_cubes(
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
                 flex_direction: FlexDirection::RowReverse,
-                margin: UiRect::all(Val::Px(5.0)),
+                margin: UiRect::all(Val::Px(5.0 * cube_scale)),
                 padding: UiRect::right(Val::Px(35.0)),
                 ..default()
             },
@@ -928,11 +1417,11 @@ fn generate_player_cubes(
             };
             parent.spawn(NodeBundle {
                 style: Style {
-                    size: Size::new(Val::Px(10.0), Val::Px(25.0)),
+                    size: Size::new(Val::Px(10.0 * cube_scale), Val::Px(25.0 * cube_scale)),
                     position_type: PositionType::Relative,
                     justify_content: JustifyContent::Center,
                     align_items: AlignItems::Center,
-                    margin: UiRect::all(Val::Px(5.0)),
+                    margin: UiRect::all(Val::Px(5.0 * cube_scale)),
                     ..default()
                 },
                 background_color: BackgroundColor::from(color),
@@ -948,11 +1437,11 @@ fn generate_player_cubes(
 
                 parent.spawn(NodeBundle {
                     style: Style {
-                        size: Size::new(Val::Px(25.0), Val::Px(25.0)),
+                        size: Size::new(Val::Px(25.0 * cube_scale), Val::Px(25.0 * cube_scale)),
                         position_type: PositionType::Relative,
                         justify_content: JustifyContent::Center,
                         align_items: AlignItems::Center,
-                        margin: UiRect::all(Val::Px(5.0)),
+                        margin: UiRect::all(Val::Px(5.0 * cube_scale)),
                         ..default()
                     },
                     background_color: BackgroundColor::from(color),
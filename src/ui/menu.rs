@@ -1,7 +1,8 @@
 use crate::audio::GameSoundSettings;
 use crate::game::game_difficulty::GameDifficulty;
-use crate::game::{setup_game_resource, GameBuildSettings};
+use crate::game::{setup_game_resource, GameBuildSettings, MAX_PLAYER_COUNT};
 use crate::level_loader::{LevelHandle, Levels};
+use crate::loading::campaign_loader::{Campaign, CampaignHandle, CampaignProgress};
 use crate::loading::FontAssets;
 use crate::GameState;
 use bevy::app::AppExit;
@@ -10,6 +11,11 @@ use bevy::prelude::*;
 
 use crate::ui::{modal_panel, BasicButton, DisabledButton, ModalStyle, PlayerColors};
 
+use super::accessibility::AccessibilitySettings;
+use super::campaign_menu::spawn_campaign_menu;
+use super::challenge_menu::spawn_challenge_menu;
+use super::palette_editor::spawn_palette_editor;
+use super::puzzle_menu::spawn_puzzle_menu;
 use super::settings_menu::spawn_settings_menu;
 use super::UpdateTextColorWithCurrentPlayerColor;
 
@@ -33,6 +39,13 @@ impl Plugin for MenuPlugin {
                 update_map_name,
                 update_color_swatches,
                 update_game_difficulty_text,
+                update_building_loss_rule_text,
+                update_tile_strength_rules_text,
+                update_territory_decay_text,
+                update_power_ups_text,
+                update_sudden_death_text,
+                update_game_mode_text,
+                update_survival_hold_text,
                 update_title_text_colors,
             )
                 .chain()
@@ -48,6 +61,15 @@ struct MenuUiThing;
 #[derive(Component)]
 struct PlayButton;
 
+#[derive(Component)]
+struct CampaignButton;
+
+#[derive(Component)]
+struct PuzzlesButton;
+
+#[derive(Component)]
+struct DailyChallengeButton;
+
 #[derive(Component)]
 struct NextMapButton;
 
@@ -90,6 +112,18 @@ struct PlayerCountText;
 #[derive(Component)]
 struct ColorSwatch(u8);
 
+#[derive(Component)]
+struct NextVisionButton;
+
+#[derive(Component)]
+struct PrevVisionButton;
+
+#[derive(Component)]
+struct VisionText;
+
+#[derive(Component)]
+struct EditPaletteButton;
+
 #[derive(Component)]
 struct UpdateMapSizeButtonColors;
 
@@ -108,10 +142,92 @@ struct DecreaseDifficultyButton;
 #[derive(Component)]
 struct GameDifficultyText;
 
+#[derive(Component)]
+struct NextBuildingLossRuleButton;
+
+#[derive(Component)]
+struct PrevBuildingLossRuleButton;
+
+#[derive(Component)]
+struct BuildingLossRuleText;
+
+#[derive(Component)]
+struct NextTileStrengthRulesButton;
+
+#[derive(Component)]
+struct PrevTileStrengthRulesButton;
+
+#[derive(Component)]
+struct TileStrengthRulesText;
+
+#[derive(Component)]
+struct IncreaseMaxStrengthButton;
+
+#[derive(Component)]
+struct DecreaseMaxStrengthButton;
+
+#[derive(Component)]
+struct IncreaseDamageButton;
+
+#[derive(Component)]
+struct DecreaseDamageButton;
+
+#[derive(Component)]
+struct IncreaseHealButton;
+
+#[derive(Component)]
+struct DecreaseHealButton;
+
+#[derive(Component)]
+struct NextTerritoryDecayButton;
+
+#[derive(Component)]
+struct PrevTerritoryDecayButton;
+
+#[derive(Component)]
+struct TerritoryDecayText;
+
+#[derive(Component)]
+struct NextPowerUpsButton;
+
+#[derive(Component)]
+struct PrevPowerUpsButton;
+
+#[derive(Component)]
+struct PowerUpsText;
+
+#[derive(Component)]
+struct NextSuddenDeathButton;
+
+#[derive(Component)]
+struct PrevSuddenDeathButton;
+
+#[derive(Component)]
+struct SuddenDeathText;
+
+#[derive(Component)]
+struct NextGameModeButton;
+
+#[derive(Component)]
+struct PrevGameModeButton;
+
+#[derive(Component)]
+struct GameModeText;
+
+#[derive(Component)]
+struct IncreaseSurvivalHoldButton;
+
+#[derive(Component)]
+struct DecreaseSurvivalHoldButton;
+
+#[derive(Component)]
+struct SurvivalHoldText;
+
 pub fn setup_menu(
     mut commands: Commands,
     font_assets: Res<FontAssets>,
     player_colors: Res<PlayerColors>,
+    accessibility_settings: Res<AccessibilitySettings>,
     game_build_settings: ResMut<GameBuildSettings>,
     level_handles: Res<LevelHandle>,
     level_assets: Res<Assets<Levels>>,
@@ -236,6 +352,87 @@ pub fn setup_menu(
                                         },
                                     ));
                                 });
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Auto, Val::Px(50.0)),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        padding: UiRect::all(Val::Px(10.0)),
+
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: BackgroundColor::from(Color::GRAY),
+                                    ..Default::default()
+                                })
+                                .insert(MenuUiThing)
+                                .insert(CampaignButton)
+                                .insert(BasicButton)
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "CAMPAIGN",
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Auto, Val::Px(50.0)),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        padding: UiRect::all(Val::Px(10.0)),
+
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: BackgroundColor::from(Color::GRAY),
+                                    ..Default::default()
+                                })
+                                .insert(MenuUiThing)
+                                .insert(PuzzlesButton)
+                                .insert(BasicButton)
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "PUZZLES",
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ));
+                                });
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Auto, Val::Px(50.0)),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        padding: UiRect::all(Val::Px(10.0)),
+
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: BackgroundColor::from(Color::GRAY),
+                                    ..Default::default()
+                                })
+                                .insert(MenuUiThing)
+                                .insert(DailyChallengeButton)
+                                .insert(BasicButton)
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "DAILY CHALLENGE",
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ));
+                                });
                             let backward_enabled = !game_build_settings.map_type == 0;
                             let forward_enabled = game_build_settings.map_type > 0
                                 && game_build_settings.map_type < game_build_settings.max_map - 1;
@@ -348,70 +545,94 @@ pub fn setup_menu(
                                     ..default()
                                 })
                                 .with_children(|parent| {
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Px(50.0), Val::Px(50.0)),
-                                                position_type: PositionType::Relative,
-                                                justify_content: JustifyContent::Center,
-                                                align_items: AlignItems::Center,
-                                                margin: UiRect::all(Val::Px(5.0)),
-                                                ..default()
-                                            },
-                                            background_color: BackgroundColor::from(
-                                                player_colors.get_color(0),
-                                            ),
-                                            ..default()
-                                        })
-                                        .insert(ColorSwatch(0));
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Px(50.0), Val::Px(50.0)),
-                                                position_type: PositionType::Relative,
-                                                justify_content: JustifyContent::Center,
-                                                align_items: AlignItems::Center,
-                                                margin: UiRect::all(Val::Px(5.0)),
-                                                ..default()
-                                            },
-                                            background_color: BackgroundColor::from(
-                                                player_colors.get_color(1),
-                                            ),
-                                            ..default()
-                                        })
-                                        .insert(ColorSwatch(1));
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Px(50.0), Val::Px(50.0)),
-                                                position_type: PositionType::Relative,
-                                                justify_content: JustifyContent::Center,
-                                                align_items: AlignItems::Center,
-                                                margin: UiRect::all(Val::Px(5.0)),
-                                                ..default()
-                                            },
-                                            background_color: BackgroundColor::from(
-                                                player_colors.get_color(2),
-                                            ),
-                                            ..default()
-                                        })
-                                        .insert(ColorSwatch(2));
-                                    parent
-                                        .spawn(NodeBundle {
-                                            style: Style {
-                                                size: Size::new(Val::Px(50.0), Val::Px(50.0)),
-                                                position_type: PositionType::Relative,
-                                                justify_content: JustifyContent::Center,
-                                                align_items: AlignItems::Center,
-                                                margin: UiRect::all(Val::Px(5.0)),
+                                    for player_id in 0..MAX_PLAYER_COUNT {
+                                        parent
+                                            .spawn(NodeBundle {
+                                                style: Style {
+                                                    size: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                                                    position_type: PositionType::Relative,
+                                                    justify_content: JustifyContent::Center,
+                                                    align_items: AlignItems::Center,
+                                                    margin: UiRect::all(Val::Px(5.0)),
+                                                    ..default()
+                                                },
+                                                background_color: BackgroundColor::from(
+                                                    accessibility_settings.vision_preview.simulate(
+                                                        player_colors.get_color(player_id),
+                                                    ),
+                                                ),
                                                 ..default()
-                                            },
-                                            background_color: BackgroundColor::from(
-                                                player_colors.get_color(3),
-                                            ),
-                                            ..default()
-                                        })
-                                        .insert(ColorSwatch(3));
+                                            })
+                                            .insert(ColorSwatch(player_id as u8));
+                                    }
+                                });
+
+                            let backward = accessibility_settings.vision_preview
+                                != accessibility_settings.vision_preview.prev();
+                            let forward = accessibility_settings.vision_preview
+                                != accessibility_settings.vision_preview.next();
+
+                            back_and_forth_button(
+                                parent,
+                                &font_assets,
+                                MenuUiThing,
+                                PrevVisionButton,
+                                backward,
+                                NextVisionButton,
+                                forward,
+                                "PREVIEW",
+                            );
+
+                            parent
+                                .spawn(
+                                    TextBundle::from_section(
+                                        vision_preview_text(
+                                            &player_colors,
+                                            &accessibility_settings,
+                                        ),
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 30.0,
+                                            color: Color::GRAY,
+                                        },
+                                    )
+                                    .with_text_alignment(TextAlignment::Center)
+                                    .with_style(Style {
+                                        position_type: PositionType::Relative,
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        margin: UiRect::all(Val::Px(5.0)),
+                                        size: Size::new(Val::Auto, Val::Auto),
+                                        ..default()
+                                    }),
+                                )
+                                .insert(VisionText);
+
+                            parent
+                                .spawn(ButtonBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Auto, Val::Px(50.0)),
+                                        margin: UiRect::all(Val::Px(10.0)),
+                                        padding: UiRect::all(Val::Px(10.0)),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: BackgroundColor::from(Color::GRAY),
+                                    ..Default::default()
+                                })
+                                .insert(MenuUiThing)
+                                .insert(EditPaletteButton)
+                                .insert(BasicButton)
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "EDIT CLRS",
+                                        TextStyle {
+                                            font: font_assets.fira_sans.clone(),
+                                            font_size: 40.0,
+                                            color: Color::BLACK,
+                                        },
+                                    ));
                                 });
 
                             parent.spawn(NodeBundle {
@@ -616,24 +837,21 @@ pub fn setup_menu(
                         )
                         .insert(UpdateTextColorWithCurrentPlayerColor);
 
-                    let backward = game_build_settings.game_difficulty != GameDifficulty::Easy;
-                    let forward = game_build_settings.game_difficulty != GameDifficulty::Hard;
-
                     back_and_forth_button(
                         parent,
                         &font_assets,
                         MenuUiThing,
-                        DecreaseDifficultyButton,
-                        backward,
-                        IncreaseDifficultyButton,
-                        forward,
-                        "DIFFICULTY",
+                        PrevGameModeButton,
+                        true,
+                        NextGameModeButton,
+                        true,
+                        "MODE",
                     );
 
                     parent
                         .spawn(
                             TextBundle::from_section(
-                                format!("{}", game_build_settings.game_difficulty,),
+                                format!("{}", game_build_settings.game_mode),
                                 TextStyle {
                                     font: font_assets.fira_sans.clone(),
                                     font_size: 40.0,
@@ -650,26 +868,23 @@ pub fn setup_menu(
                                 ..default()
                             }),
                         )
-                        .insert(GameDifficultyText);
-
-                    let backward = game_build_settings.enemy_count != 1;
-                    let forward = game_build_settings.enemy_count != 3;
+                        .insert(GameModeText);
 
                     back_and_forth_button(
                         parent,
                         &font_assets,
                         MenuUiThing,
-                        DecreasePlayerCountButton,
-                        backward,
-                        IncreasePlayerCountButton,
-                        forward,
-                        "ENEMIES",
+                        DecreaseSurvivalHoldButton,
+                        true,
+                        IncreaseSurvivalHoldButton,
+                        true,
+                        "SURVIVAL HOLD",
                     );
 
                     parent
                         .spawn(
                             TextBundle::from_section(
-                                format!("{}", game_build_settings.enemy_count,),
+                                survival_hold_text(&game_build_settings),
                                 TextStyle {
                                     font: font_assets.fira_sans.clone(),
                                     font_size: 40.0,
@@ -686,20 +901,292 @@ pub fn setup_menu(
                                 ..default()
                             }),
                         )
-                        .insert(PlayerCountText);
-                });
-        });
-}
+                        .insert(SurvivalHoldText);
 
-fn click_play_button(
-    mut state: ResMut<NextState<GameState>>,
-    mut exit: EventWriter<AppExit>,
-    mut game_build_settings: ResMut<GameBuildSettings>,
-    mut player_colors: ResMut<PlayerColors>,
-    keyboard_input: Res<Input<KeyCode>>,
-    mut commands: Commands,
-    mut interaction_query: Query<
-        (
+                    let backward = game_build_settings.game_difficulty != GameDifficulty::Easy;
+                    let forward = game_build_settings.game_difficulty != GameDifficulty::Hard;
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        DecreaseDifficultyButton,
+                        backward,
+                        IncreaseDifficultyButton,
+                        forward,
+                        "DIFFICULTY",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                format!("{}", game_build_settings.game_difficulty,),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(GameDifficultyText);
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        PrevBuildingLossRuleButton,
+                        true,
+                        NextBuildingLossRuleButton,
+                        true,
+                        "LOST BUILDINGS",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                format!("{}", game_build_settings.building_loss_rule),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(BuildingLossRuleText);
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        PrevTileStrengthRulesButton,
+                        true,
+                        NextTileStrengthRulesButton,
+                        true,
+                        "TILE STRENGTH",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                format!("{}", game_build_settings.tile_strength_rules),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(TileStrengthRulesText);
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        DecreaseMaxStrengthButton,
+                        true,
+                        IncreaseMaxStrengthButton,
+                        true,
+                        "MAX STRENGTH",
+                    );
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        DecreaseDamageButton,
+                        true,
+                        IncreaseDamageButton,
+                        true,
+                        "DAMAGE",
+                    );
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        DecreaseHealButton,
+                        true,
+                        IncreaseHealButton,
+                        true,
+                        "HEAL",
+                    );
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        PrevTerritoryDecayButton,
+                        true,
+                        NextTerritoryDecayButton,
+                        true,
+                        "SUPPLY DECAY",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                on_off_text(game_build_settings.territory_decay),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(TerritoryDecayText);
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        PrevPowerUpsButton,
+                        true,
+                        NextPowerUpsButton,
+                        true,
+                        "POWER-UPS",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                on_off_text(game_build_settings.power_ups),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(PowerUpsText);
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        PrevSuddenDeathButton,
+                        true,
+                        NextSuddenDeathButton,
+                        true,
+                        "SUDDEN DEATH",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                format!("{}", game_build_settings.sudden_death),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(SuddenDeathText);
+
+                    let backward = game_build_settings.enemy_count != 1;
+                    let forward =
+                        game_build_settings.enemy_count < game_build_settings.max_enemy_count();
+
+                    back_and_forth_button(
+                        parent,
+                        &font_assets,
+                        MenuUiThing,
+                        DecreasePlayerCountButton,
+                        backward,
+                        IncreasePlayerCountButton,
+                        forward,
+                        "ENEMIES",
+                    );
+
+                    parent
+                        .spawn(
+                            TextBundle::from_section(
+                                format!("{}", game_build_settings.enemy_count,),
+                                TextStyle {
+                                    font: font_assets.fira_sans.clone(),
+                                    font_size: 40.0,
+                                    color: Color::GRAY,
+                                },
+                            )
+                            .with_text_alignment(TextAlignment::Center)
+                            .with_style(Style {
+                                position_type: PositionType::Relative,
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                size: Size::new(Val::Auto, Val::Auto),
+                                ..default()
+                            }),
+                        )
+                        .insert(PlayerCountText);
+                });
+        });
+}
+
+fn click_play_button(
+    mut state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+    mut game_build_settings: ResMut<GameBuildSettings>,
+    mut player_colors: ResMut<PlayerColors>,
+    mut accessibility_settings: ResMut<AccessibilitySettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut commands: Commands,
+    mut interaction_query: Query<
+        (
             Entity,
             &Interaction,
             Option<&DisabledButton>,
@@ -713,9 +1200,39 @@ fn click_play_button(
             (
                 Option<&IncreaseDifficultyButton>,
                 Option<&DecreaseDifficultyButton>,
+                Option<&NextBuildingLossRuleButton>,
+                Option<&PrevBuildingLossRuleButton>,
+                Option<&NextTileStrengthRulesButton>,
+                Option<&PrevTileStrengthRulesButton>,
+                Option<&NextTerritoryDecayButton>,
+                Option<&PrevTerritoryDecayButton>,
+                Option<&NextPowerUpsButton>,
+                Option<&PrevPowerUpsButton>,
+                Option<&NextSuddenDeathButton>,
+                Option<&PrevSuddenDeathButton>,
+                Option<&NextGameModeButton>,
+                Option<&PrevGameModeButton>,
+            ),
+            (
+                Option<&NextColorButton>,
+                Option<&PrevColorButton>,
+                Option<&NextVisionButton>,
+                Option<&PrevVisionButton>,
+                Option<&EditPaletteButton>,
+                Option<&CampaignButton>,
+                Option<&PuzzlesButton>,
+                Option<&DailyChallengeButton>,
+            ),
+            (
+                Option<&IncreaseMaxStrengthButton>,
+                Option<&DecreaseMaxStrengthButton>,
+                Option<&IncreaseDamageButton>,
+                Option<&DecreaseDamageButton>,
+                Option<&IncreaseHealButton>,
+                Option<&DecreaseHealButton>,
+                Option<&IncreaseSurvivalHoldButton>,
+                Option<&DecreaseSurvivalHoldButton>,
             ),
-            Option<&NextColorButton>,
-            Option<&PrevColorButton>,
             Option<&QuitButton>,
             Option<&SettingsButton>,
         ),
@@ -723,6 +1240,9 @@ fn click_play_button(
     >,
     font_assets: Res<FontAssets>,
     sound_settings: Res<GameSoundSettings>,
+    campaign_handle: Res<CampaignHandle>,
+    campaign_assets: Res<Assets<Campaign>>,
+    campaign_progress: Res<CampaignProgress>,
 ) {
     for (
         _,
@@ -735,9 +1255,42 @@ fn click_play_button(
         option_dmsb,
         option_ipcb,
         option_dpcb,
-        (option_idb, option_ddb),
-        option_ncb,
-        option_pcb,
+        (
+            option_idb,
+            option_ddb,
+            option_nblb,
+            option_pblb,
+            option_ntsb,
+            option_ptsb,
+            option_ntdb,
+            option_ptdb,
+            option_npub,
+            option_ppub,
+            option_nsdb,
+            option_psdb,
+            option_ngmb,
+            option_pgmb,
+        ),
+        (
+            option_ncb,
+            option_pcb,
+            option_nvb,
+            option_pvb,
+            option_epb,
+            option_cb,
+            option_pzb,
+            option_dcb,
+        ),
+        (
+            option_imxb,
+            option_dmxb,
+            option_idmb,
+            option_ddmb,
+            option_ihb,
+            option_dhb,
+            option_ishb,
+            option_dshb,
+        ),
         option_qb,
         option_sb,
     ) in &mut interaction_query
@@ -753,9 +1306,40 @@ fn click_play_button(
         let modifier = keyboard_input.pressed(KeyCode::LShift);
 
         if let Some(_) = option_pb {
+            game_build_settings.leave_scenario();
             state.set(GameState::Playing);
         }
 
+        if let Some(_) = option_cb {
+            if let Some(campaign) = campaign_assets.get(&campaign_handle.campaign) {
+                spawn_campaign_menu(
+                    MenuUiThing,
+                    &mut commands,
+                    &font_assets,
+                    campaign,
+                    campaign_progress.as_ref(),
+                );
+            }
+        }
+
+        if let Some(_) = option_pzb {
+            spawn_puzzle_menu(
+                MenuUiThing,
+                &mut commands,
+                &font_assets,
+                game_build_settings.puzzle.unwrap_or(0),
+            );
+        }
+
+        if let Some(_) = option_dcb {
+            spawn_challenge_menu(
+                MenuUiThing,
+                &mut commands,
+                &font_assets,
+                game_build_settings.as_ref(),
+            );
+        }
+
         {
             if let Some(_) = option_nmb {
                 game_build_settings.next_map();
@@ -787,6 +1371,79 @@ fn click_play_button(
             }
         }
 
+        if option_nblb.is_some() || option_pblb.is_some() {
+            game_build_settings.building_loss_rule =
+                game_build_settings.building_loss_rule.toggle();
+        }
+
+        {
+            if let Some(_) = option_ntsb {
+                game_build_settings.tile_strength_rules =
+                    game_build_settings.tile_strength_rules.next_preset();
+            }
+            if let Some(_) = option_ptsb {
+                game_build_settings.tile_strength_rules =
+                    game_build_settings.tile_strength_rules.prev_preset();
+            }
+            if let Some(_) = option_imxb {
+                game_build_settings
+                    .tile_strength_rules
+                    .increase_max_strength();
+            }
+            if let Some(_) = option_dmxb {
+                game_build_settings
+                    .tile_strength_rules
+                    .decrease_max_strength();
+            }
+            if let Some(_) = option_idmb {
+                game_build_settings.tile_strength_rules.increase_damage();
+            }
+            if let Some(_) = option_ddmb {
+                game_build_settings.tile_strength_rules.decrease_damage();
+            }
+            if let Some(_) = option_ihb {
+                game_build_settings.tile_strength_rules.increase_heal();
+            }
+            if let Some(_) = option_dhb {
+                game_build_settings.tile_strength_rules.decrease_heal();
+            }
+        }
+
+        if option_ntdb.is_some() || option_ptdb.is_some() {
+            game_build_settings.territory_decay = !game_build_settings.territory_decay;
+        }
+
+        if option_npub.is_some() || option_ppub.is_some() {
+            game_build_settings.power_ups = !game_build_settings.power_ups;
+        }
+
+        {
+            if let Some(_) = option_nsdb {
+                game_build_settings.sudden_death = game_build_settings.sudden_death.next();
+            }
+            if let Some(_) = option_psdb {
+                game_build_settings.sudden_death = game_build_settings.sudden_death.prev();
+            }
+        }
+
+        {
+            if let Some(_) = option_ishb {
+                game_build_settings.increase_survival_hold_percent();
+            }
+            if let Some(_) = option_dshb {
+                game_build_settings.decrease_survival_hold_percent();
+            }
+        }
+
+        {
+            if let Some(_) = option_ngmb {
+                game_build_settings.game_mode = game_build_settings.game_mode.next();
+            }
+            if let Some(_) = option_pgmb {
+                game_build_settings.game_mode = game_build_settings.game_mode.prev();
+            }
+        }
+
         {
             if let Some(_) = option_ipcb {
                 game_build_settings.increase_enemy_count();
@@ -805,6 +1462,26 @@ fn click_play_button(
             }
         }
 
+        {
+            if let Some(_) = option_nvb {
+                accessibility_settings.vision_preview =
+                    accessibility_settings.vision_preview.next();
+            }
+            if let Some(_) = option_pvb {
+                accessibility_settings.vision_preview =
+                    accessibility_settings.vision_preview.prev();
+            }
+        }
+
+        if let Some(_) = option_epb {
+            spawn_palette_editor(
+                MenuUiThing,
+                &mut commands,
+                &font_assets,
+                player_colors.as_ref(),
+            );
+        }
+
         if let Some(_) = option_sb {
             spawn_settings_menu(
                 MenuUiThing,
@@ -817,6 +1494,7 @@ fn click_play_button(
                 &font_assets,
                 player_colors.as_ref(),
                 &sound_settings,
+                accessibility_settings.as_ref(),
             );
         }
 
@@ -983,52 +1661,106 @@ fn update_enemies_count(
         text.sections[0].value = format!("{}", game_build_settings.enemy_count);
     }
 
+    let backward = game_build_settings.enemy_count > 1;
+    let forward = game_build_settings.enemy_count < game_build_settings.max_enemy_count();
+
     for (entity, option_disabled_button, option_1, option_2, mut background_color) in
         buttons.iter_mut()
     {
-        if game_build_settings.enemy_count == 1 {
-            if let Some(_) = option_1 {
-                if let None = option_disabled_button {
-                    background_color.0 = Color::DARK_GRAY;
-                    commands.entity(entity).insert(DisabledButton);
-                }
-            }
-            if let Some(_) = option_2 {
-                if let Some(_) = option_disabled_button {
-                    background_color.0 = Color::GRAY;
-                    commands.entity(entity).remove::<DisabledButton>();
-                }
-            }
-        }
-        if game_build_settings.enemy_count == 3 {
-            if let Some(_) = option_1 {
-                if let Some(_) = option_disabled_button {
-                    background_color.0 = Color::GRAY;
-                    commands.entity(entity).remove::<DisabledButton>();
-                }
+        let enable_button = match (option_1, option_2) {
+            (Some(_), _) => backward,
+            (_, Some(_)) => forward,
+            _ => continue,
+        };
+
+        match (enable_button, option_disabled_button) {
+            (true, Some(_)) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
             }
-            if let Some(_) = option_2 {
-                if let None = option_disabled_button {
-                    background_color.0 = Color::DARK_GRAY;
-                    commands.entity(entity).insert(DisabledButton);
-                }
+            (false, None) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
             }
+            _ => {}
         }
+    }
+}
 
-        if game_build_settings.enemy_count > 1 && game_build_settings.enemy_count < 3 {
-            if let Some(_) = option_1 {
-                if let Some(_) = option_disabled_button {
-                    background_color.0 = Color::GRAY;
-                    commands.entity(entity).remove::<DisabledButton>();
-                }
-            }
-            if let Some(_) = option_2 {
-                if let Some(_) = option_disabled_button {
-                    background_color.0 = Color::GRAY;
-                    commands.entity(entity).remove::<DisabledButton>();
-                }
-            }
-        }
+fn update_building_loss_rule_text(
+    mut texts: Query<&mut Text, With<BuildingLossRuleText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("{}", game_build_settings.building_loss_rule);
+    }
+}
+
+fn update_tile_strength_rules_text(
+    mut texts: Query<&mut Text, With<TileStrengthRulesText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("{}", game_build_settings.tile_strength_rules);
+    }
+}
+
+fn on_off_text(enabled: bool) -> String {
+    match enabled {
+        true => String::from("On"),
+        false => String::from("Off"),
+    }
+}
+
+fn update_territory_decay_text(
+    mut texts: Query<&mut Text, With<TerritoryDecayText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = on_off_text(game_build_settings.territory_decay);
+    }
+}
+
+fn update_power_ups_text(
+    mut texts: Query<&mut Text, With<PowerUpsText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = on_off_text(game_build_settings.power_ups);
+    }
+}
+
+fn update_sudden_death_text(
+    mut texts: Query<&mut Text, With<SuddenDeathText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("{}", game_build_settings.sudden_death);
+    }
+}
+
+fn update_game_mode_text(
+    mut texts: Query<&mut Text, With<GameModeText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = format!("{}", game_build_settings.game_mode);
+    }
+}
+
+fn survival_hold_text(game_build_settings: &GameBuildSettings) -> String {
+    format!(
+        "{}% of the board",
+        game_build_settings.survival_hold_percent
+    )
+}
+
+fn update_survival_hold_text(
+    mut texts: Query<&mut Text, With<SurvivalHoldText>>,
+    game_build_settings: Res<GameBuildSettings>,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = survival_hold_text(&game_build_settings);
     }
 }
 
@@ -1101,7 +1833,9 @@ fn update_game_difficulty_text(
 
 fn update_color_swatches(
     mut colors: Query<(&ColorSwatch, &mut BackgroundColor)>,
+    mut vision_texts: Query<&mut Text, With<VisionText>>,
     player_colors: Res<PlayerColors>,
+    accessibility_settings: Res<AccessibilitySettings>,
     mut buttons: Query<
         (
             Entity,
@@ -1112,10 +1846,53 @@ fn update_color_swatches(
         ),
         Without<ColorSwatch>,
     >,
+    mut vision_buttons: Query<
+        (
+            Entity,
+            Option<&DisabledButton>,
+            Option<&PrevVisionButton>,
+            Option<&NextVisionButton>,
+            &mut BackgroundColor,
+        ),
+        (
+            Without<ColorSwatch>,
+            Without<PrevColorButton>,
+            Without<NextColorButton>,
+        ),
+    >,
     mut commands: Commands,
 ) {
+    let vision_preview = accessibility_settings.vision_preview;
+    for (entity, option_disabled_button, option_1, option_2, mut background_color) in
+        vision_buttons.iter_mut()
+    {
+        let enable_button = match (option_1, option_2) {
+            (Some(_), _) => vision_preview != vision_preview.prev(),
+            (_, Some(_)) => vision_preview != vision_preview.next(),
+            _ => continue,
+        };
+
+        match (enable_button, option_disabled_button) {
+            (true, Some(_)) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
+            }
+            (false, None) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
+            }
+            _ => {}
+        }
+    }
+
     for (color_swatch, mut background_color) in colors.iter_mut() {
-        background_color.0 = player_colors.get_color(color_swatch.0 as usize);
+        background_color.0 = accessibility_settings
+            .vision_preview
+            .simulate(player_colors.get_color(color_swatch.0 as usize));
+    }
+
+    for mut text in vision_texts.iter_mut() {
+        text.sections[0].value = vision_preview_text(&player_colors, &accessibility_settings);
     }
 
     for (entity, option_disabled_button, option_1, option_2, mut background_color) in
@@ -1169,6 +1946,19 @@ fn update_color_swatches(
     }
 }
 
+fn vision_preview_text(
+    player_colors: &PlayerColors,
+    accessibility_settings: &AccessibilitySettings,
+) -> String {
+    match player_colors.current_palette.colorblind_safe {
+        true => format!(
+            "{} - Colorblind Safe",
+            accessibility_settings.vision_preview
+        ),
+        false => format!("{}", accessibility_settings.vision_preview),
+    }
+}
+
 fn update_title_text_colors(
     player_colors: Option<Res<PlayerColors>>,
     mut interaction_query: Query<(Entity, &mut Text), (With<UpdateTitleColors>,)>,
@@ -1,25 +1,37 @@
+pub mod accessibility;
+mod campaign_menu;
+mod challenge_menu;
 mod components;
 mod dev;
 mod end_game;
 mod game;
 mod menu;
+mod palette_editor;
 mod pause;
+mod puzzle_menu;
 mod settings_menu;
 mod widgets;
 
-use crate::loading::colors_loader::{PalettesAssets, PalettesHandle};
+use crate::game::MAX_PLAYER_COUNT;
+use crate::loading::colors_loader::{load_user_palettes, PalettesAssets, PalettesHandle};
 use crate::loading::FontAssets;
+use crate::ui::campaign_menu::CampaignMenuPlugin;
+use crate::ui::challenge_menu::ChallengeMenuPlugin;
 use crate::ui::end_game::EndGameUiPlugin;
 use crate::ui::game::GameUiPlugin;
 use crate::ui::menu::MenuPlugin;
+use crate::ui::palette_editor::PaletteEditorPlugin;
 use crate::ui::pause::PauseUiPlugin;
+use crate::ui::puzzle_menu::PuzzleMenuPlugin;
 use bevy::prelude::*;
 use bevy::ui::FocusPolicy;
 use bevy::window::PrimaryWindow;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
 use bevy_tweening::lens::TransformScaleLens;
 use bevy_tweening::{Animator, EaseFunction, Lens, Lerp, RepeatCount, RepeatStrategy, Tween};
 use std::time::Duration;
 
+use self::accessibility::AccessibilitySettings;
 use self::dev::DevPlugin;
 use self::settings_menu::SettingsPlugin;
 
@@ -32,7 +44,13 @@ impl Plugin for UiPlugin {
             .add_plugin(PauseUiPlugin)
             .add_plugin(EndGameUiPlugin)
             .add_plugin(DevPlugin)
-            .add_plugin(SettingsPlugin);
+            .add_plugin(SettingsPlugin)
+            .add_plugin(PaletteEditorPlugin)
+            .add_plugin(CampaignMenuPlugin)
+            .add_plugin(PuzzleMenuPlugin)
+            .add_plugin(ChallengeMenuPlugin);
+
+        app.init_resource::<AccessibilitySettings>();
 
         app.add_systems((
             handle_button_visuals,
@@ -96,13 +114,19 @@ impl FromWorld for PlayerColors {
         for palette in palettes.palettes.iter() {
             player_colors.palettes.push(palette.clone());
         }
+        for palette in load_user_palettes() {
+            player_colors.palettes.push(palette);
+        }
         player_colors
     }
 }
 
 impl PlayerColors {
     pub fn get_color(&self, player_id: usize) -> Color {
-        return Color::hex(self.current_palette.player_colors[player_id].clone()).unwrap();
+        return match self.current_palette.player_colors.get(player_id) {
+            Some(color) => Color::hex(color.clone()).unwrap(),
+            None => generate_player_color(player_id),
+        };
     }
     pub fn next_palette(&mut self) {
         if self.palette_index.saturating_add(1) < self.palettes.len() {
@@ -120,13 +144,158 @@ impl PlayerColors {
     pub fn get_colorable(&self) -> Color {
         return Color::hex(self.current_palette.colorable_tile.clone()).unwrap();
     }
+
+    /// The background color of an unowned tile with the given terrain
+    pub fn get_terrain(&self, tile_terrain_info: &TileTerrainInfo) -> Color {
+        let hex = match tile_terrain_info.terrain_type.name.as_str() {
+            "Fertile" => &self.current_palette.fertile_tile,
+            "Hardened" => &self.current_palette.hardened_tile,
+            "Conduit" => &self.current_palette.conduit_tile,
+            _ => match tile_terrain_info.terrain_type.terrain_class.name.as_str() {
+                "NonColorable" => &self.current_palette.noncolorable_tile,
+                _ => &self.current_palette.colorable_tile,
+            },
+        };
+        Color::hex(hex).unwrap_or(Color::BLACK)
+    }
+
+    /// Stores an edited palette and makes it the current one. Edits to a user palette overwrite it,
+    /// edits to a built in palette are added as a new user palette
+    pub fn save_palette(&mut self, mut palette: Palette, editing_index: usize) {
+        palette.user_created = true;
+        match self.palettes.get(editing_index) {
+            Some(existing) if existing.user_created => {
+                self.palettes[editing_index] = palette;
+                self.palette_index = editing_index;
+            }
+            _ => {
+                self.palettes.push(palette);
+                self.palette_index = self.palettes.len() - 1;
+            }
+        }
+        self.current_palette = self.palettes[self.palette_index].clone();
+    }
+
+    pub fn user_palettes(&self) -> Vec<Palette> {
+        self.palettes
+            .iter()
+            .filter(|palette| palette.user_created)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Fallback for palettes that have fewer colors than there are players. Steps the hue by the golden
+/// angle so that each generated color stays distinct from the ones before it
+fn generate_player_color(player_id: usize) -> Color {
+    let hue = (player_id as f32 * 137.508) % 360.0;
+    Color::hsl(hue, 0.65, 0.55)
+}
+
+fn default_fertile_tile() -> String {
+    String::from("324c3d")
+}
+
+fn default_hardened_tile() -> String {
+    String::from("4b4754")
 }
 
-#[derive(Clone, serde::Deserialize)]
+fn default_conduit_tile() -> String {
+    String::from("2e4264")
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
 pub struct Palette {
     pub player_colors: Vec<String>,
     pub noncolorable_tile: String,
     pub colorable_tile: String,
+    #[serde(default = "default_fertile_tile")]
+    pub fertile_tile: String,
+    #[serde(default = "default_hardened_tile")]
+    pub hardened_tile: String,
+    #[serde(default = "default_conduit_tile")]
+    pub conduit_tile: String,
+    /// Whether the player colors stay distinguishable with common color vision deficiencies
+    #[serde(default)]
+    pub colorblind_safe: bool,
+    /// Palettes made in the palette editor. Only these get written to the user palettes file
+    #[serde(skip)]
+    pub user_created: bool,
+}
+
+impl Palette {
+    /// Parses a list of hex colors separated by spaces, commas or new lines. The list is the
+    /// player colors followed by the colorable tile and then the noncolorable tile color. The
+    /// special terrain colors aren't part of the list and start out as the defaults
+    pub fn from_hex_list(hex_list: &str) -> Result<Palette, String> {
+        let mut colors: Vec<String> = vec![];
+        for entry in hex_list.split(|c: char| c.is_whitespace() || c == ',' || c == ';') {
+            if trim_hex_color(entry).is_empty() {
+                continue;
+            }
+            colors.push(parse_hex_color(entry)?);
+        }
+
+        if colors.len() < 4 {
+            return Err(String::from(
+                "Need at least two player colors plus the two tile colors",
+            ));
+        }
+        if colors.len() > MAX_PLAYER_COUNT + 2 {
+            return Err(format!(
+                "At most {} player colors plus the two tile colors",
+                MAX_PLAYER_COUNT
+            ));
+        }
+
+        let noncolorable_tile = colors.pop().unwrap();
+        let colorable_tile = colors.pop().unwrap();
+        Ok(Palette {
+            player_colors: colors,
+            noncolorable_tile,
+            colorable_tile,
+            fertile_tile: default_fertile_tile(),
+            hardened_tile: default_hardened_tile(),
+            conduit_tile: default_conduit_tile(),
+            colorblind_safe: false,
+            user_created: true,
+        })
+    }
+
+    /// The palette as a hex list in the same order [`Palette::from_hex_list`] reads
+    pub fn to_hex_list(&self) -> String {
+        let mut colors = self.player_colors.clone();
+        colors.push(self.colorable_tile.clone());
+        colors.push(self.noncolorable_tile.clone());
+        colors.join(", ")
+    }
+}
+
+fn trim_hex_color(entry: &str) -> &str {
+    entry
+        .trim()
+        .trim_matches(|c: char| c == '#' || c == '"' || c == '\'')
+}
+
+/// Parses a single six digit hex color, optionally written with a leading `#` or in quotes, into
+/// the format palettes are stored in
+pub fn parse_hex_color(entry: &str) -> Result<String, String> {
+    let entry = trim_hex_color(entry);
+    if entry.len() != 6 || Color::hex(entry).is_err() {
+        return Err(format!("{} is not a hex color", entry));
+    }
+    Ok(entry.to_lowercase())
+}
+
+/// Converts a color into the six digit hex format palettes are stored in
+pub fn color_to_hex(color: Color) -> String {
+    let [r, g, b, _] = color.as_rgba_f32();
+    format!(
+        "{:02x}{:02x}{:02x}",
+        (r * 255.0).round() as u8,
+        (g * 255.0).round() as u8,
+        (b * 255.0).round() as u8
+    )
 }
 
 pub fn scale(
@@ -501,3 +670,61 @@ where
 
     inside_entity
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_list_round_trips() {
+        let palette =
+            Palette::from_hex_list("#FF0000, #00ff00\n'0000ff' \"ffffff\";000000").unwrap();
+        assert_eq!(palette.player_colors, vec!["ff0000", "00ff00", "0000ff"]);
+        assert_eq!(palette.colorable_tile, "ffffff");
+        assert_eq!(palette.noncolorable_tile, "000000");
+        assert_eq!(
+            Palette::from_hex_list(&palette.to_hex_list())
+                .unwrap()
+                .to_hex_list(),
+            palette.to_hex_list()
+        );
+    }
+
+    #[test]
+    fn hex_list_rejects_bad_hex() {
+        assert_eq!(
+            Palette::from_hex_list("ff0000 00gg00 ffffff 000000").err(),
+            Some(String::from("00gg00 is not a hex color"))
+        );
+    }
+
+    #[test]
+    fn hex_list_rejects_short_hex() {
+        assert_eq!(
+            Palette::from_hex_list("ff0000 0f0 ffffff 000000").err(),
+            Some(String::from("0f0 is not a hex color"))
+        );
+        assert_eq!(
+            Palette::from_hex_list("ff0000 00ff00ff ffffff 000000").err(),
+            Some(String::from("00ff00ff is not a hex color"))
+        );
+    }
+
+    #[test]
+    fn single_hex_color_parses() {
+        assert_eq!(parse_hex_color(" #A0B1C2 "), Ok(String::from("a0b1c2")));
+        assert_eq!(
+            parse_hex_color("a0b1c"),
+            Err(String::from("a0b1c is not a hex color"))
+        );
+    }
+
+    #[test]
+    fn hex_list_needs_two_players_and_both_tiles() {
+        assert!(Palette::from_hex_list("").is_err());
+        assert!(Palette::from_hex_list("ff0000 ffffff 000000").is_err());
+
+        let too_many = vec!["ff0000"; MAX_PLAYER_COUNT + 3].join(" ");
+        assert!(Palette::from_hex_list(&too_many).is_err());
+    }
+}
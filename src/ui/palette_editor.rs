@@ -0,0 +1,741 @@
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+
+use crate::game::MAX_PLAYER_COUNT;
+use crate::loading::colors_loader::save_user_palettes;
+use crate::loading::FontAssets;
+use crate::GameState;
+
+use super::menu::back_and_forth_button;
+use super::{
+    color_to_hex, generate_player_color, modal_panel, parse_hex_color, BasicButton, DisabledButton,
+    ModalStyle, Palette, PlayerColors,
+};
+
+pub struct PaletteEditorPlugin;
+
+impl Plugin for PaletteEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                handle_palette_editor_buttons,
+                handle_import_text_input,
+                update_palette_editor,
+                close_palette_editor,
+            )
+                .chain()
+                .in_set(OnUpdate(GameState::Menu)),
+        );
+        app.add_system(remove_palette_editor.in_schedule(OnExit(GameState::Menu)));
+    }
+}
+
+/// Sample board shown in the editor. Digits are owned by that player, `.` is colorable, `#` is
+/// noncolorable and `f`, `h` and `c` are fertile, hardened and conduit tiles
+const PREVIEW_BOARD: [&str; 8] = [
+    "000..111..22",
+    "00#..11#..22",
+    "0..f.1.hh.2.",
+    "..##.cc.##..",
+    "33.f..4..h.5",
+    "33#..44#..55",
+    "333..444.555",
+    "66..##..7777",
+];
+
+const PREVIEW_TILE_SIZE: f32 = 24.0;
+
+const MAX_IMPORT_TEXT_LENGTH: usize = 120;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaletteSlot {
+    Player(usize),
+    Colorable,
+    NonColorable,
+    Fertile,
+    Hardened,
+    Conduit,
+}
+
+impl PaletteSlot {
+    fn name(&self) -> String {
+        match self {
+            PaletteSlot::Player(player_id) => format!("Player {}", player_id + 1),
+            PaletteSlot::Colorable => String::from("Colorable Tile"),
+            PaletteSlot::NonColorable => String::from("NonColorable Tile"),
+            PaletteSlot::Fertile => String::from("Fertile Tile"),
+            PaletteSlot::Hardened => String::from("Hardened Tile"),
+            PaletteSlot::Conduit => String::from("Conduit Tile"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChannel {
+    Red,
+    Green,
+    Blue,
+}
+
+impl ColorChannel {
+    fn name(&self) -> &str {
+        match self {
+            ColorChannel::Red => "RED",
+            ColorChannel::Green => "GREEN",
+            ColorChannel::Blue => "BLUE",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            ColorChannel::Red => 0,
+            ColorChannel::Green => 1,
+            ColorChannel::Blue => 2,
+        }
+    }
+}
+
+/// The palette currently being edited. Inserted when the editor is opened
+#[derive(Resource, Clone)]
+pub struct PaletteEditor {
+    pub palette: Palette,
+    /// Index in [`PlayerColors::palettes`] of the palette that was opened in the editor
+    pub editing_index: usize,
+    pub selected_slot: PaletteSlot,
+    pub import_text: String,
+    pub status: String,
+}
+
+impl PaletteEditor {
+    pub fn new(player_colors: &PlayerColors) -> PaletteEditor {
+        let mut palette = player_colors.current_palette.clone();
+        fill_player_colors(&mut palette);
+        PaletteEditor {
+            palette,
+            editing_index: player_colors.palette_index,
+            selected_slot: PaletteSlot::Player(0),
+            import_text: String::new(),
+            status: String::new(),
+        }
+    }
+
+    pub fn slot_color(&self, slot: PaletteSlot) -> Color {
+        let hex = match slot {
+            PaletteSlot::Player(player_id) => &self.palette.player_colors[player_id],
+            PaletteSlot::Colorable => &self.palette.colorable_tile,
+            PaletteSlot::NonColorable => &self.palette.noncolorable_tile,
+            PaletteSlot::Fertile => &self.palette.fertile_tile,
+            PaletteSlot::Hardened => &self.palette.hardened_tile,
+            PaletteSlot::Conduit => &self.palette.conduit_tile,
+        };
+        Color::hex(hex).unwrap_or(Color::WHITE)
+    }
+
+    pub fn set_slot_color(&mut self, slot: PaletteSlot, color: Color) {
+        let hex = color_to_hex(color);
+        match slot {
+            PaletteSlot::Player(player_id) => self.palette.player_colors[player_id] = hex,
+            PaletteSlot::Colorable => self.palette.colorable_tile = hex,
+            PaletteSlot::NonColorable => self.palette.noncolorable_tile = hex,
+            PaletteSlot::Fertile => self.palette.fertile_tile = hex,
+            PaletteSlot::Hardened => self.palette.hardened_tile = hex,
+            PaletteSlot::Conduit => self.palette.conduit_tile = hex,
+        }
+    }
+
+    /// Moves one channel of the selected slot by `amount` out of 255
+    pub fn adjust_channel(&mut self, channel: ColorChannel, amount: i32) {
+        let mut rgba = self.slot_color(self.selected_slot).as_rgba_f32();
+        let value = (rgba[channel.index()] * 255.0).round() as i32;
+        rgba[channel.index()] = value.saturating_add(amount).clamp(0, 255) as f32 / 255.0;
+        self.set_slot_color(
+            self.selected_slot,
+            Color::rgba(rgba[0], rgba[1], rgba[2], rgba[3]),
+        );
+    }
+
+    fn channel_value(&self, channel: ColorChannel) -> u8 {
+        (self.slot_color(self.selected_slot).as_rgba_f32()[channel.index()] * 255.0).round() as u8
+    }
+}
+
+/// Pads the palette out to a color for every possible player so each one has a slot in the editor
+fn fill_player_colors(palette: &mut Palette) {
+    while palette.player_colors.len() < MAX_PLAYER_COUNT {
+        let player_id = palette.player_colors.len();
+        palette
+            .player_colors
+            .push(color_to_hex(generate_player_color(player_id)));
+    }
+}
+
+#[derive(Component)]
+struct PaletteEditorCloseButton;
+
+#[derive(Component)]
+struct PaletteSlotButton(PaletteSlot);
+
+#[derive(Component)]
+struct SelectedSlotText;
+
+#[derive(Component)]
+struct DecreaseChannelButton(ColorChannel);
+
+#[derive(Component)]
+struct IncreaseChannelButton(ColorChannel);
+
+#[derive(Component)]
+struct ChannelText(ColorChannel);
+
+#[derive(Component)]
+struct PreviewTile(char);
+
+#[derive(Component)]
+struct ImportText;
+
+#[derive(Component)]
+struct ImportButton;
+
+#[derive(Component)]
+struct SetColorButton;
+
+#[derive(Component)]
+struct CopyButton;
+
+#[derive(Component)]
+struct SavePaletteButton;
+
+#[derive(Component)]
+struct StatusText;
+
+fn editor_text(
+    text: impl Into<String>,
+    font_assets: &Res<FontAssets>,
+    font_size: f32,
+) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: font_assets.fira_sans.clone(),
+            font_size,
+            color: Color::GRAY,
+        },
+    )
+    .with_text_alignment(TextAlignment::Center)
+    .with_style(Style {
+        position_type: PositionType::Relative,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        margin: UiRect::all(Val::Px(5.0)),
+        size: Size::new(Val::Auto, Val::Auto),
+        ..default()
+    })
+}
+
+fn editor_button(
+    parent: &mut ChildBuilder,
+    font_assets: &Res<FontAssets>,
+    marker: impl Component,
+    button_text: &str,
+) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                size: Size::new(Val::Auto, Val::Px(50.0)),
+                margin: UiRect::all(Val::Px(10.0)),
+                padding: UiRect::all(Val::Px(10.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: BackgroundColor::from(Color::GRAY),
+            ..Default::default()
+        })
+        .insert(marker)
+        .insert(BasicButton)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                button_text,
+                TextStyle {
+                    font: font_assets.fira_sans.clone(),
+                    font_size: 40.0,
+                    color: Color::BLACK,
+                },
+            ));
+        });
+}
+
+fn row_node() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            size: Size::new(Val::Auto, Val::Auto),
+            position_type: PositionType::Relative,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Row,
+            margin: UiRect::all(Val::Px(5.0)),
+            ..default()
+        },
+        background_color: Color::rgba(0.0, 0.0, 0.0, 0.0).into(),
+        ..default()
+    }
+}
+
+pub fn spawn_palette_editor<MenuMarker: Component + Clone>(
+    menu_marker: MenuMarker,
+    mut commands: &mut Commands,
+    font_assets: &Res<FontAssets>,
+    player_colors: &PlayerColors,
+) {
+    let palette_editor = PaletteEditor::new(player_colors);
+
+    let modal_content = modal_panel(
+        menu_marker.clone(),
+        ModalStyle {
+            with_close_button: true,
+            close_button_bundle: Some(PaletteEditorCloseButton),
+            modal_size: Some(Size::new(Val::Percent(80.0), Val::Percent(95.0))),
+        },
+        &mut commands,
+        &font_assets,
+    );
+
+    commands.entity(modal_content).with_children(|parent| {
+        parent.spawn(editor_text("PALETTE EDITOR", font_assets, 55.0));
+
+        let mut slots: Vec<PaletteSlot> = (0..MAX_PLAYER_COUNT).map(PaletteSlot::Player).collect();
+        slots.push(PaletteSlot::Colorable);
+        slots.push(PaletteSlot::NonColorable);
+        slots.push(PaletteSlot::Fertile);
+        slots.push(PaletteSlot::Hardened);
+        slots.push(PaletteSlot::Conduit);
+
+        parent.spawn(row_node()).with_children(|parent| {
+            for slot in slots {
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(40.0), Val::Px(40.0)),
+                            margin: UiRect::all(Val::Px(5.0)),
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor::from(palette_editor.slot_color(slot)),
+                        ..Default::default()
+                    })
+                    .insert(PaletteSlotButton(slot));
+            }
+        });
+
+        parent
+            .spawn(editor_text(String::new(), font_assets, 40.0))
+            .insert(SelectedSlotText);
+
+        parent.spawn(row_node()).with_children(|parent| {
+            for channel in [ColorChannel::Red, ColorChannel::Green, ColorChannel::Blue] {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Auto, Val::Auto),
+                            position_type: PositionType::Relative,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            flex_direction: FlexDirection::Column,
+                            ..default()
+                        },
+                        background_color: Color::rgba(0.0, 0.0, 0.0, 0.0).into(),
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        back_and_forth_button(
+                            parent,
+                            font_assets,
+                            menu_marker.clone(),
+                            DecreaseChannelButton(channel),
+                            true,
+                            IncreaseChannelButton(channel),
+                            true,
+                            channel.name(),
+                        );
+                        parent
+                            .spawn(editor_text(String::new(), font_assets, 30.0))
+                            .insert(ChannelText(channel));
+                    });
+            }
+        });
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Auto, Val::Auto),
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::ColumnReverse,
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.0).into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                for row in PREVIEW_BOARD.iter().rev() {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Auto, Val::Auto),
+                                flex_direction: FlexDirection::Row,
+                                ..default()
+                            },
+                            background_color: Color::rgba(0.0, 0.0, 0.0, 0.0).into(),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for tile in row.chars() {
+                                parent.spawn((
+                                    NodeBundle {
+                                        style: Style {
+                                            size: Size::new(
+                                                Val::Px(PREVIEW_TILE_SIZE),
+                                                Val::Px(PREVIEW_TILE_SIZE),
+                                            ),
+                                            ..default()
+                                        },
+                                        background_color: BackgroundColor::from(
+                                            preview_tile_color(&palette_editor, tile),
+                                        ),
+                                        ..default()
+                                    },
+                                    PreviewTile(tile),
+                                ));
+                            }
+                        });
+                }
+            });
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(80.0), Val::Px(50.0)),
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    ..default()
+                },
+                background_color: Color::DARK_GRAY.into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(editor_text(String::new(), font_assets, 25.0))
+                    .insert(ImportText);
+            });
+
+        parent.spawn(row_node()).with_children(|parent| {
+            editor_button(parent, font_assets, ImportButton, "IMPORT");
+            editor_button(parent, font_assets, SetColorButton, "SET COLOR");
+            editor_button(parent, font_assets, CopyButton, "COPY");
+            editor_button(parent, font_assets, SavePaletteButton, "SAVE");
+        });
+
+        parent
+            .spawn(editor_text(String::new(), font_assets, 30.0))
+            .insert(StatusText);
+    });
+
+    commands.insert_resource(palette_editor);
+}
+
+fn preview_tile_color(palette_editor: &PaletteEditor, tile: char) -> Color {
+    match tile {
+        '#' => palette_editor.slot_color(PaletteSlot::NonColorable),
+        '.' => palette_editor.slot_color(PaletteSlot::Colorable),
+        'f' => palette_editor.slot_color(PaletteSlot::Fertile),
+        'h' => palette_editor.slot_color(PaletteSlot::Hardened),
+        'c' => palette_editor.slot_color(PaletteSlot::Conduit),
+        player_id => match player_id.to_digit(10) {
+            Some(player_id) if (player_id as usize) < MAX_PLAYER_COUNT => {
+                palette_editor.slot_color(PaletteSlot::Player(player_id as usize))
+            }
+            _ => palette_editor.slot_color(PaletteSlot::Colorable),
+        },
+    }
+}
+
+fn handle_palette_editor_buttons(
+    keyboard_input: Res<Input<KeyCode>>,
+    palette_editor: Option<ResMut<PaletteEditor>>,
+    mut player_colors: ResMut<PlayerColors>,
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&DisabledButton>,
+            Option<&PaletteSlotButton>,
+            Option<&DecreaseChannelButton>,
+            Option<&IncreaseChannelButton>,
+            Option<&ImportButton>,
+            Option<&SetColorButton>,
+            Option<&CopyButton>,
+            Option<&SavePaletteButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    let Some(mut palette_editor) = palette_editor else {
+        return;
+    };
+
+    for (
+        interaction,
+        option_disabled,
+        option_slot,
+        option_decrease,
+        option_increase,
+        option_import,
+        option_set_color,
+        option_copy,
+        option_save,
+    ) in interaction_query.iter()
+    {
+        if Interaction::Clicked != *interaction {
+            continue;
+        }
+
+        if option_disabled.is_some() {
+            continue;
+        }
+
+        let amount = match keyboard_input.pressed(KeyCode::LShift) {
+            true => 32,
+            false => 8,
+        };
+
+        if let Some(slot_button) = option_slot {
+            palette_editor.selected_slot = slot_button.0;
+        }
+
+        if let Some(decrease) = option_decrease {
+            palette_editor.adjust_channel(decrease.0, -amount);
+        }
+
+        if let Some(increase) = option_increase {
+            palette_editor.adjust_channel(increase.0, amount);
+        }
+
+        if option_import.is_some() {
+            match Palette::from_hex_list(&palette_editor.import_text) {
+                Ok(mut palette) => {
+                    fill_player_colors(&mut palette);
+                    // The hex list doesn't carry the special terrain colors so those are kept
+                    palette.fertile_tile = palette_editor.palette.fertile_tile.clone();
+                    palette.hardened_tile = palette_editor.palette.hardened_tile.clone();
+                    palette.conduit_tile = palette_editor.palette.conduit_tile.clone();
+                    palette_editor.palette = palette;
+                    palette_editor.import_text.clear();
+                    palette_editor.status = String::from("Imported palette");
+                }
+                Err(error) => palette_editor.status = error,
+            }
+        }
+
+        // Sets the selected slot to the single hex color typed into the import box
+        if option_set_color.is_some() {
+            match parse_hex_color(&palette_editor.import_text) {
+                Ok(hex) => {
+                    let slot = palette_editor.selected_slot;
+                    palette_editor.set_slot_color(slot, Color::hex(&hex).unwrap());
+                    palette_editor.import_text.clear();
+                    palette_editor.status = format!("Set {} to #{}", slot.name(), hex);
+                }
+                Err(error) => palette_editor.status = error,
+            }
+        }
+
+        if option_copy.is_some() {
+            palette_editor.status = match write_clipboard(palette_editor.palette.to_hex_list()) {
+                Ok(_) => String::from("Copied palette as a hex list"),
+                Err(error) => error,
+            };
+        }
+
+        if option_save.is_some() {
+            player_colors
+                .save_palette(palette_editor.palette.clone(), palette_editor.editing_index);
+            palette_editor.editing_index = player_colors.palette_index;
+            palette_editor.status = match save_user_palettes(player_colors.user_palettes()) {
+                Ok(_) => String::from("Saved palette"),
+                Err(error) => format!("Palette kept for this session only: {}", error),
+            };
+        }
+    }
+}
+
+/// The modal despawns itself when closed, the palette being edited has to be dropped with it
+fn close_palette_editor(
+    interaction_query: Query<
+        &Interaction,
+        (
+            Changed<Interaction>,
+            With<Button>,
+            With<PaletteEditorCloseButton>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked)
+    {
+        commands.remove_resource::<PaletteEditor>();
+    }
+}
+
+fn remove_palette_editor(mut commands: Commands) {
+    commands.remove_resource::<PaletteEditor>();
+}
+
+/// Typing into the import box. Ctrl+V pastes from the clipboard
+fn handle_import_text_input(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    palette_editor: Option<ResMut<PaletteEditor>>,
+    import_text: Query<&ImportText>,
+) {
+    let Some(mut palette_editor) = palette_editor else {
+        received_characters.clear();
+        return;
+    };
+    if import_text.is_empty() {
+        received_characters.clear();
+        return;
+    }
+
+    let control = keyboard_input.pressed(KeyCode::LControl)
+        || keyboard_input.pressed(KeyCode::RControl)
+        || keyboard_input.pressed(KeyCode::LWin)
+        || keyboard_input.pressed(KeyCode::RWin);
+
+    if control && keyboard_input.just_pressed(KeyCode::V) {
+        match read_clipboard() {
+            Ok(text) => palette_editor.import_text.push_str(&text),
+            Err(error) => palette_editor.status = error,
+        }
+    } else {
+        for received_character in received_characters.iter() {
+            let character = received_character.char;
+            if character.is_ascii_hexdigit() || matches!(character, '#' | ',' | ' ') {
+                palette_editor.import_text.push(character);
+            }
+        }
+    }
+    received_characters.clear();
+
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        palette_editor.import_text.pop();
+    }
+
+    if palette_editor.import_text.len() > MAX_IMPORT_TEXT_LENGTH {
+        palette_editor.import_text.truncate(MAX_IMPORT_TEXT_LENGTH);
+    }
+}
+
+fn update_palette_editor(
+    palette_editor: Option<Res<PaletteEditor>>,
+    mut texts: Query<
+        (
+            &mut Text,
+            Option<&SelectedSlotText>,
+            Option<&ChannelText>,
+            Option<&ImportText>,
+            Option<&StatusText>,
+        ),
+        Or<(
+            With<SelectedSlotText>,
+            With<ChannelText>,
+            With<ImportText>,
+            With<StatusText>,
+        )>,
+    >,
+    mut tiles: Query<
+        (
+            &mut BackgroundColor,
+            &mut Style,
+            Option<&PaletteSlotButton>,
+            Option<&PreviewTile>,
+        ),
+        Or<(With<PaletteSlotButton>, With<PreviewTile>)>,
+    >,
+) {
+    let Some(palette_editor) = palette_editor else {
+        return;
+    };
+    if !palette_editor.is_changed() {
+        return;
+    }
+
+    for (mut text, option_selected, option_channel, option_import, option_status) in
+        texts.iter_mut()
+    {
+        if option_selected.is_some() {
+            text.sections[0].value = format!(
+                "{} - #{}",
+                palette_editor.selected_slot.name(),
+                color_to_hex(palette_editor.slot_color(palette_editor.selected_slot))
+            );
+        }
+        if let Some(channel_text) = option_channel {
+            text.sections[0].value = format!("{}", palette_editor.channel_value(channel_text.0));
+        }
+        if option_import.is_some() {
+            text.sections[0].value = match palette_editor.import_text.is_empty() {
+                true => String::from(
+                    "Type or paste hex colors: players, tile, noncolorable. Or one color to set",
+                ),
+                false => palette_editor.import_text.clone(),
+            };
+        }
+        if option_status.is_some() {
+            text.sections[0].value = palette_editor.status.clone();
+        }
+    }
+
+    for (mut background_color, mut style, option_slot, option_preview) in tiles.iter_mut() {
+        if let Some(slot_button) = option_slot {
+            background_color.0 = palette_editor.slot_color(slot_button.0);
+            let size = match slot_button.0 == palette_editor.selected_slot {
+                true => 50.0,
+                false => 40.0,
+            };
+            style.size = Size::new(Val::Px(size), Val::Px(size));
+        }
+        if let Some(preview_tile) = option_preview {
+            background_color.0 = preview_tile_color(&palette_editor, preview_tile.0);
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn read_clipboard() -> Result<String, String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn read_clipboard() -> Result<String, String> {
+    Err(String::from(
+        "Clipboard isn't available on the web, type it in instead",
+    ))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) fn write_clipboard(text: String) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(super) fn write_clipboard(_text: String) -> Result<(), String> {
+    Err(String::from("Clipboard isn't available on the web"))
+}
@@ -9,6 +9,7 @@ use bevy::app::AppExit;
 use bevy::prelude::CoreSet::Update;
 use bevy::prelude::*;
 
+use super::accessibility::AccessibilitySettings;
 use super::settings_menu::spawn_settings_menu;
 
 pub struct PauseUiPlugin;
@@ -259,6 +260,7 @@ fn button_interaction(
     >,
     font_assets: Res<FontAssets>,
     sound_settings: Res<GameSoundSettings>,
+    accessibility_settings: Res<AccessibilitySettings>,
 ) {
     for (
         _,
@@ -306,6 +308,7 @@ fn button_interaction(
                 &font_assets,
                 &player_colors,
                 &sound_settings,
+                &accessibility_settings,
             );
         }
     }
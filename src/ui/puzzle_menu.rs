@@ -0,0 +1,290 @@
+use bevy::prelude::*;
+
+use crate::game::survival::format_survival_time;
+use crate::game::GameBuildSettings;
+use crate::loading::puzzle_loader::{Puzzle, PuzzleHandle, Puzzles};
+use crate::loading::FontAssets;
+use crate::GameState;
+
+use super::menu::back_and_forth_button;
+use super::{modal_panel, BasicButton, DisabledButton, ModalStyle};
+
+pub struct PuzzleMenuPlugin;
+
+impl Plugin for PuzzleMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (handle_puzzle_menu_buttons, update_puzzle_menu)
+                .chain()
+                .in_set(OnUpdate(GameState::Menu)),
+        );
+    }
+}
+
+/// The puzzle currently shown in the puzzle menu. Inserted when the menu is opened
+#[derive(Resource)]
+pub struct PuzzleMenu {
+    pub selected_puzzle: usize,
+}
+
+#[derive(Component)]
+struct PuzzleCloseButton;
+
+#[derive(Component)]
+struct NextPuzzleButton;
+
+#[derive(Component)]
+struct PrevPuzzleButton;
+
+#[derive(Component)]
+struct PlayPuzzleButton;
+
+#[derive(Component)]
+struct PuzzleNameText;
+
+#[derive(Component)]
+struct PuzzleDescriptionText;
+
+#[derive(Component)]
+struct PuzzleDetailsText;
+
+fn puzzle_text(
+    text: impl Into<String>,
+    font_assets: &Res<FontAssets>,
+    font_size: f32,
+) -> TextBundle {
+    TextBundle::from_section(
+        text,
+        TextStyle {
+            font: font_assets.fira_sans.clone(),
+            font_size,
+            color: Color::GRAY,
+        },
+    )
+    .with_text_alignment(TextAlignment::Center)
+    .with_style(Style {
+        position_type: PositionType::Relative,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        margin: UiRect::all(Val::Px(5.0)),
+        size: Size::new(Val::Auto, Val::Auto),
+        max_size: Size::new(Val::Percent(90.0), Val::Auto),
+        ..default()
+    })
+}
+
+pub fn spawn_puzzle_menu<MenuMarker: Component + Clone>(
+    menu_marker: MenuMarker,
+    mut commands: &mut Commands,
+    font_assets: &Res<FontAssets>,
+    selected_puzzle: usize,
+) {
+    let modal_content = modal_panel(
+        menu_marker.clone(),
+        ModalStyle {
+            with_close_button: true,
+            close_button_bundle: None::<PuzzleCloseButton>,
+            modal_size: Some(Size::new(Val::Percent(70.0), Val::Percent(80.0))),
+        },
+        &mut commands,
+        &font_assets,
+    );
+
+    commands.entity(modal_content).with_children(|parent| {
+        parent.spawn(puzzle_text("PUZZLES", font_assets, 55.0));
+
+        back_and_forth_button(
+            parent,
+            font_assets,
+            menu_marker.clone(),
+            PrevPuzzleButton,
+            true,
+            NextPuzzleButton,
+            true,
+            "PUZZLE",
+        );
+
+        parent
+            .spawn(puzzle_text(String::new(), font_assets, 45.0))
+            .insert(PuzzleNameText);
+        parent
+            .spawn(puzzle_text(String::new(), font_assets, 30.0))
+            .insert(PuzzleDescriptionText);
+        parent
+            .spawn(puzzle_text(String::new(), font_assets, 30.0))
+            .insert(PuzzleDetailsText);
+
+        parent
+            .spawn(ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Auto, Val::Px(50.0)),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    padding: UiRect::all(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: BackgroundColor::from(Color::GRAY),
+                ..Default::default()
+            })
+            .insert(menu_marker)
+            .insert(PlayPuzzleButton)
+            .insert(BasicButton)
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "PLAY PUZZLE",
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: Color::BLACK,
+                    },
+                ));
+            });
+    });
+
+    commands.insert_resource(PuzzleMenu { selected_puzzle });
+}
+
+fn puzzle_details(puzzle: &Puzzle) -> String {
+    let enemies = match puzzle.enemy_count() {
+        1 => String::from("1 enemy"),
+        count => format!("{} enemies", count),
+    };
+    format!(
+        "{} - Capture the marked region within {}\nInventory: {}",
+        enemies,
+        format_survival_time(puzzle.time_limit_seconds as f32),
+        puzzle.inventory
+    )
+}
+
+fn handle_puzzle_menu_buttons(
+    mut state: ResMut<NextState<GameState>>,
+    puzzle_menu: Option<ResMut<PuzzleMenu>>,
+    puzzle_handle: Res<PuzzleHandle>,
+    puzzle_assets: Res<Assets<Puzzles>>,
+    mut game_build_settings: ResMut<GameBuildSettings>,
+    interaction_query: Query<
+        (
+            &Interaction,
+            Option<&DisabledButton>,
+            Option<&NextPuzzleButton>,
+            Option<&PrevPuzzleButton>,
+            Option<&PlayPuzzleButton>,
+        ),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    let Some(mut puzzle_menu) = puzzle_menu else {
+        return;
+    };
+    let Some(puzzles) = puzzle_assets.get(&puzzle_handle.puzzles) else {
+        return;
+    };
+
+    for (interaction, option_disabled, option_next, option_prev, option_play) in
+        interaction_query.iter()
+    {
+        if Interaction::Clicked != *interaction {
+            continue;
+        }
+
+        if option_disabled.is_some() {
+            continue;
+        }
+
+        if option_next.is_some() {
+            puzzle_menu.selected_puzzle =
+                (puzzle_menu.selected_puzzle + 1).min(puzzles.puzzles.len().saturating_sub(1));
+        }
+
+        if option_prev.is_some() {
+            puzzle_menu.selected_puzzle = puzzle_menu.selected_puzzle.saturating_sub(1);
+        }
+
+        if option_play.is_some() {
+            let Some(puzzle) = puzzles.puzzles.get(puzzle_menu.selected_puzzle) else {
+                continue;
+            };
+            game_build_settings.start_puzzle(puzzle_menu.selected_puzzle, puzzle);
+            state.set(GameState::Playing);
+        }
+    }
+}
+
+fn update_puzzle_menu(
+    puzzle_menu: Option<Res<PuzzleMenu>>,
+    puzzle_handle: Res<PuzzleHandle>,
+    puzzle_assets: Res<Assets<Puzzles>>,
+    mut texts: Query<
+        (
+            &mut Text,
+            Option<&PuzzleNameText>,
+            Option<&PuzzleDescriptionText>,
+            Option<&PuzzleDetailsText>,
+        ),
+        Or<(
+            With<PuzzleNameText>,
+            With<PuzzleDescriptionText>,
+            With<PuzzleDetailsText>,
+        )>,
+    >,
+    mut buttons: Query<
+        (
+            Entity,
+            &mut BackgroundColor,
+            Option<&DisabledButton>,
+            Option<&NextPuzzleButton>,
+            Option<&PrevPuzzleButton>,
+        ),
+        Or<(With<NextPuzzleButton>, With<PrevPuzzleButton>)>,
+    >,
+    mut commands: Commands,
+) {
+    let Some(puzzle_menu) = puzzle_menu else {
+        return;
+    };
+    if !puzzle_menu.is_changed() {
+        return;
+    }
+    let Some(puzzles) = puzzle_assets.get(&puzzle_handle.puzzles) else {
+        return;
+    };
+    let puzzle_index = puzzle_menu.selected_puzzle;
+    let Some(puzzle) = puzzles.puzzles.get(puzzle_index) else {
+        return;
+    };
+
+    for (mut text, option_name, option_description, option_details) in texts.iter_mut() {
+        if option_name.is_some() {
+            text.sections[0].value = format!("{}. {}", puzzle_index + 1, puzzle.name);
+        }
+        if option_description.is_some() {
+            text.sections[0].value = puzzle.description.clone();
+        }
+        if option_details.is_some() {
+            text.sections[0].value = puzzle_details(puzzle);
+        }
+    }
+
+    for (entity, mut background_color, option_disabled, option_next, option_prev) in
+        buttons.iter_mut()
+    {
+        let enabled = match (option_next, option_prev) {
+            (Some(_), _) => puzzle_index + 1 < puzzles.puzzles.len(),
+            (_, Some(_)) => puzzle_index > 0,
+            _ => continue,
+        };
+        match (enabled, option_disabled.is_some()) {
+            (true, true) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
+            }
+            (false, false) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
+            }
+            _ => {}
+        }
+    }
+}
@@ -4,7 +4,8 @@ use bevy::{
         bundle::Bundle,
         component::Component,
         entity::Entity,
-        query::{Changed, With},
+        query::{Changed, Or, With},
+        schedule::{common_conditions::resource_changed, IntoSystemConfig},
         system::{Commands, Query, Res, ResMut},
     },
     hierarchy::BuildChildren,
@@ -24,13 +25,17 @@ use crate::{
     loading::FontAssets,
 };
 
-use super::{menu::back_and_forth_button, modal_panel, DisabledButton, ModalStyle, PlayerColors};
+use super::{
+    accessibility::AccessibilitySettings, menu::back_and_forth_button, modal_panel, DisabledButton,
+    ModalStyle, PlayerColors,
+};
 
 pub struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_systems((handle_buttons, update_sound_level));
+        app.add_system(update_patterns_setting.run_if(resource_changed::<AccessibilitySettings>()));
     }
 }
 
@@ -43,6 +48,22 @@ pub struct IncreaseSoundButton;
 #[derive(Component)]
 pub struct SoundText;
 
+#[derive(Component)]
+pub struct DisablePatternsButton;
+
+#[derive(Component)]
+pub struct EnablePatternsButton;
+
+#[derive(Component)]
+pub struct PatternsText;
+
+fn patterns_text(accessibility_settings: &AccessibilitySettings) -> String {
+    match accessibility_settings.ownership_patterns {
+        true => String::from("On"),
+        false => String::from("Off"),
+    }
+}
+
 pub fn spawn_settings_menu<MenuMarker: Component + Clone, CloseButtonBundle: Bundle>(
     menu_marker: MenuMarker,
     modal_style: ModalStyle<CloseButtonBundle>,
@@ -50,6 +71,7 @@ pub fn spawn_settings_menu<MenuMarker: Component + Clone, CloseButtonBundle: Bun
     font_assets: &Res<FontAssets>,
     player_colors: &PlayerColors,
     sound_settings: &GameSoundSettings,
+    accessibility_settings: &AccessibilitySettings,
 ) {
     let modal_content = modal_panel(
         menu_marker.clone(),
@@ -65,7 +87,7 @@ pub fn spawn_settings_menu<MenuMarker: Component + Clone, CloseButtonBundle: Bun
         back_and_forth_button(
             parent,
             &font_assets,
-            menu_marker,
+            menu_marker.clone(),
             DecreaseSoundButton,
             backward,
             IncreaseSoundButton,
@@ -94,6 +116,39 @@ pub fn spawn_settings_menu<MenuMarker: Component + Clone, CloseButtonBundle: Bun
                 }),
             )
             .insert(SoundText);
+
+        back_and_forth_button(
+            parent,
+            &font_assets,
+            menu_marker,
+            DisablePatternsButton,
+            accessibility_settings.ownership_patterns,
+            EnablePatternsButton,
+            !accessibility_settings.ownership_patterns,
+            "Patterns",
+        );
+
+        parent
+            .spawn(
+                TextBundle::from_section(
+                    patterns_text(accessibility_settings),
+                    TextStyle {
+                        font: font_assets.fira_sans.clone(),
+                        font_size: 40.0,
+                        color: Color::GRAY,
+                    },
+                )
+                .with_text_alignment(TextAlignment::Center)
+                .with_style(Style {
+                    position_type: PositionType::Relative,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(5.0)),
+                    size: Size::new(Val::Auto, Val::Auto),
+                    ..default()
+                }),
+            )
+            .insert(PatternsText);
     });
 }
 
@@ -107,12 +162,17 @@ fn handle_buttons(
             Option<&DisabledButton>,
             Option<&DecreaseSoundButton>,
             Option<&IncreaseSoundButton>,
+            Option<&DisablePatternsButton>,
+            Option<&EnablePatternsButton>,
         ),
         (Changed<Interaction>, With<Button>),
     >,
     mut sound_settings: ResMut<GameSoundSettings>,
+    mut accessibility_settings: ResMut<AccessibilitySettings>,
 ) {
-    for (_, interaction, option_disabled, opt_dsb, opt_isb) in &mut interaction_query {
+    for (_, interaction, option_disabled, opt_dsb, opt_isb, opt_dpb, opt_epb) in
+        &mut interaction_query
+    {
         if Interaction::Clicked != *interaction {
             continue;
         }
@@ -130,6 +190,10 @@ fn handle_buttons(
         if opt_isb.is_some() {
             sound_settings.increase_sound_level();
         }
+
+        if opt_dpb.is_some() || opt_epb.is_some() {
+            accessibility_settings.toggle_ownership_patterns();
+        }
     }
 }
 
@@ -199,3 +263,45 @@ fn update_sound_level(
         }
     }
 }
+
+fn update_patterns_setting(
+    mut texts: Query<&mut Text, With<PatternsText>>,
+    mut buttons: Query<
+        (
+            Entity,
+            Option<&DisabledButton>,
+            Option<&DisablePatternsButton>,
+            Option<&EnablePatternsButton>,
+            &mut BackgroundColor,
+        ),
+        Or<(With<DisablePatternsButton>, With<EnablePatternsButton>)>,
+    >,
+    accessibility_settings: Res<AccessibilitySettings>,
+    mut commands: Commands,
+) {
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = patterns_text(&accessibility_settings);
+    }
+
+    for (entity, option_disabled_button, option_1, option_2, mut background_color) in
+        buttons.iter_mut()
+    {
+        let enable_button = match (option_1, option_2) {
+            (Some(_), _) => accessibility_settings.ownership_patterns,
+            (_, Some(_)) => !accessibility_settings.ownership_patterns,
+            _ => continue,
+        };
+
+        match (enable_button, option_disabled_button) {
+            (true, Some(_)) => {
+                background_color.0 = Color::GRAY;
+                commands.entity(entity).remove::<DisabledButton>();
+            }
+            (false, None) => {
+                background_color.0 = Color::DARK_GRAY;
+                commands.entity(entity).insert(DisabledButton);
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,122 @@
+pub mod painter;
+
+use crate::buildings::building_pathfinding::PathfindStrengthExt;
+use crate::units::painter::Painter;
+use bevy::prelude::{
+    Commands, Component, Entity, FromReflect, Query, Reflect, With, Without, World,
+};
+use bevy_ecs_tilemap::prelude::{TilePos, TileStorage};
+use bevy_ggf::game_core::change_detection::DespawnObject;
+use bevy_ggf::game_core::saving::{BinaryComponentId, SaveId};
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::object::{Object, ObjectGridPosition};
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Eq, Hash, Debug, PartialEq, Component, Reflect, FromReflect)]
+pub struct UnitMarker;
+
+/// An object that moves around the map instead of staying where it was placed
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Unit<T> {
+    pub unit_type: T,
+}
+
+impl<T> PathfindStrengthExt for Unit<T>
+where
+    T: PathfindStrengthExt,
+{
+    fn pathfinding_strength(&self) -> u32 {
+        self.unit_type.pathfinding_strength()
+    }
+
+    fn tile_step_cost(&self, world: &World, tile_entity: Entity) -> Option<u32> {
+        self.unit_type.tile_step_cost(world, tile_entity)
+    }
+}
+
+impl SaveId for Unit<Painter> {
+    fn save_id(&self) -> BinaryComponentId {
+        26
+    }
+
+    fn save_id_const() -> BinaryComponentId
+    where
+        Self: Sized,
+    {
+        26
+    }
+
+    #[doc = r" Serializes the state of the object at the given tick into binary. Only saves the keyframe and not the curve itself"]
+    fn to_binary(&self) -> Option<Vec<u8>> {
+        bincode::serialize(self).ok()
+    }
+}
+
+/// Destroys units standing on a tile that is owned by another player or that can't be colored
+pub fn destroy_units(
+    units: Query<
+        (
+            Entity,
+            &PlayerMarker,
+            &ObjectGridPosition,
+            &ObjectStackingClass,
+        ),
+        (With<Object>, With<UnitMarker>, Without<DespawnObject>),
+    >,
+    mut tiles: Query<
+        (
+            &TileTerrainInfo,
+            Option<&PlayerMarker>,
+            &mut TileObjectStacks,
+        ),
+        (Without<Object>, With<Tile>),
+    >,
+    tile_storage_query: Query<(&MapId, &TileStorage)>,
+    mut commands: Commands,
+) {
+    let Some((_, tile_storage)) = tile_storage_query
+        .iter()
+        .find(|(id, _)| id == &&MapId { id: 1 })
+    else {
+        return;
+    };
+
+    for (unit_entity, player_marker, object_grid_pos, object_stacking_class) in units.iter() {
+        let Some(tile_entity) =
+            tile_storage.get(&Into::<TilePos>::into(object_grid_pos.tile_position))
+        else {
+            continue;
+        };
+        let Ok((tile_terrain_info, tile_marker, mut tile_object_stacks)) =
+            tiles.get_mut(tile_entity)
+        else {
+            continue;
+        };
+
+        let destroy_unit = match tile_marker {
+            Some(tile_marker) => tile_marker != player_marker,
+            None => false,
+        } || tile_terrain_info.terrain_type.terrain_class.name.as_str()
+            == "NonColorable";
+
+        if destroy_unit {
+            commands.entity(unit_entity).insert(DespawnObject);
+            tile_object_stacks.decrement_object_class_count(object_stacking_class);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use crate::buildings::building_pathfinding::{
+    PathfindStrengthExt, SimplePathfindMap, SimplePathfinder,
+};
+use crate::buildings::{Activate, Simulate};
+use crate::color_system::{
+    ColorConflictCallback, ColorConflictGuarantees, ConflictType, TileColor,
+};
+use crate::game::GameData;
+use crate::mapping::map::MapTileStorage;
+use crate::pathfinding::{IsColorableNodeCheck, NodeIsNotEnemyCheck};
+use crate::units::Unit;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::{Component, Entity, FromReflect, Query, Reflect, With, Without, World};
+use bevy_ecs_tilemap::prelude::TilePos;
+use bevy_ggf::game_core::command::{GameCommand, GameCommands};
+use bevy_ggf::game_core::state::Changed;
+use bevy_ggf::mapping::terrain::TileTerrainInfo;
+use bevy_ggf::mapping::tiles::{ObjectStackingClass, Tile, TileObjectStacks};
+use bevy_ggf::mapping::MapId;
+use bevy_ggf::movement::{MovementType, TerrainMovementCosts, TileMoveCheckMeta, TileMoveChecks};
+use bevy_ggf::object::{ObjectGridPosition, ObjectId};
+use bevy_ggf::pathfinding::PathfindAlgorithm;
+use bevy_ggf::player::PlayerMarker;
+use serde::{Deserialize, Serialize};
+
+/// A unit that walks towards the closest neutral or weak tile of its player and colors every tile
+/// it stands on
+#[derive(
+    Default,
+    Clone,
+    Eq,
+    Hash,
+    Debug,
+    PartialEq,
+    Component,
+    Reflect,
+    FromReflect,
+    Serialize,
+    Deserialize,
+)]
+pub struct Painter {
+    /// How far away the painter looks for tiles to move to
+    pub sight_range: u32,
+}
+
+impl Painter {
+    /// The movement type painters are given costs for in the `TileMovementCosts` of every
+    /// terrain they can walk on
+    pub fn movement_type() -> MovementType {
+        MovementType {
+            name: String::from("Painter"),
+        }
+    }
+}
+
+impl PathfindStrengthExt for Painter {
+    fn pathfinding_strength(&self) -> u32 {
+        self.sight_range
+    }
+
+    /// Painters use the movement costs of the terrain. Terrain without a cost for painters can't
+    /// be walked on
+    fn tile_step_cost(&self, world: &World, tile_entity: Entity) -> Option<u32> {
+        let tile_terrain_info = world.get::<TileTerrainInfo>(tile_entity)?;
+        world
+            .resource::<TerrainMovementCosts>()
+            .movement_cost_rules
+            .get(&tile_terrain_info.terrain_type)?
+            .movement_type_cost
+            .get(&Painter::movement_type())
+            .copied()
+    }
+}
+
+/// Every time a painter activates it colors the tile it is on and then takes one step along the
+/// path to the tile it wants to paint next
+pub fn simulate_painters(world: &mut World) {
+    let mut system_state: SystemState<
+        Query<
+            (Entity, &ObjectId, &PlayerMarker, &ObjectGridPosition),
+            (
+                With<Unit<Painter>>,
+                With<Activate>,
+                With<Simulate>,
+                Without<MapId>,
+            ),
+        >,
+    > = SystemState::new(world);
+    let painters: Vec<(Entity, ObjectId, usize, TilePos)> = system_state
+        .get(world)
+        .iter()
+        .map(|(entity, object_id, player_marker, object_grid_position)| {
+            (
+                entity,
+                object_id.clone(),
+                player_marker.id(),
+                object_grid_position.tile_position.into(),
+            )
+        })
+        .collect();
+
+    // Painters can only walk on colorable tiles that aren't owned by another player
+    let mut tile_move_checks = TileMoveChecks {
+        tile_move_checks: vec![
+            TileMoveCheckMeta {
+                check: Box::new(IsColorableNodeCheck),
+            },
+            TileMoveCheckMeta {
+                check: Box::new(NodeIsNotEnemyCheck),
+            },
+        ],
+    };
+    let mut pathfind = SimplePathfinder::<Unit<Painter>>::default();
+
+    for (entity, object_id, player_id, tile_pos) in painters {
+        world.entity_mut(entity).remove::<Activate>();
+
+        world.send_event(ColorConflictGuarantees {
+            tile_pos,
+            casting_player: player_id,
+            affect_casting_player: true,
+            affect_neutral: true,
+            affect_other_players: false,
+            conflict_type: ConflictType::Natural,
+        });
+
+        let mut pathfind_map = SimplePathfindMap::<Unit<Painter>>::default();
+        pathfind.pathfind(
+            MapId { id: 1 },
+            entity,
+            world,
+            &mut tile_move_checks,
+            &mut None::<ColorConflictCallback>,
+            &mut pathfind_map,
+        );
+
+        let Some(next_tile_pos) = next_painter_step(tile_pos, &pathfind_map, world) else {
+            continue;
+        };
+
+        let mut game_commands = GameCommands::new();
+        if game_commands
+            .move_object(object_id, MapId { id: 1 }, tile_pos, next_tile_pos)
+            .execute(world)
+            .is_ok()
+        {
+            world.entity_mut(entity).insert(Changed::default());
+        }
+    }
+}
+
+/// Finds the closest reachable neutral tile, or the closest of the players own tiles that isn't
+/// fully strengthened if there are none, and returns the first step towards it
+fn next_painter_step(
+    start: TilePos,
+    pathfind_map: &SimplePathfindMap<Unit<Painter>>,
+    world: &mut World,
+) -> Option<TilePos> {
+    let unit_stacking_class = ObjectStackingClass {
+        stack_class: world
+            .resource::<GameData>()
+            .stacking_classes
+            .get("Unit")
+            .unwrap()
+            .clone(),
+    };
+    let tile_storage = world.resource::<MapTileStorage>().tile_storage.clone();
+
+    let mut system_state: SystemState<
+        Query<(Option<&PlayerMarker>, Option<&TileColor>, &TileObjectStacks), With<Tile>>,
+    > = SystemState::new(world);
+    let tiles = system_state.get(world);
+
+    let mut target: Option<(u32, u32, TilePos)> = None;
+    for (tile_pos, node) in pathfind_map.map.iter() {
+        if !node.valid_move || *tile_pos == start {
+            continue;
+        }
+        let Some(tile_entity) = tile_storage.get(tile_pos) else {
+            continue;
+        };
+        let Ok((tile_player_marker, tile_color, _)) = tiles.get(tile_entity) else {
+            continue;
+        };
+
+        let priority = match (tile_player_marker, tile_color) {
+            (None, _) => 0,
+            (Some(_), Some(tile_color)) if !tile_color.max_strength() => 1,
+            _ => continue,
+        };
+
+        // Ties are broken by position so every simulation of the game picks the same tile
+        if target.map_or(true, |(target_priority, target_cost, target_pos)| {
+            (priority, node.move_cost, tile_pos.x, tile_pos.y)
+                < (target_priority, target_cost, target_pos.x, target_pos.y)
+        }) {
+            target = Some((priority, node.move_cost, *tile_pos));
+        }
+    }
+
+    let (_, _, mut step) = target?;
+    for _ in 0..pathfind_map.map.len() {
+        let node = pathfind_map.map.get(&step)?;
+        if node.prior_node_pos == start {
+            break;
+        }
+        step = node.prior_node_pos;
+    }
+
+    let tile_entity = tile_storage.get(&step)?;
+    let (_, _, tile_object_stacks) = tiles.get(tile_entity).ok()?;
+    if !tile_object_stacks.has_space(&unit_stacking_class) {
+        return None;
+    }
+    Some(step)
+}